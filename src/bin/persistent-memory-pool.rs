@@ -0,0 +1,255 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+#![deny(missing_docs)]
+
+
+//! # persistent-memory-pool
+//!
+//! Administers CTO pools of persistent memory.
+//!
+//! * `persistent-memory-pool create <path> <layout-name> <size>` creates a new pool; `size` may end in `K`, `M`, `G` or `T` and is ignored for a pool set file or DAX device.
+//! * `persistent-memory-pool info <path> <layout-name>` prints the header, size, usage and how the pool was last shut down.
+//! * `persistent-memory-pool check <path> <layout-name>` checks the integrity of the pool, printing anything found to be wrong.
+//! * `persistent-memory-pool dump <path> <layout-name>` prints the raw header and heap metadata.
+//! * `persistent-memory-pool dax` lists the DAX devices in `/dev`.
+//!
+//! Exits with `0` on success, `1` if there was an error or `check` found something wrong, and `2` if the arguments were not understood.
+
+
+extern crate persistent_memory;
+
+
+use ::persistent_memory::cto_pool::CtoPoolPathExt;
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::persistent_memory::dax::DaxDevicePathExt;
+use ::std::env::args;
+use ::std::ffi::CString;
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::fs::read_dir;
+use ::std::path::Path;
+use ::std::process::exit;
+
+
+const Success: i32 = 0;
+
+const Failure: i32 = 1;
+
+const Usage: i32 = 2;
+
+const CreatedPoolMode: u32 = 0o600;
+
+
+fn main()
+{
+	let arguments: Vec<String> = args().collect();
+	let program_name = arguments.get(0).map(|argument| argument.as_str()).unwrap_or("persistent-memory-pool");
+	let subcommand = arguments.get(1).map(|argument| argument.as_str());
+	let path = || Path::new(&arguments[2]);
+	let layout_name = || layout_name_c_string(&arguments[3]);
+	
+	let exit_code = match (subcommand, arguments.len())
+	{
+		(Some("create"), 5) => match parse_size(&arguments[4])
+		{
+			Err(reason) =>
+			{
+				eprintln!("{:?}: {}", arguments[4], reason);
+				usage(program_name)
+			}
+			Ok(size) => create(path(), &layout_name(), size),
+		},
+		(Some("info"), 4) => info(path(), &layout_name()),
+		(Some("check"), 4) => check(path(), &layout_name()),
+		(Some("dump"), 4) => dump(path(), &layout_name()),
+		(Some("dax"), 2) => dax(),
+		_ => usage(program_name),
+	};
+	exit(exit_code)
+}
+
+fn usage(program_name: &str) -> i32
+{
+	eprintln!("Usage:");
+	eprintln!("	{} create <path> <layout-name> <size>", program_name);
+	eprintln!("	{} info <path> <layout-name>", program_name);
+	eprintln!("	{} check <path> <layout-name>", program_name);
+	eprintln!("	{} dump <path> <layout-name>", program_name);
+	eprintln!("	{} dax", program_name);
+	Usage
+}
+
+fn create(path: &Path, layout_name: &CString, size: usize) -> i32
+{
+	match path.create_empty_cto_pool(layout_name, size, CreatedPoolMode)
+	{
+		Err(error) => failed(path, error),
+		Ok(false) =>
+		{
+			eprintln!("{:?}: there is already a pool", path);
+			Failure
+		}
+		Ok(true) => Success,
+	}
+}
+
+fn info(path: &Path, layout_name: &CString) -> i32
+{
+	let information = match path.cto_pool_information(layout_name)
+	{
+		Err(error) => return failed(path, error),
+		Ok(information) => information,
+	};
+	
+	println!("format version: {}", information.format_version);
+	println!("uuid: {}", information.uuid.iter().map(|byte| format!("{:02x}", byte)).collect::<String>());
+	println!("layout name: {}", information.layout_name);
+	println!("creation size: {}", information.creation_size);
+	println!("size: {}", information.size);
+	println!("mapped base address: {:#x}", information.mapped_base_address);
+	println!("last shutdown: {:?}", information.last_shutdown);
+	if information.root_address == 0
+	{
+		println!("root: none");
+	}
+	else
+	{
+		println!("root: {:#x} {:?}", information.root_address, information.root_type_fingerprint);
+	}
+	println!("named roots: {}", information.named_roots.len());
+	for &(ref name, ref type_fingerprint) in information.named_roots.iter()
+	{
+		println!("	{}: {:?}", name, type_fingerprint);
+	}
+	
+	let statistics = information.statistics;
+	println!("total size: {}", statistics.total_size);
+	println!("allocated size: {}", statistics.allocated_size);
+	println!("free size: {}", statistics.free_size);
+	println!("number of allocations: {}", statistics.number_of_allocations);
+	println!("largest free extent: {}", statistics.largest_free_extent);
+	println!("fragmentation: {:.3}", statistics.fragmentation());
+	Success
+}
+
+fn check(path: &Path, layout_name: &CString) -> i32
+{
+	match path.check_cto_pool(layout_name)
+	{
+		Err(error) => failed(path, error),
+		Ok(findings) =>
+		{
+			for finding in findings.iter()
+			{
+				println!("{}", finding);
+			}
+			
+			if findings.is_empty()
+			{
+				Success
+			}
+			else
+			{
+				Failure
+			}
+		}
+	}
+}
+
+fn dump(path: &Path, layout_name: &CString) -> i32
+{
+	match path.dump_cto_pool(layout_name)
+	{
+		Err(error) => failed(path, error),
+		Ok(dump) =>
+		{
+			print!("{}", dump);
+			Success
+		}
+	}
+}
+
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+fn dax() -> i32
+{
+	let entries = match read_dir("/dev")
+	{
+		Err(error) => return failed(Path::new("/dev"), error),
+		Ok(entries) => entries,
+	};
+	
+	let mut dax_devices: Vec<_> = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|path| path.is_this_a_dax_device()).collect();
+	dax_devices.sort();
+	
+	for dax_device in dax_devices.iter()
+	{
+		println!("{}", dax_device.display());
+		
+		match dax_device.find_dax_device_size()
+		{
+			Err(error) => println!("	size: unknown ({})", error),
+			Ok(size) => println!("	size: {}", size),
+		}
+		
+		match dax_device.find_dax_device_alignment()
+		{
+			Err(error) => println!("	alignment: unknown ({})", error),
+			Ok(alignment) => println!("	alignment: {}", alignment),
+		}
+		
+		match dax_device.find_dax_device_region_id()
+		{
+			Err(error) => println!("	region id: unknown ({})", error),
+			Ok(region_id) => println!("	region id: {}", region_id),
+		}
+		
+		match dax_device.find_nvdimm_unsafe_shutdown_count()
+		{
+			Err(error) => println!("	unsafe shutdown count: unknown ({})", error),
+			Ok(unsafe_shutdown_count) => println!("	unsafe shutdown count: {}", unsafe_shutdown_count),
+		}
+	}
+	Success
+}
+
+#[cfg(not(any(target_os = "android", target_os = "freebsd", target_os = "linux")))]
+fn dax() -> i32
+{
+	eprintln!("DAX devices are not supported on this platform");
+	Failure
+}
+
+fn failed<E: ::std::fmt::Display>(path: &Path, error: E) -> i32
+{
+	eprintln!("{:?}: {}", path, error);
+	Failure
+}
+
+fn layout_name_c_string(layout_name: &str) -> CString
+{
+	CString::new(layout_name).unwrap_or_else(|_|
+	{
+		eprintln!("layout name '{}' contains a NUL", layout_name);
+		exit(Usage)
+	})
+}
+
+// Sizes are calculated as u64, as a size in terabytes does not fit in a 32-bit usize.
+fn parse_size(size: &str) -> Result<usize, &'static str>
+{
+	let (digits, multiplier): (&str, u64) = match size.chars().last()
+	{
+		Some('K') | Some('k') => (&size[.. size.len() - 1], 1 << 10),
+		Some('M') | Some('m') => (&size[.. size.len() - 1], 1 << 20),
+		Some('G') | Some('g') => (&size[.. size.len() - 1], 1 << 30),
+		Some('T') | Some('t') => (&size[.. size.len() - 1], 1 << 40),
+		_ => (size, 1),
+	};
+	
+	let number = digits.parse::<u64>().map_err(|_| "is not a size")?;
+	let size = number.checked_mul(multiplier).ok_or("is too large")?;
+	if size > usize::max_value() as u64
+	{
+		return Err("is too large for this platform")
+	}
+	Ok(size as usize)
+}
@@ -20,9 +20,26 @@ trait Allocator
 	fn alloc_trait_free(self, pointer_to_free: *mut u8);
 	
 	#[inline(always)]
-	fn map_allocation_result(_allocation_result: Result<*mut c_void, PmdkError>, _error_message: &'static str, request: &Layout) -> Result<*mut u8, AllocErr>
+	fn map_allocation_result(allocation_result: Result<*mut c_void, PmdkError>, error_message: &'static str, request: &Layout) -> Result<*mut u8, AllocErr>
 	{
-		Err(AllocErr::Exhausted { request: request.clone() })
+		use self::PmdkError::*;
+		
+		match allocation_result
+		{
+			Ok(allocation_pointer) => Ok(allocation_pointer as *mut u8),
+			
+			Err(OutOfSpace(_)) => Err(AllocErr::Exhausted { request: request.clone() }),
+			
+			Err(BadAlignment(_)) => Err(AllocErr::invalid_input("alignment is not a power of two")),
+			
+			Err(PoolClosed) => Err(AllocErr::Unsupported { details: "CTO pool is closed" }),
+			
+			Err(ReadOnly) => Err(AllocErr::Unsupported { details: "CTO pool is read-only" }),
+			
+			Err(CorruptMetadata(..)) => Err(AllocErr::Unsupported { details: "CTO pool metadata is corrupt" }),
+			
+			Err(_) => Err(AllocErr::Unsupported { details: error_message }),
+		}
 	}
 }
 
@@ -35,7 +52,7 @@ impl Allocator for *mut PMEMctopool
 		
 		match self.aligned_allocate::<P::PersistentMemory>()
 		{
-			Err(allocation_error) => return Err(CtoPoolAllocationError::Allocation(allocation_error)),
+			Err(allocation_error) => return Err(allocation_error.into()),
 			
 			Ok(persistent_memory_pointer) => match unsafe { P::initialize_persistent_memory(persistent_memory_pointer, cto_pool_arc, initializer) }
 			{
@@ -66,7 +83,7 @@ impl Allocator for *mut PMEMctopool
 	{
 		debug_assert!(self.is_not_null(), "self is null");
 		
-		Self::map_allocation_result(self.aligned_alloc(layout.align(), layout.size()), "PMDK libpmemcto.pmemcto_aligned_alloc failed", layout)
+		Self::map_allocation_result(self.aligned_alloc(layout.align(), layout.size()), "CTO pool aligned_alloc failed", layout)
 	}
 	
 	#[inline(always)]
@@ -87,7 +104,7 @@ impl Allocator for *mut PMEMctopool
 			}
 			else
 			{
-				Self::map_allocation_result(self.realloc(old_pointer as *mut _, new_size), "PMDK libpmemcto.pmemcto_realloc failed", new_layout)
+				Self::map_allocation_result(self.realloc(old_pointer as *mut _, new_size), "CTO pool realloc failed", new_layout)
 			}
 		}
 		else
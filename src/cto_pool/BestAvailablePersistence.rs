@@ -0,0 +1,23 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Persists using the best cache line write back and fence instructions supported by the CPU this process is running on, chosen once at runtime (see `CacheLineWriteBack::best_available()`).
+/// Suitable for DAX devices and files mapped with `MAP_SYNC` when one binary has to run on a mix of CPUs.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct BestAvailablePersistence;
+
+impl Persistence for BestAvailablePersistence
+{
+	#[inline(always)]
+	fn flush_memory(address: *mut c_void, length: usize)
+	{
+		Self::for_each_cache_line(address, length, persistent_write_back)
+	}
+	
+	#[inline(always)]
+	fn drain_memory()
+	{
+		persistent_fence()
+	}
+}
@@ -0,0 +1,31 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Persists using the `clflush` instruction, which writes back and evicts cache lines.
+/// `clflush` is serialized, so there is nothing to drain; it is, however, slow, and should only be used on CPUs without `clflushopt` or `clwb`.
+///
+/// The CPU must support `clflush`; see `CacheLineWriteBack::Clflush.is_available()`.
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct ClflushPersistence;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+impl Persistence for ClflushPersistence
+{
+	#[inline(always)]
+	fn flush_memory(address: *mut c_void, length: usize)
+	{
+		debug_assert!(CacheLineWriteBack::Clflush.is_available(), "clflush is not supported by this CPU");
+		
+		#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))] Self::for_each_cache_line(address, length, |cache_line_address| record_write_back(cache_line_address as usize));
+		
+		Self::for_each_cache_line(address, length, clflush)
+	}
+	
+	#[inline(always)]
+	fn drain_memory()
+	{
+		#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))] record_fence();
+	}
+}
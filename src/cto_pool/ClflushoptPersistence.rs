@@ -0,0 +1,33 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Persists using the `clflushopt` instruction, which writes back and evicts cache lines, followed by an `sfence`.
+/// Suitable for DAX devices and files mapped with `MAP_SYNC` on CPUs from Skylake and Zen v1 onwards.
+///
+/// The CPU must support `clflushopt`; see `CacheLineWriteBack::Clflushopt.is_available()`.
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct ClflushoptPersistence;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+impl Persistence for ClflushoptPersistence
+{
+	#[inline(always)]
+	fn flush_memory(address: *mut c_void, length: usize)
+	{
+		debug_assert!(CacheLineWriteBack::Clflushopt.is_available(), "clflushopt is not supported by this CPU");
+		
+		#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))] Self::for_each_cache_line(address, length, |cache_line_address| record_write_back(cache_line_address as usize));
+		
+		Self::for_each_cache_line(address, length, clflushopt)
+	}
+	
+	#[inline(always)]
+	fn drain_memory()
+	{
+		sfence();
+		
+		#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))] record_fence();
+	}
+}
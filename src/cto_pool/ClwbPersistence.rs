@@ -0,0 +1,33 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Persists using the `clwb` instruction, which writes back cache lines without evicting them, followed by an `sfence`.
+/// The best choice for DAX devices and files mapped with `MAP_SYNC` on CPUs from Cannonlake or Icelake onwards.
+///
+/// The CPU must support `clwb`; see `CacheLineWriteBack::Clwb.is_available()`.
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct ClwbPersistence;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+impl Persistence for ClwbPersistence
+{
+	#[inline(always)]
+	fn flush_memory(address: *mut c_void, length: usize)
+	{
+		debug_assert!(CacheLineWriteBack::Clwb.is_available(), "clwb is not supported by this CPU");
+		
+		#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))] Self::for_each_cache_line(address, length, |cache_line_address| record_write_back(cache_line_address as usize));
+		
+		Self::for_each_cache_line(address, length, clwb)
+	}
+	
+	#[inline(always)]
+	fn drain_memory()
+	{
+		sfence();
+		
+		#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))] record_fence();
+	}
+}
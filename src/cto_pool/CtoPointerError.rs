@@ -0,0 +1,25 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+quick_error!
+{
+	/// Reason for a pointer being rejected by a checked constructor or setter (eg `CtoArcCell::checked_set()`) or by `CtoPoolRegistry::check_pointer()`; such a pointer would dangle once the pool is reopened.
+	#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	pub enum CtoPointerError
+	{
+		/// A pointer (the target) to be stored in an open pool (at the location) does not point into any open pool; it probably points to volatile memory, eg the stack or the heap of the process.
+		VolatileTarget(location: usize, target: usize)
+		{
+			description("Pointer to be stored in a pool points to volatile memory")
+			display("Pointer '0x{:x}' to be stored at '0x{:x}' in a pool does not point into an open pool; it probably points to volatile memory", target, location)
+		}
+		
+		/// A pointer (the target) to be stored in an open pool (at the location) points into a different open pool.
+		DifferentPool(location: usize, target: usize)
+		{
+			description("Pointer to be stored in a pool points into a different pool")
+			display("Pointer '0x{:x}' to be stored at '0x{:x}' in a pool points into a different pool", target, location)
+		}
+	}
+}
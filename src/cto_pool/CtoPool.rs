@@ -6,7 +6,9 @@
 /// Unlike the system `malloc`, multiple instances of it can be created, one for each bank of Persistent memory.
 /// And, it has a graph 'root'.
 /// To access the 'root' of the graph, use `deref()` or `deref_mut()`.
+/// It can also have named roots of any type; see `root()`.
 /// Persistence does not happen successfully until this object is closed (dropped).
+/// Whether the previous close was clean can be found with `last_shutdown()` after opening.
 /// Dropping only occurs when there are not more instances of `CtoPoolArc`.
 pub struct CtoPool<RootValue: CtoSafe>(CtoPoolAlloc, PhantomData<RootValue>);
 
@@ -116,8 +118,24 @@ impl<RootValue: CtoSafe> CtoPool<RootValue>
 {
 	/// Opens a pool, creating it if necessary, and re-initializing any memory that is volatile (eg condition variables, mutex locks, etc).
 	/// If the pool does not contain a root, then it is initialized using `root_value_initializer`.
+	/// If it does, but the root is of a different type (see `TypeFingerprint`), then `CtoPoolOpenError::DifferentRootType` is returned.
 	#[inline(always)]
 	pub fn open<InitializationError: error::Error, RootValueInitializer: FnOnce(&mut RootValue, &CtoPoolArc) -> Result<(), InitializationError>>(pool_set_file_path: &Path, layout_name: &str, pool_size: usize, mode: mode_t, root_value_initializer: RootValueInitializer) -> Result<Self, CtoPoolOpenError<InitializationError>>
+	{
+		Self::open_with_migrations(pool_set_file_path, layout_name, pool_size, mode, &CtoPoolMigrations::default(), root_value_initializer)
+	}
+	
+	/// As `open()`, but if the pool's root has an older schema version than `RootValue` (see `CtoSafe::schema_version()`), it is first migrated to `RootValue` using `migrations`.
+	#[inline(always)]
+	pub fn open_with_migrations<InitializationError: error::Error, RootValueInitializer: FnOnce(&mut RootValue, &CtoPoolArc) -> Result<(), InitializationError>>(pool_set_file_path: &Path, layout_name: &str, pool_size: usize, mode: mode_t, migrations: &CtoPoolMigrations<RootValue, InitializationError>, root_value_initializer: RootValueInitializer) -> Result<Self, CtoPoolOpenError<InitializationError>>
+	{
+		Self::open_with_named_roots(pool_set_file_path, layout_name, pool_size, mode, migrations, &CtoPoolNamedRoots::default(), root_value_initializer)
+	}
+	
+	/// As `open_with_migrations()`, but for a pool with named roots, whose types are registered in `named_roots`.
+	/// If the pool has been mapped at a different address to the one it was last mapped at, its named roots are relocated along with its root; if any of them is not registered (or is registered as a different type), `CtoPoolOpenError::NamedRootsNotRelocated` is returned and nothing is relocated.
+	#[inline(always)]
+	pub fn open_with_named_roots<InitializationError: error::Error, RootValueInitializer: FnOnce(&mut RootValue, &CtoPoolArc) -> Result<(), InitializationError>>(pool_set_file_path: &Path, layout_name: &str, pool_size: usize, mode: mode_t, migrations: &CtoPoolMigrations<RootValue, InitializationError>, named_roots: &CtoPoolNamedRoots, root_value_initializer: RootValueInitializer) -> Result<Self, CtoPoolOpenError<InitializationError>>
 	{
 		let layout_name = CString::new(layout_name).expect("Embedded NULs are not allowed in a layout name");
 		let length = layout_name.as_bytes().len();
@@ -155,16 +173,31 @@ impl<RootValue: CtoSafe> CtoPool<RootValue>
 			},
 		};
 		
+		// Dropping this closes the pool if opening it fails from here on.
 		let cto_pool_arc = CtoPoolArc::new(pool_pointer);
 		
+		if let Some(schema_version) = pool_pointer.pool().root_schema_version_if_older::<RootValue>()
+		{
+			migrations.migrate(&cto_pool_arc, schema_version, named_roots)?;
+		}
+		
+		// Must be checked before the root is relocated, as relocation assumes the root is a `RootValue`.
+		if let Err(found) = pool_pointer.pool().check_root_type::<RootValue>()
+		{
+			return Err(CtoPoolOpenError::DifferentRootType { expected: TypeFingerprint::of::<RootValue>(), found })
+		}
+		
+		pool_pointer.relocate_if_moved::<RootValue>(named_roots).map_err(CtoPoolOpenError::OpenFailed)?.map_err(CtoPoolOpenError::NamedRootsNotRelocated)?;
+		
 		let cto_pool_alloc: CtoPool<RootValue> = CtoPool(CtoPoolAlloc(cto_pool_arc), PhantomData);
 		
 		let existing_root = pool_pointer.get_root();
 		if existing_root.is_null()
 		{
-			let new_root = cto_pool_alloc.pool_pointer().aligned_allocate::<RootValue>().map_err(|pmdk_error| CtoPoolOpenError::RootCreation(CtoPoolAllocationError::Allocation(pmdk_error)))?;
+			let new_root = cto_pool_alloc.pool_pointer().aligned_allocate::<RootValue>().map_err(|pmdk_error| CtoPoolOpenError::RootCreation(pmdk_error.into()))?;
 			let root = unsafe { &mut * (new_root as *mut RootValue) };
 			root_value_initializer(root, cto_pool_alloc.allocator()).map_err(|initialization_error| CtoPoolOpenError::RootCreation(CtoPoolAllocationError::Initialization(initialization_error)))?;
+			pool_pointer.pool().record_root_type_fingerprint(TypeFingerprint::of::<RootValue>());
 			pool_pointer.set_root(new_root);
 		}
 		else
@@ -176,6 +209,122 @@ impl<RootValue: CtoSafe> CtoPool<RootValue>
 		Ok(cto_pool_alloc)
 	}
 	
+	/// How the pool was last shut down before it was opened.
+	/// If this requires a consistency check, `CtoPoolPathExt::validate_cto_pool_is_consistent()` should be used before trusting the pool's contents.
+	#[inline(always)]
+	pub fn last_shutdown(&self) -> LastShutdown
+	{
+		self.pool_pointer().last_shutdown()
+	}
+	
+	/// A snapshot of how the pool's memory is used.
+	#[inline(always)]
+	pub fn statistics(&self) -> CtoPoolStatistics
+	{
+		self.pool_pointer().statistics()
+	}
+	
+	/// Finds everything allocated in the pool that can not be reached from its root, and frees it; returns what was freed.
+	/// Leaks occur if a process crashes after allocating something but before linking it into the pool's objects (eg in `BlockAllocator::allocate_chains()`).
+	/// Everything reachable is marked by walking the pool (see `CtoPoolTraversal`), and every allocation in the heap and every block in a reachable `BlockAllocator` that was not marked is freed.
+	///
+	/// Nothing is freed, and an error is returned, if checking the pool finds a problem (see `ReadOnlyCtoPool::check()`), if the pool has named roots (use `collect_leaks_with()` for a pool with named roots), or if a reachable `BlockAllocator` is used through a `BlockAllocatorAlloc`, as the chains it hands out can not be walked.
+	/// Every `CtoSafe` type in the pool must implement `cto_pool_traverse()`, as anything reachable only through a type that does not is freed; likewise, blocks from `BlockAllocator::allocate_chain()` are freed, as only `Chains` are walked.
+	/// No other thread may use the pool while this runs, as anything it has allocated but not yet linked would be freed; it is best called straight after opening the pool.
+	#[inline(always)]
+	pub fn collect_leaks(&self) -> Result<CtoPoolLeaks, PmdkError>
+	{
+		self.collect_leaks_with(|_cto_pool_traversal| {})
+	}
+	
+	/// As `collect_leaks()`, but `traverse_named_roots` is also called to walk named roots, as only it knows their types.
+	/// It must walk every named root, by passing a pointer to each, as obtained from `root()`, to `CtoPoolTraversal::traverse()`.
+	#[inline(always)]
+	pub fn collect_leaks_with<TraverseNamedRoots: FnOnce(&CtoPoolTraversal)>(&self, traverse_named_roots: TraverseNamedRoots) -> Result<CtoPoolLeaks, PmdkError>
+	{
+		let pool = self.pool_pointer().pool();
+		let cto_pool_traversal = pool.traversal()?;
+		cto_pool_traversal.traverse(self.pool_pointer().get_root::<RootValue>() as *const RootValue);
+		traverse_named_roots(&cto_pool_traversal);
+		pool.collect_leaks(&cto_pool_traversal)
+	}
+	
+	/// The named root `name`, if there is one.
+	/// Named roots let independent parts of a program each keep their own root in one pool, alongside the root of type `RootValue`.
+	/// Returns an error if the named root was inserted as a different type.
+	///
+	/// The first time a named root is used after the pool is opened, `cto_pool_opened()` is called on it.
+	/// A pool with named roots must be opened with `open_with_named_roots()` if it might have moved, as only then can its named roots be relocated.
+	#[inline(always)]
+	pub fn root<T: CtoSafe>(&self, name: &str) -> Result<Option<&T>, RootDirectoryError>
+	{
+		let root = self.pool_pointer().pool().named_root::<T>(name, self.allocator())?;
+		Ok(if root.is_null()
+		{
+			None
+		}
+		else
+		{
+			Some(unsafe { & * root })
+		})
+	}
+	
+	/// As `root()`, but mutable.
+	#[inline(always)]
+	pub fn root_mut<T: CtoSafe>(&mut self, name: &str) -> Result<Option<&mut T>, RootDirectoryError>
+	{
+		let root = self.pool_pointer().pool().named_root::<T>(name, self.allocator())?;
+		Ok(if root.is_null()
+		{
+			None
+		}
+		else
+		{
+			Some(unsafe { &mut * root })
+		})
+	}
+	
+	/// Inserts `root_value` as the named root `name`, recording its type fingerprint.
+	/// Names can be up to `MaximumNamedRootNameLength` bytes long, and a pool can have up to `MaximumNumberOfNamedRoots`.
+	#[inline(always)]
+	pub fn insert_root<T: CtoSafe>(&self, name: &str, root_value: T) -> Result<(), RootDirectoryError>
+	{
+		self.pool_pointer().pool().insert_named_root(name, root_value)
+	}
+	
+	/// Removes the named root `name`, dropping and freeing it; returns `false` if there is no such named root.
+	/// Returns an error if the named root was inserted as a different type.
+	#[inline(always)]
+	pub fn remove_root<T: CtoSafe>(&mut self, name: &str) -> Result<bool, RootDirectoryError>
+	{
+		self.pool_pointer().pool().remove_named_root::<T>(name, self.allocator())
+	}
+	
+	/// The names and type fingerprints of all named roots.
+	#[inline(always)]
+	pub fn list_roots(&self) -> Vec<(String, TypeFingerprint)>
+	{
+		self.pool_pointer().pool().named_roots()
+	}
+	
+	/// Grows the pool, while it is open, by at least `additional_size` bytes; the memory it grows by is immediately available for allocations.
+	/// `pool_set_file_path` must be the path the pool was opened with.
+	/// See `CtoPoolPathExt::grow_cto_pool()`.
+	#[inline(always)]
+	pub fn grow(&self, pool_set_file_path: &Path, additional_size: usize) -> Result<(), PmdkError>
+	{
+		pool_set_file_path.grow_cto_pool(self.pool_pointer(), additional_size)
+	}
+	
+	/// Grows the pool, while it is open, by adding a new part, `part_path`, of `part_size` bytes to the end of a pool set file; the memory it grows by is immediately available for allocations.
+	/// `pool_set_file_path` must be the path of the pool set file the pool was opened with.
+	/// See `CtoPoolPathExt::grow_cto_pool_by_adding_part()`.
+	#[inline(always)]
+	pub fn grow_by_adding_part(&self, pool_set_file_path: &Path, part_path: &Path, part_size: usize, mode: mode_t) -> Result<(), PmdkError>
+	{
+		pool_set_file_path.grow_cto_pool_by_adding_part(self.pool_pointer(), part_path, part_size, mode)
+	}
+	
 	/// Returns an object that can be used for allocations.
 	#[inline(always)]
 	pub fn allocator(&self) -> &CtoPoolArc
@@ -190,6 +339,14 @@ impl<RootValue: CtoSafe> CtoPool<RootValue>
 		&self.0
 	}
 	
+	/// Closes the pool even if objects (eg in the pool) still refer to it; see `VolatileCtoPoolFile::reopen()`.
+	#[inline(always)]
+	pub(crate) unsafe fn close_regardless_of_references(self)
+	{
+		let CtoPool(cto_pool_alloc, _) = self;
+		cto_pool_alloc.0.close_regardless_of_references()
+	}
+	
 	#[inline(always)]
 	fn pool_pointer(&self) -> *mut PMEMctopool
 	{
@@ -13,6 +13,24 @@ impl CtoSafe for CtoPoolAlloc
 	{
 		cto_pool_arc.write(&mut self.0);
 	}
+	
+	// The `CtoPoolArc` is rewritten by `cto_pool_opened()`.
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	// The `CtoPoolArc` points to the pool, not into it; what is allocated through this is walked by whatever holds it.
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::CtoPoolAlloc")
+	}
 }
 
 impl PartialEq for CtoPoolAlloc
@@ -22,13 +22,22 @@ impl<InitializationError: Display> Display for CtoPoolAllocationError<Initializa
 		
 		match *self
 		{
-			Allocation(ref pmdk_error) => Display::fmt(pmdk_error, formatter),
+			Allocation(ref pmdk_error) => write!(formatter, "Could not allocate: {}", pmdk_error),
 			
-			Initialization(ref initialization_error) => Display::fmt(initialization_error, formatter),
+			Initialization(ref initialization_error) => write!(formatter, "Could not initialize: {}", initialization_error),
 		}
 	}
 }
 
+impl<InitializationError> From<PmdkError> for CtoPoolAllocationError<InitializationError>
+{
+	#[inline(always)]
+	fn from(pmdk_error: PmdkError) -> Self
+	{
+		CtoPoolAllocationError::Allocation(pmdk_error)
+	}
+}
+
 impl<InitializationError: error::Error> error::Error for CtoPoolAllocationError<InitializationError>
 {
 	#[inline(always)]
@@ -55,6 +55,14 @@ impl CtoPoolArc
 		unsafe { write(location, self.clone()) };
 	}
 	
+	/// Closes the pool even if there are other references to it, which must then never be used or dropped; see `VolatileCtoPoolFile::reopen()`.
+	#[inline(always)]
+	pub(crate) unsafe fn close_regardless_of_references(self)
+	{
+		self.cto_pool_arc_inner.as_ref().close_regardless_of_references();
+		forget(self)
+	}
+	
 	/// Pointer to CTO pool from FFI `libpmemcto`.
 	#[inline(always)]
 	pub fn pool_pointer(&self) -> *mut PMEMctopool
@@ -62,6 +70,13 @@ impl CtoPoolArc
 		unsafe { self.cto_pool_arc_inner.as_ref() }.pool_pointer
 	}
 	
+	/// A snapshot of how the pool's memory is used.
+	#[inline(always)]
+	pub fn statistics(&self) -> CtoPoolStatistics
+	{
+		self.pool_pointer().statistics()
+	}
+	
 	/// Allocate a CtoString, which is similar to a Rust String but uses the persistent memory pool instead of the system allocator.
 	/// Returns on success a CtoString.
 	#[inline(always)]
@@ -209,9 +224,13 @@ impl CtoPoolArc
 	{
 		let cto_pool_alloc_arc = Box::new(CtoPoolArcInner::new(pool_pointer));
 		
-		Self
+		let this = Self
 		{
 			cto_pool_arc_inner: Box::into_raw_non_null(cto_pool_alloc_arc).into(),
-		}
+		};
+		
+		CtoPoolRegistry::register_cto_pool_arc(pool_pointer, this.cto_pool_arc_inner);
+		
+		this
 	}
 }
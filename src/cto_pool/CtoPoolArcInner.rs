@@ -29,6 +29,34 @@ impl CtoPoolArcInner
 		self.reference_counter.fetch_add(1, SeqCst);
 	}
 	
+	/// Acquires a reference unless there are none left, ie the pool is being closed, in which case returns 'false'.
+	#[inline(always)]
+	fn try_acquire_reference(&self) -> bool
+	{
+		let mut reference_counter = self.reference_counter.load(SeqCst);
+		loop
+		{
+			if reference_counter == 0
+			{
+				return false
+			}
+			
+			match self.reference_counter.compare_exchange(reference_counter, reference_counter + 1, SeqCst, SeqCst)
+			{
+				Ok(_) => return true,
+				Err(was) => reference_counter = was,
+			}
+		}
+	}
+	
+	/// The remaining references are never released, and so this is never freed; try_acquire_reference() then fails, as for a pool being closed.
+	#[inline(always)]
+	fn close_regardless_of_references(&self)
+	{
+		self.reference_counter.store(0, SeqCst);
+		self.pool_pointer.close()
+	}
+	
 	/// Returns 'true' if the caller was the last reference.
 	#[inline(always)]
 	fn release_reference(&self) -> bool
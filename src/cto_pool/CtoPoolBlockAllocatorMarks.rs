@@ -0,0 +1,77 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// The blocks of a `BlockAllocator` found to be free (in its bags) or used (in reachable `Chains`) by a `CtoPoolTraversal`.
+/// `recycle_blocks` returns a run of blocks to the block allocator at an address; it is specific to the block allocator's `Persistence`.
+#[derive(Debug)]
+struct CtoPoolBlockAllocatorMarks
+{
+	reached_blocks: Vec<bool>,
+	recycle_blocks: fn(usize, usize, usize),
+}
+
+impl CtoPoolBlockAllocatorMarks
+{
+	#[inline(always)]
+	fn new(free_blocks: Vec<bool>, recycle_blocks: fn(usize, usize, usize)) -> Self
+	{
+		Self
+		{
+			reached_blocks: free_blocks,
+			recycle_blocks,
+		}
+	}
+	
+	/// Returns `false`, without marking anything, if any of the blocks has already been marked (or is past the last block).
+	#[inline(always)]
+	fn mark(&mut self, inclusive_from_block_index: usize, exclusive_to_block_index: usize) -> bool
+	{
+		if exclusive_to_block_index > self.reached_blocks.len()
+		{
+			return false
+		}
+		
+		let blocks = &mut self.reached_blocks[inclusive_from_block_index .. exclusive_to_block_index];
+		if blocks.iter().any(|&is_reached| is_reached)
+		{
+			return false
+		}
+		
+		for is_reached in blocks.iter_mut()
+		{
+			*is_reached = true
+		}
+		true
+	}
+	
+	/// Appends each run of blocks that has not been marked to `blocks`, as in `CtoPoolLeaks::blocks`.
+	#[inline(always)]
+	fn unreached_blocks(&self, block_allocator_address: usize, blocks: &mut Vec<(usize, usize, usize)>)
+	{
+		let number_of_blocks = self.reached_blocks.len();
+		
+		let mut block_index = 0;
+		while block_index < number_of_blocks
+		{
+			if self.reached_blocks[block_index]
+			{
+				block_index += 1;
+				continue
+			}
+			
+			let inclusive_from_block_index = block_index;
+			while block_index < number_of_blocks && !self.reached_blocks[block_index]
+			{
+				block_index += 1;
+			}
+			blocks.push((block_allocator_address, inclusive_from_block_index, block_index - inclusive_from_block_index));
+		}
+	}
+	
+	#[inline(always)]
+	fn recycle(&self, block_allocator_address: usize, inclusive_from_block_index: usize, number_of_blocks: usize)
+	{
+		(self.recycle_blocks)(block_allocator_address, inclusive_from_block_index, inclusive_from_block_index + number_of_blocks)
+	}
+}
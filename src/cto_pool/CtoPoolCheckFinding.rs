@@ -0,0 +1,59 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A problem found when checking the integrity of a pool (see `ReadOnlyCtoPool::check()`).
+/// Addresses are those in the pool as it is currently mapped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CtoPoolCheckFinding
+{
+	/// A pointer (the address) that does not land inside the pool mapping.
+	PointerOutsidePool(usize),
+	
+	/// A pointer (the address) that is not aligned for the type it points to (the alignment).
+	MisalignedPointer(usize, usize),
+	
+	/// A pointer (the address) to a value (of the size) that does not lie within an allocation, eg because it points to memory that has been freed.
+	PointerOutsideAllocation(usize, usize),
+	
+	/// The strong count of a `CtoArc`, `CtoRc` or `CtoStrongArc` (at the address) does not match the number of strong references to it that were found (the strong count, the number found).
+	StrongCountMismatch(usize, usize, usize),
+	
+	/// The weak count of a `CtoArc` or `CtoRc` (at the address) is not what it should be given that weak references can not be persisted (the weak count, what it should be).
+	/// This is usually because a process holding a weak reference crashed.
+	WeakCountMismatch(usize, usize, usize),
+	
+	/// The pointers a `BlockAllocator` (at the address) holds to its blocks and their meta data do not match its number of blocks, or run past the end of its allocation; nothing else about it is checked.
+	BlockAllocatorLayoutInconsistent(usize),
+	
+	/// A free chain (starting at the block index) in a `BlockAllocator` (at the address) is not linked consistently to its neighbours in its bag, or is not marked as being in the bag it is in.
+	BlockAllocatorBagLinkBroken(usize, usize),
+	
+	/// A chain (starting at the block index) in a `BlockAllocator` (at the address) has a length (in its `BlockMetaData`) that does not match the bag it is in, or that makes it run past the last block or overlap another chain (the length).
+	BlockAllocatorChainLengthInconsistent(usize, usize, usize),
+	
+	/// A bag of a `BlockAllocator` (at the address) for chains of a length (the length) records a different number of chains to those found in it (the number recorded, the number found).
+	BlockAllocatorBagCountMismatch(usize, usize, usize, usize),
+}
+
+impl Display for CtoPoolCheckFinding
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::CtoPoolCheckFinding::*;
+		
+		match *self
+		{
+			PointerOutsidePool(address) => write!(f, "pointer '0x{:x}' is outside the pool", address),
+			MisalignedPointer(address, alignment) => write!(f, "pointer '0x{:x}' is not aligned to '{}'", address, alignment),
+			PointerOutsideAllocation(address, size) => write!(f, "pointer '0x{:x}' to '{}' bytes is not within an allocation", address, size),
+			StrongCountMismatch(address, strong_count, found) => write!(f, "strong count '{}' at '0x{:x}' does not match the '{}' strong references found", strong_count, address, found),
+			WeakCountMismatch(address, weak_count, expected) => write!(f, "weak count '{}' at '0x{:x}' should be '{}'", weak_count, address, expected),
+			BlockAllocatorLayoutInconsistent(address) => write!(f, "block allocator at '0x{:x}' has an inconsistent layout", address),
+			BlockAllocatorBagLinkBroken(address, block_index) => write!(f, "block allocator at '0x{:x}' has a free chain starting at block '{}' that is not linked consistently into its bag", address, block_index),
+			BlockAllocatorChainLengthInconsistent(address, block_index, chain_length) => write!(f, "block allocator at '0x{:x}' has a chain starting at block '{}' with an inconsistent length '{}'", address, block_index, chain_length),
+			BlockAllocatorBagCountMismatch(address, chain_length, recorded, found) => write!(f, "block allocator at '0x{:x}' records '{}' free chains of length '{}' but '{}' were found", address, recorded, chain_length, found),
+		}
+	}
+}
@@ -0,0 +1,46 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// The raw metadata of a CTO pool: the bytes of its header and the chunks of its heap; see `CtoPoolPathExt::dump_cto_pool()`.
+/// Use `Display` to format it as a hex dump followed by a list of chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CtoPoolDump
+{
+	/// The address the pool is mapped at.
+	pub mapped_base_address: usize,
+	
+	/// The bytes of the header at the start of the pool.
+	pub header: Vec<u8>,
+	
+	/// Every chunk in the heap, in address order, as its address, its size (including its chunk header) and whether it is allocated.
+	pub chunks: Vec<(usize, usize, bool)>,
+}
+
+impl Display for CtoPoolDump
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		const BytesPerLine: usize = 16;
+		
+		writeln!(f, "header ({} bytes):", self.header.len())?;
+		for (line_index, line) in self.header.chunks(BytesPerLine).enumerate()
+		{
+			write!(f, "{:08x}:", line_index * BytesPerLine)?;
+			for byte in line.iter()
+			{
+				write!(f, " {:02x}", byte)?;
+			}
+			writeln!(f)?;
+		}
+		
+		writeln!(f, "heap ({} chunks):", self.chunks.len())?;
+		for &(chunk_address, chunk_size, is_allocated) in self.chunks.iter()
+		{
+			writeln!(f, "{:08x}: {} bytes {}", chunk_address - self.mapped_base_address, chunk_size, if is_allocated { "allocated" } else { "free" })?;
+		}
+		
+		Ok(())
+	}
+}
@@ -0,0 +1,477 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Stored in Persistent Memory, at the very start of a CTO pool's mapping.
+/// Uses `#[repr(C)]` to prevent reordering of fields.
+/// The heap starts immediately after this struct.
+///
+/// The fields up to `checksum` are written once, when the pool is created, and everything from `format_version` up to (but excluding) `checksum` is covered by the checksum; `magic` is written last, so a pool whose creation did not complete is never mistaken for a valid one.
+///
+/// The fields after `checksum` change while the pool is in use and so are not covered by it; each is persisted on its own, in an order that leaves the pool consistent if interrupted.
+///
+/// `bases` records the address the pool is mapped at (see `CtoPoolHeaderBase`); it changes when the pool is relocated, and each of its slots has a checksum of its own.
+///
+/// `size` is the size of the pool that the heap extends to the end of; it changes when the pool is grown.
+///
+/// `root` and `root_type_fingerprint` are null (zeros) until the root is created; `pending_root` and `pending_root_type_fingerprint` are used to replace them together (eg when migrating the root), and `pending_root` is null unless a replacement was interrupted.
+///
+/// `root_directory` is null until the first named root is inserted.
+///
+/// The shutdown state (`clean_shutdown` and `unsafe_shutdown_count`) changes every time the pool is opened and closed.
+#[repr(C, align(4096))]
+pub(crate) struct CtoPoolHeader
+{
+	magic: u64,
+	format_version: u64,
+	uuid: [u8; 16],
+	layout_name: [u8; PMEMCTO_MAX_LAYOUT],
+	creation_size: u64,
+	checksum: u64,
+	bases: [CtoPoolHeaderBase; 2],
+	size: u64,
+	root: AtomicPtr<c_void>,
+	root_type_fingerprint: TypeFingerprint,
+	pending_root: AtomicPtr<c_void>,
+	pending_root_type_fingerprint: TypeFingerprint,
+	root_directory: AtomicPtr<RootDirectory>,
+	clean_shutdown: u64,
+	unsafe_shutdown_count: u64,
+}
+
+impl CtoPoolHeader
+{
+	/// `CTOPOOL` followed by a NUL in ASCII (little-endian).
+	const Magic: u64 = 0x004C_4F4F_504F_5443;
+	
+	/// Increment whenever the on-media format changes incompatibly.
+	pub(crate) const FormatVersion: u64 = 1;
+	
+	const CleanShutdown: u64 = 1;
+	
+	const DirtyShutdown: u64 = 0;
+	
+	const UnknownUnsafeShutdownCount: u64 = ::std::u64::MAX;
+	
+	#[inline(always)]
+	fn from_mapped_memory<'mapped_memory>(mapped_memory: &'mapped_memory MappedMemory) -> &'mapped_memory Self
+	{
+		debug_assert!(mapped_memory.length() >= size_of::<Self>(), "mapped_memory is too small to contain a CtoPoolHeader");
+		
+		unsafe { & * (mapped_memory.as_ptr() as *const Self) }
+	}
+	
+	#[inline(always)]
+	fn from_mapped_memory_mut<'mapped_memory>(mapped_memory: &'mapped_memory MappedMemory) -> &'mapped_memory mut Self
+	{
+		debug_assert!(mapped_memory.length() >= size_of::<Self>(), "mapped_memory is too small to contain a CtoPoolHeader");
+		
+		unsafe { &mut * (mapped_memory.as_ptr() as *mut Self) }
+	}
+	
+	/// The pool is created open, and so with a dirty shutdown state.
+	#[inline(always)]
+	fn initialize(&mut self, mapped_memory: &MappedMemory, layout_name: &CStr, unsafe_shutdown_count: Option<u64>, persistence: MappingPersistence)
+	{
+		let layout_name = layout_name.to_bytes();
+		debug_assert!(layout_name.len() <= PMEMCTO_MAX_LAYOUT, "layout_name is longer than PMEMCTO_MAX_LAYOUT");
+		
+		let mut padded_layout_name = [0u8; PMEMCTO_MAX_LAYOUT];
+		padded_layout_name[.. layout_name.len()].copy_from_slice(layout_name);
+		
+		unsafe
+		{
+			write(&mut self.format_version, Self::FormatVersion);
+			write(&mut self.uuid, Self::generate_uuid());
+			write(&mut self.layout_name, padded_layout_name);
+			write(&mut self.creation_size, mapped_memory.length() as u64);
+			write(&mut self.bases, [CtoPoolHeaderBase::new(1, mapped_memory.as_ptr() as usize, 0), CtoPoolHeaderBase::new(0, mapped_memory.as_ptr() as usize, 0)]);
+			write(&mut self.size, mapped_memory.length() as u64);
+			write(&mut self.root, AtomicPtr::new(null_mut()));
+			write(&mut self.root_type_fingerprint, TypeFingerprint::default());
+			write(&mut self.pending_root, AtomicPtr::new(null_mut()));
+			write(&mut self.pending_root_type_fingerprint, TypeFingerprint::default());
+			write(&mut self.root_directory, AtomicPtr::new(null_mut()));
+			write(&mut self.clean_shutdown, Self::DirtyShutdown);
+			write(&mut self.unsafe_shutdown_count, unsafe_shutdown_count.unwrap_or(Self::UnknownUnsafeShutdownCount));
+		}
+		self.update_checksum();
+		self.persist(persistence);
+		
+		unsafe { write(&mut self.magic, Self::Magic) };
+		persistence.persist_memory(&self.magic as *const u64 as usize, size_of::<u64>())
+	}
+	
+	/// Does this look like a CTO pool (even if it is for a different layout or version)?
+	#[inline(always)]
+	fn has_magic(&self) -> bool
+	{
+		self.magic == Self::Magic
+	}
+	
+	/// Checks, in order, the magic number, the format version, the checksum, the layout name and that there is a valid base.
+	#[inline(always)]
+	fn validate(&self, layout_name: &CStr) -> Result<(), CtoPoolHeaderValidationError>
+	{
+		use self::CtoPoolHeaderValidationError::*;
+		
+		if !self.has_magic()
+		{
+			return Err(WrongMagic(self.magic))
+		}
+		
+		if self.format_version != Self::FormatVersion
+		{
+			return Err(UnsupportedVersion(self.format_version))
+		}
+		
+		let computed = self.compute_checksum();
+		if self.checksum != computed
+		{
+			return Err(BadChecksum(self.checksum, computed))
+		}
+		
+		let expected = layout_name.to_bytes();
+		let found = self.layout_name();
+		if expected != found
+		{
+			return Err(WrongLayout(String::from_utf8_lossy(expected).into_owned(), String::from_utf8_lossy(found).into_owned()))
+		}
+		
+		if self.current_base_index().is_none()
+		{
+			return Err(NoValidBase)
+		}
+		
+		Ok(())
+	}
+	
+	/// The layout name, without trailing NUL padding.
+	#[inline(always)]
+	fn layout_name(&self) -> &[u8]
+	{
+		let length = self.layout_name.iter().position(|byte| *byte == 0).unwrap_or(PMEMCTO_MAX_LAYOUT);
+		&self.layout_name[.. length]
+	}
+	
+	#[inline(always)]
+	fn format_version(&self) -> u64
+	{
+		self.format_version
+	}
+	
+	#[inline(always)]
+	fn creation_size(&self) -> usize
+	{
+		self.creation_size as usize
+	}
+	
+	/// The address pointers in the pool are relative to (unless relocating the pool was interrupted; see `relocating_to_address()`).
+	#[inline(always)]
+	fn mapped_base_address(&self) -> usize
+	{
+		self.current_base().mapped_base_address
+	}
+	
+	/// The address relocating the pool to was interrupted, if it was.
+	#[inline(always)]
+	fn relocating_to_address(&self) -> Option<usize>
+	{
+		self.current_base().relocating_to_address()
+	}
+	
+	#[inline(always)]
+	fn uuid(&self) -> [u8; 16]
+	{
+		self.uuid
+	}
+	
+	/// The size of the pool that the heap extends to the end of.
+	/// The pool may be mapped larger than this if growing it was interrupted by a crash.
+	#[inline(always)]
+	fn size(&self) -> usize
+	{
+		self.size as usize
+	}
+	
+	/// Must only be done once the heap chunk covering the memory the pool has grown by has been persisted.
+	#[inline(always)]
+	fn record_size(&mut self, size: usize, persistence: MappingPersistence)
+	{
+		unsafe { write(&mut self.size, size as u64) }
+		persistence.persist_memory(&self.size as *const u64 as usize, size_of::<u64>())
+	}
+	
+	/// Records that the pool is about to be relocated from `mapped_base_address` to `relocating_to_address`; done first when relocating, so that an interrupted relocation can be completed.
+	#[inline(always)]
+	fn record_relocating(&mut self, mapped_base_address: usize, relocating_to_address: usize, persistence: MappingPersistence)
+	{
+		debug_assert_ne!(relocating_to_address, 0, "relocating_to_address can not be zero");
+		
+		self.record_base(mapped_base_address, relocating_to_address, persistence)
+	}
+	
+	/// Records that the pool is now mapped at `mapped_base_address`; done last when relocating.
+	#[inline(always)]
+	fn rebase(&mut self, mapped_base_address: usize, persistence: MappingPersistence)
+	{
+		self.record_base(mapped_base_address, 0, persistence)
+	}
+	
+	/// Writes the slot that is not current, which becomes current once it is persisted, as only then does its checksum match.
+	#[inline(always)]
+	fn record_base(&mut self, mapped_base_address: usize, relocating_to_address: usize, persistence: MappingPersistence)
+	{
+		let current_base_index = self.current_base_index().expect("validated headers have a valid base");
+		let next_base_index = 1 - current_base_index;
+		let sequence = self.bases[current_base_index].sequence + 1;
+		
+		let next_base = &mut self.bases[next_base_index];
+		unsafe { write(next_base, CtoPoolHeaderBase::new(sequence, mapped_base_address, relocating_to_address)) }
+		persistence.persist_memory(next_base as *const CtoPoolHeaderBase as usize, size_of::<CtoPoolHeaderBase>())
+	}
+	
+	#[inline(always)]
+	fn current_base(&self) -> &CtoPoolHeaderBase
+	{
+		&self.bases[self.current_base_index().expect("validated headers have a valid base")]
+	}
+	
+	/// The index of the valid slot in `bases` with the greater sequence, if either is valid.
+	#[inline(always)]
+	fn current_base_index(&self) -> Option<usize>
+	{
+		match (self.bases[0].is_valid(), self.bases[1].is_valid())
+		{
+			(false, false) => None,
+			(true, false) => Some(0),
+			(false, true) => Some(1),
+			(true, true) => Some(if self.bases[0].sequence >= self.bases[1].sequence { 0 } else { 1 }),
+		}
+	}
+	
+	/// Finds how the pool was last shut down, and then records that it is open (ie that a shutdown would now be dirty) with the current NVDIMM unsafe shutdown count.
+	#[inline(always)]
+	fn record_open(&mut self, unsafe_shutdown_count: Option<u64>, persistence: MappingPersistence) -> LastShutdown
+	{
+		let last_shutdown = self.last_shutdown(unsafe_shutdown_count);
+		
+		unsafe
+		{
+			write(&mut self.clean_shutdown, Self::DirtyShutdown);
+			write(&mut self.unsafe_shutdown_count, unsafe_shutdown_count.unwrap_or(Self::UnknownUnsafeShutdownCount));
+		}
+		self.persist_shutdown_state(persistence);
+		
+		last_shutdown
+	}
+	
+	/// Finds how the pool was last shut down, given the current NVDIMM unsafe shutdown count.
+	#[inline(always)]
+	fn last_shutdown(&self, unsafe_shutdown_count: Option<u64>) -> LastShutdown
+	{
+		let unsafe_shutdown_count = unsafe_shutdown_count.unwrap_or(Self::UnknownUnsafeShutdownCount);
+		
+		if self.clean_shutdown == Self::CleanShutdown
+		{
+			LastShutdown::Clean
+		}
+		else if self.unsafe_shutdown_count != unsafe_shutdown_count && self.unsafe_shutdown_count != Self::UnknownUnsafeShutdownCount && unsafe_shutdown_count != Self::UnknownUnsafeShutdownCount
+		{
+			LastShutdown::UnsafeShutdown
+		}
+		else
+		{
+			LastShutdown::Dirty
+		}
+	}
+	
+	/// Must only be done once everything else in the pool has been persisted.
+	#[inline(always)]
+	fn record_clean_shutdown(&mut self, persistence: MappingPersistence)
+	{
+		unsafe { write(&mut self.clean_shutdown, Self::CleanShutdown) }
+		self.persist_shutdown_state(persistence)
+	}
+	
+	#[inline(always)]
+	fn heap_inclusive_start_address(&self) -> usize
+	{
+		self as *const Self as usize + size_of::<Self>()
+	}
+	
+	/// Fails if the pool has been truncated since it was last grown.
+	#[inline(always)]
+	fn heap_exclusive_end_address(&self, mapped_memory: &MappedMemory) -> Result<usize, CtoPoolHeaderValidationError>
+	{
+		let length = mapped_memory.length();
+		let size = self.size();
+		if size > length
+		{
+			Err(CtoPoolHeaderValidationError::SmallerThanPool(length, size))
+		}
+		else
+		{
+			Ok(self as *const Self as usize + size)
+		}
+	}
+	
+	#[inline(always)]
+	fn get_root(&self) -> *mut c_void
+	{
+		self.root.load(Acquire)
+	}
+	
+	#[inline(always)]
+	fn set_root(&self, root: *mut c_void, persistence: MappingPersistence)
+	{
+		self.root.store(root, Release);
+		persistence.persist_memory(&self.root as *const AtomicPtr<c_void> as usize, size_of::<AtomicPtr<c_void>>())
+	}
+	
+	#[inline(always)]
+	fn root_type_fingerprint(&self) -> TypeFingerprint
+	{
+		self.root_type_fingerprint
+	}
+	
+	#[inline(always)]
+	fn record_root_type_fingerprint(&mut self, root_type_fingerprint: TypeFingerprint, persistence: MappingPersistence)
+	{
+		unsafe { write(&mut self.root_type_fingerprint, root_type_fingerprint) }
+		persistence.persist_memory(&self.root_type_fingerprint as *const TypeFingerprint as usize, size_of::<TypeFingerprint>())
+	}
+	
+	#[inline(always)]
+	fn has_pending_root(&self) -> bool
+	{
+		self.get_pending_root().is_not_null()
+	}
+	
+	#[inline(always)]
+	fn get_pending_root(&self) -> *mut c_void
+	{
+		self.pending_root.load(Acquire)
+	}
+	
+	/// Records a root, and the fingerprint of its type, that are to replace the current ones; recording `pending_root` is the point at which the replacement will happen, even if interrupted.
+	#[inline(always)]
+	fn record_pending_root(&mut self, pending_root: *mut c_void, pending_root_type_fingerprint: TypeFingerprint, persistence: MappingPersistence)
+	{
+		debug_assert!(pending_root.is_not_null(), "pending_root can not be null");
+		debug_assert!(!self.has_pending_root(), "there is already a pending root");
+		
+		unsafe { write(&mut self.pending_root_type_fingerprint, pending_root_type_fingerprint) }
+		persistence.persist_memory(&self.pending_root_type_fingerprint as *const TypeFingerprint as usize, size_of::<TypeFingerprint>());
+		
+		self.pending_root.store(pending_root, Release);
+		self.persist_pending_root(persistence)
+	}
+	
+	/// Replaces the root and the fingerprint of its type with the pending ones, if any.
+	/// Can be repeated if interrupted.
+	#[inline(always)]
+	fn complete_pending_root(&mut self, persistence: MappingPersistence)
+	{
+		let pending_root = self.pending_root.load(Acquire);
+		if pending_root.is_null()
+		{
+			return
+		}
+		
+		let pending_root_type_fingerprint = self.pending_root_type_fingerprint;
+		self.record_root_type_fingerprint(pending_root_type_fingerprint, persistence);
+		self.set_root(pending_root, persistence);
+		
+		self.pending_root.store(null_mut(), Release);
+		self.persist_pending_root(persistence)
+	}
+	
+	#[inline(always)]
+	fn persist_pending_root(&self, persistence: MappingPersistence)
+	{
+		persistence.persist_memory(&self.pending_root as *const AtomicPtr<c_void> as usize, size_of::<AtomicPtr<c_void>>())
+	}
+	
+	#[inline(always)]
+	fn get_root_directory(&self) -> *mut RootDirectory
+	{
+		self.root_directory.load(Acquire)
+	}
+	
+	#[inline(always)]
+	fn set_root_directory(&self, root_directory: *mut RootDirectory, persistence: MappingPersistence)
+	{
+		self.root_directory.store(root_directory, Release);
+		persistence.persist_memory(&self.root_directory as *const AtomicPtr<RootDirectory> as usize, size_of::<AtomicPtr<RootDirectory>>())
+	}
+	
+	/// Relocates the pointer to the root directory, but not the root directory itself, and returns the relocated pointer.
+	#[inline(always)]
+	fn relocate_root_directory(&self, cto_pool_relocation: &CtoPoolRelocation) -> *mut RootDirectory
+	{
+		cto_pool_relocation.relocate_atomic_pointer(&self.root_directory)
+	}
+	
+	#[inline(always)]
+	fn update_checksum(&mut self)
+	{
+		let checksum = self.compute_checksum();
+		unsafe { write(&mut self.checksum, checksum) }
+	}
+	
+	/// Fletcher-64 (as used by PMDK) over the 32-bit little-endian words from `format_version` up to `checksum`.
+	#[inline(always)]
+	fn compute_checksum(&self) -> u64
+	{
+		Self::fletcher64(&self.format_version as *const u64 as usize, &self.checksum as *const u64 as usize)
+	}
+	
+	/// Fletcher-64 (as used by PMDK) over the 32-bit little-endian words in memory from `inclusive_start_address` up to `exclusive_end_address`.
+	#[inline(always)]
+	fn fletcher64(inclusive_start_address: usize, exclusive_end_address: usize) -> u64
+	{
+		let words = unsafe { from_raw_parts(inclusive_start_address as *const u32, (exclusive_end_address - inclusive_start_address) / size_of::<u32>()) };
+		
+		let mut low: u32 = 0;
+		let mut high: u32 = 0;
+		for word in words.iter()
+		{
+			low = low.wrapping_add(u32::from_le(*word));
+			high = high.wrapping_add(low);
+		}
+		((high as u64) << 32) | (low as u64)
+	}
+	
+	/// A random (version 4) UUID.
+	#[inline(always)]
+	fn generate_uuid() -> [u8; 16]
+	{
+		let mut uuid = [0u8; 16];
+		for chunk in uuid.chunks_mut(size_of::<usize>())
+		{
+			let random = generate_hyper_thread_safe_random_usize();
+			for (index, byte) in chunk.iter_mut().enumerate()
+			{
+				*byte = (random >> (index * 8)) as u8;
+			}
+		}
+		uuid[6] = (uuid[6] & 0x0F) | 0x40;
+		uuid[8] = (uuid[8] & 0x3F) | 0x80;
+		uuid
+	}
+	
+	#[inline(always)]
+	fn persist_shutdown_state(&self, persistence: MappingPersistence)
+	{
+		let inclusive_start_address = &self.clean_shutdown as *const u64 as usize;
+		let exclusive_end_address = &self.unsafe_shutdown_count as *const u64 as usize + size_of::<u64>();
+		persistence.persist_memory(inclusive_start_address, exclusive_end_address - inclusive_start_address)
+	}
+	
+	#[inline(always)]
+	fn persist(&self, persistence: MappingPersistence)
+	{
+		persistence.persist_memory(self as *const Self as usize, size_of::<Self>())
+	}
+}
@@ -0,0 +1,59 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Stored in Persistent Memory, as one of two slots in a `CtoPoolHeader` that record the address the pool is mapped at.
+/// A slot is valid if its checksum matches its contents; the valid slot with the greater `sequence` is current, so a new value is committed atomically by writing it to the other slot.
+///
+/// Pointers in the pool are relative to `mapped_base_address`, unless `relocating_to_address` is not zero, in which case relocating the pool to `relocating_to_address` was interrupted, and each pointer may be relative to either.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct CtoPoolHeaderBase
+{
+	sequence: u64,
+	mapped_base_address: usize,
+	relocating_to_address: usize,
+	checksum: u64,
+}
+
+impl CtoPoolHeaderBase
+{
+	#[inline(always)]
+	fn new(sequence: u64, mapped_base_address: usize, relocating_to_address: usize) -> Self
+	{
+		let mut this = Self
+		{
+			sequence,
+			mapped_base_address,
+			relocating_to_address,
+			checksum: 0,
+		};
+		this.checksum = this.compute_checksum();
+		this
+	}
+	
+	#[inline(always)]
+	fn is_valid(&self) -> bool
+	{
+		self.checksum == self.compute_checksum()
+	}
+	
+	#[inline(always)]
+	fn relocating_to_address(&self) -> Option<usize>
+	{
+		if self.relocating_to_address == 0
+		{
+			None
+		}
+		else
+		{
+			Some(self.relocating_to_address)
+		}
+	}
+	
+	#[inline(always)]
+	fn compute_checksum(&self) -> u64
+	{
+		CtoPoolHeader::fletcher64(&self.sequence as *const u64 as usize, &self.checksum as *const u64 as usize)
+	}
+}
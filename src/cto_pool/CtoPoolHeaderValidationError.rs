@@ -0,0 +1,60 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+quick_error!
+{
+	/// Reason why the header at the start of a CTO pool's mapping was rejected.
+	#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	pub enum CtoPoolHeaderValidationError
+	{
+		/// The file or DAX device is too small to contain a CTO pool header.
+		Truncated(length: usize)
+		{
+			description("too small to contain a CTO pool header")
+			display("Only '{}' bytes long, which is too small to contain a CTO pool header", length)
+		}
+		
+		/// The file or DAX device is smaller than the size the pool was last grown to.
+		SmallerThanPool(length: usize, size: usize)
+		{
+			description("smaller than the size of the CTO pool")
+			display("Only '{}' bytes long, which is smaller than the size of the CTO pool, '{}' bytes", length, size)
+		}
+		
+		/// The magic number is wrong; this is not a CTO pool (or its creation never completed).
+		WrongMagic(magic: u64)
+		{
+			description("wrong magic number")
+			display("Wrong magic number '0x{:016X}'; not a CTO pool", magic)
+		}
+		
+		/// The CTO pool was created by a newer (or older, incompatible) version of this crate.
+		UnsupportedVersion(format_version: u64)
+		{
+			description("unsupported format version")
+			display("Unsupported CTO pool format version '{}' (expected '{}')", format_version, CtoPoolHeader::FormatVersion)
+		}
+		
+		/// The checksum of the header does not match its contents.
+		BadChecksum(stored: u64, computed: u64)
+		{
+			description("bad checksum")
+			display("Bad CTO pool header checksum; stored '0x{:016X}' but computed '0x{:016X}'", stored, computed)
+		}
+		
+		/// The layout name the pool was created with is not the one it is being opened with.
+		WrongLayout(expected: String, found: String)
+		{
+			description("wrong layout name")
+			display("Wrong layout name; expected '{}' but found '{}'", expected, found)
+		}
+		
+		/// Neither slot recording the address the pool is mapped at has a checksum that matches its contents.
+		NoValidBase
+		{
+			description("no valid base address")
+			display("Neither CTO pool header base address slot is valid")
+		}
+	}
+}
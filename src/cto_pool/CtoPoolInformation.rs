@@ -0,0 +1,41 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// What is recorded about a CTO pool in its header, and how its memory is used; see `CtoPoolPathExt::cto_pool_information()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CtoPoolInformation
+{
+	/// The version of the on-media format of the pool.
+	pub format_version: u64,
+	
+	/// Generated when the pool was created.
+	pub uuid: [u8; 16],
+	
+	/// The layout name the pool was created with.
+	pub layout_name: String,
+	
+	/// The size of the pool when it was created.
+	pub creation_size: usize,
+	
+	/// The size of the pool now, which is larger than `creation_size` if the pool has been grown.
+	pub size: usize,
+	
+	/// The address the pool is mapped at.
+	pub mapped_base_address: usize,
+	
+	/// The address of the root, or zero if there is no root.
+	pub root_address: usize,
+	
+	/// The fingerprint of the type of the root; all zeros if there is no root.
+	pub root_type_fingerprint: TypeFingerprint,
+	
+	/// The names and type fingerprints of all named roots.
+	pub named_roots: Vec<(String, TypeFingerprint)>,
+	
+	/// How the pool was last shut down.
+	pub last_shutdown: LastShutdown,
+	
+	/// How the pool's memory is used.
+	pub statistics: CtoPoolStatistics,
+}
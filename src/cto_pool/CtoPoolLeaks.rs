@@ -0,0 +1,38 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// What is allocated in a CTO pool but can not be reached from its roots; see `ReadOnlyCtoPool::find_leaks()` and `CtoPool::collect_leaks()`.
+/// Leaks occur if a process crashes after allocating something but before linking it into the pool's objects.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct CtoPoolLeaks
+{
+	/// Allocations in the pool's heap, as the address and usable size of each.
+	pub allocations: Vec<(usize, usize)>,
+	
+	/// Runs of blocks in a `BlockAllocator`, as the address of the block allocator, the index of the first block and the number of blocks.
+	pub blocks: Vec<(usize, usize, usize)>,
+	
+	/// Block allocators (as their addresses) that have handed out chains that can not be walked (eg through a `BlockAllocatorAlloc`); their blocks are not included in `blocks`, as it can not be known which are leaked.
+	pub unwalked_block_allocators: Vec<usize>,
+	
+	/// Named roots that were not traversed; anything reachable only from them is included in `allocations` and `blocks`.
+	pub untraversed_named_roots: Vec<String>,
+}
+
+impl CtoPoolLeaks
+{
+	/// Was nothing found to be leaked?
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool
+	{
+		self.allocations.is_empty() && self.blocks.is_empty()
+	}
+	
+	/// The total size in bytes of the leaked allocations in the pool's heap (but not of the leaked blocks, whose size depends on their block allocator).
+	#[inline(always)]
+	pub fn allocated_size(&self) -> usize
+	{
+		self.allocations.iter().map(|&(_address, size)| size).sum()
+	}
+}
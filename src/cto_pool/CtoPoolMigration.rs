@@ -0,0 +1,85 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A migration of a root from a type `Old` to a type `New`, with the types erased so that migrations between different types can be kept together.
+pub(crate) struct CtoPoolMigration<InitializationError>
+{
+	from: TypeFingerprint,
+	to: TypeFingerprint,
+	relocate: fn(*mut c_void, &CtoPoolRelocation),
+	opened: fn(*mut c_void, &CtoPoolArc),
+	migrate: Box<Fn(*mut c_void, &CtoPoolArc) -> Result<*mut c_void, CtoPoolAllocationError<InitializationError>>>,
+	drop_in_place: fn(*mut c_void),
+}
+
+impl<InitializationError: 'static> CtoPoolMigration<InitializationError>
+{
+	#[inline(always)]
+	fn new<Old: CtoSafe + 'static, New: CtoSafe + 'static, Migration: Fn(&Old, &CtoPoolArc) -> Result<New, InitializationError> + 'static>(migration: Migration) -> Self
+	{
+		Self
+		{
+			from: TypeFingerprint::of::<Old>(),
+			to: TypeFingerprint::of::<New>(),
+			relocate: PMEMctopool::relocate_root::<Old>,
+			opened: Self::opened_root::<Old>,
+			migrate: Box::new(move |old_root: *mut c_void, cto_pool_arc: &CtoPoolArc|
+			{
+				let new_root = cto_pool_arc.pool_pointer().aligned_allocate::<New>()?;
+				
+				let new_root_value = match migration(unsafe { & * (old_root as *const Old) }, cto_pool_arc)
+				{
+					Err(initialization_error) =>
+					{
+						cto_pool_arc.pool_pointer().free(new_root);
+						return Err(CtoPoolAllocationError::Initialization(initialization_error))
+					}
+					Ok(new_root_value) => new_root_value,
+				};
+				
+				unsafe { write(new_root, new_root_value) };
+				cto_pool_arc.pool_pointer().pool().persist_memory(new_root as usize, size_of::<New>());
+				Ok(new_root as *mut c_void)
+			}),
+			drop_in_place: Self::drop_root_in_place::<Old>,
+		}
+	}
+}
+
+impl<InitializationError> CtoPoolMigration<InitializationError>
+{
+	#[inline(always)]
+	fn opened_root<Old: CtoSafe>(old_root: *mut c_void, cto_pool_arc: &CtoPoolArc)
+	{
+		unsafe { &mut * (old_root as *mut Old) }.cto_pool_opened(cto_pool_arc)
+	}
+	
+	#[inline(always)]
+	fn drop_root_in_place<Old: CtoSafe>(old_root: *mut c_void)
+	{
+		unsafe { drop_in_place(old_root as *mut Old) }
+	}
+	
+	/// Migrates the root of a pool, which must be of type `Old`, to a new root of type `New`, and then drops and frees the old root.
+	/// If the pool has moved, it must have been relocated first (with `relocate`).
+	///
+	/// Nothing reachable from the old root is changed until the new root has replaced it, so a crash before then leaves the old root in place to be migrated again; a crash after then leaks the old root.
+	#[inline(always)]
+	fn run(&self, cto_pool_arc: &CtoPoolArc) -> Result<(), CtoPoolAllocationError<InitializationError>>
+	{
+		let pool_pointer = cto_pool_arc.pool_pointer();
+		let pool = pool_pointer.pool();
+		
+		let old_root = pool_pointer.get_root::<c_void>();
+		(self.opened)(old_root, cto_pool_arc);
+		
+		let new_root = (self.migrate)(old_root, cto_pool_arc)?;
+		pool.replace_root(new_root, self.to);
+		
+		(self.drop_in_place)(old_root);
+		pool_pointer.free(old_root);
+		
+		Ok(())
+	}
+}
@@ -0,0 +1,98 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Migrations of a pool's root from the types it had in older versions of a program to `RootValue`, run by `CtoPool::open_with_migrations()`.
+///
+/// Each type a root has had should have a different `CtoSafe::schema_version()`, increasing with each change; there must be a migration from each old schema version, either to `RootValue` or to a later old type.
+pub struct CtoPoolMigrations<RootValue: CtoSafe, InitializationError>
+{
+	migrations: Vec<CtoPoolMigration<InitializationError>>,
+	marker: PhantomData<RootValue>,
+}
+
+impl<RootValue: CtoSafe, InitializationError> Debug for CtoPoolMigrations<RootValue, InitializationError>
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		let migrations: Vec<(u64, u64)> = self.migrations.iter().map(|migration| (migration.from.schema_version, migration.to.schema_version)).collect();
+		write!(f, "CtoPoolMigrations({:?})", migrations)
+	}
+}
+
+impl<RootValue: CtoSafe, InitializationError> Default for CtoPoolMigrations<RootValue, InitializationError>
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			migrations: Vec::new(),
+			marker: PhantomData,
+		}
+	}
+}
+
+impl<RootValue: CtoSafe, InitializationError: error::Error + 'static> CtoPoolMigrations<RootValue, InitializationError>
+{
+	/// Registers a migration from a root of type `Old` to one of type `New`.
+	///
+	/// `migration` is given the old root, which has been relocated (if the pool has moved) and had `cto_pool_opened()` called, and a `CtoPoolArc` with which to allocate anything the new root needs.
+	/// It must not change anything reachable from the old root (eg by moving a `CtoVec` out of it); anything the new root shares with the old root must be cloned.
+	/// The old root is dropped once the new root has replaced it.
+	///
+	/// Panics if `Old` does not have an older schema version than `New`, if `New` has a newer schema version than `RootValue` or if there is already a migration from the schema version of `Old`.
+	#[inline(always)]
+	pub fn register<Old: CtoSafe + 'static, New: CtoSafe + 'static, Migration: Fn(&Old, &CtoPoolArc) -> Result<New, InitializationError> + 'static>(&mut self, migration: Migration) -> &mut Self
+	{
+		let from_schema_version = Old::schema_version();
+		let to_schema_version = New::schema_version();
+		assert!(from_schema_version < to_schema_version, "Old schema version '{}' must be less than New schema version '{}'", from_schema_version, to_schema_version);
+		assert!(to_schema_version <= RootValue::schema_version(), "New schema version '{}' can not be greater than RootValue schema version '{}'", to_schema_version, RootValue::schema_version());
+		assert!(self.find(from_schema_version).is_none(), "There is already a migration from schema version '{}'", from_schema_version);
+		
+		self.migrations.push(CtoPoolMigration::new::<Old, New, Migration>(migration));
+		self
+	}
+}
+
+impl<RootValue: CtoSafe, InitializationError: error::Error> CtoPoolMigrations<RootValue, InitializationError>
+{
+	/// Migrates the root of a pool from `schema_version` to that of `RootValue`, one migration at a time.
+	/// If the pool has moved, it is relocated (with `named_roots`) before the first migration.
+	#[inline(always)]
+	fn migrate(&self, cto_pool_arc: &CtoPoolArc, mut schema_version: u64, named_roots: &CtoPoolNamedRoots) -> Result<(), CtoPoolOpenError<InitializationError>>
+	{
+		let to_schema_version = RootValue::schema_version();
+		
+		while schema_version != to_schema_version
+		{
+			let migration = match self.find(schema_version)
+			{
+				None => return Err(CtoPoolOpenError::MissingMigration(schema_version, to_schema_version)),
+				Some(migration) => migration,
+			};
+			
+			let found = cto_pool_arc.pool_pointer().pool().root_type_fingerprint();
+			if found != migration.from
+			{
+				return Err(CtoPoolOpenError::DifferentRootType { expected: migration.from, found })
+			}
+			
+			cto_pool_arc.pool_pointer().pool().relocate_if_moved(migration.relocate, named_roots).map_err(CtoPoolOpenError::OpenFailed)?.map_err(CtoPoolOpenError::NamedRootsNotRelocated)?;
+			
+			migration.run(cto_pool_arc).map_err(|cto_pool_allocation_error| CtoPoolOpenError::Migration(schema_version, cto_pool_allocation_error))?;
+			
+			schema_version = migration.to.schema_version;
+		}
+		
+		Ok(())
+	}
+	
+	#[inline(always)]
+	fn find(&self, from_schema_version: u64) -> Option<&CtoPoolMigration<InitializationError>>
+	{
+		self.migrations.iter().find(|migration| migration.from.schema_version == from_schema_version)
+	}
+}
@@ -0,0 +1,70 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// The types of the named roots of a pool, so that they can be relocated when it is opened; see `CtoPool::open_with_named_roots()`.
+/// If the pool has been mapped at a different address to the one it was last mapped at, every named root in it is relocated along with the root, so anything reachable from more than one root is relocated once.
+#[derive(Default)]
+pub struct CtoPoolNamedRoots
+{
+	named_roots: Vec<(Vec<u8>, TypeFingerprint, fn(*mut c_void, &CtoPoolRelocation))>,
+}
+
+impl Debug for CtoPoolNamedRoots
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		let named_roots: Vec<(String, TypeFingerprint)> = self.named_roots.iter().map(|&(ref name, type_fingerprint, _)| (String::from_utf8_lossy(name).into_owned(), type_fingerprint)).collect();
+		write!(f, "CtoPoolNamedRoots({:?})", named_roots)
+	}
+}
+
+impl CtoPoolNamedRoots
+{
+	/// Registers the named root `name` as being of type `T`.
+	///
+	/// Panics if `name` is already registered.
+	#[inline(always)]
+	pub fn register<T: CtoSafe>(&mut self, name: &str) -> &mut Self
+	{
+		let name = name.as_bytes();
+		assert!(self.find(name).is_none(), "There is already a named root '{}'", String::from_utf8_lossy(name));
+		
+		self.named_roots.push((name.to_vec(), TypeFingerprint::of::<T>(), PMEMctopool::relocate_root::<T>));
+		self
+	}
+	
+	/// The index of each named root in use in `root_directory`, and how to relocate it.
+	/// Returns the names of those named roots that are not registered, or are registered as a different type, if there are any.
+	#[inline(always)]
+	fn relocators(&self, root_directory: &RootDirectory) -> Result<Vec<(usize, fn(*mut c_void, &CtoPoolRelocation))>, Vec<String>>
+	{
+		let mut relocators = Vec::new();
+		let mut unregistered = Vec::new();
+		
+		for (index, entry) in root_directory.entries().iter().enumerate().filter(|&(_, entry)| entry.is_in_use())
+		{
+			match self.find(entry.name())
+			{
+				Some(&(_, type_fingerprint, relocate)) if type_fingerprint == entry.type_fingerprint() => relocators.push((index, relocate)),
+				_ => unregistered.push(String::from_utf8_lossy(entry.name()).into_owned()),
+			}
+		}
+		
+		if unregistered.is_empty()
+		{
+			Ok(relocators)
+		}
+		else
+		{
+			Err(unregistered)
+		}
+	}
+	
+	#[inline(always)]
+	fn find(&self, name: &[u8]) -> Option<&(Vec<u8>, TypeFingerprint, fn(*mut c_void, &CtoPoolRelocation))>
+	{
+		self.named_roots.iter().find(|&&(ref registered_name, _, _)| &registered_name[..] == name)
+	}
+}
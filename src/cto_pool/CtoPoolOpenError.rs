@@ -15,11 +15,31 @@ pub enum CtoPoolOpenError<InitializationError: error::Error>
 	/// An existing CTO pool could not be opened.
 	OpenFailed(PmdkError),
 	
+	/// An existing CTO pool's root is of a different type (`found`) to the one it is being opened with (`expected`).
+	DifferentRootType
+	{
+		/// The type fingerprint of the root the pool is being opened with.
+		expected: TypeFingerprint,
+		
+		/// The type fingerprint of the root in the pool.
+		found: TypeFingerprint,
+	},
+	
 	/// An existing CTO pool is invalid or inconsistent.
 	Invalid,
 	
 	/// After creating or opening a CTO pool, a root object was missing and creation of it was tried. Creation then failed.
 	RootCreation(CtoPoolAllocationError<InitializationError>),
+	
+	/// An existing CTO pool's root has an older schema version (first) than the one it is being opened with (second), but there is no migration from it.
+	MissingMigration(u64, u64),
+	
+	/// Migrating an existing CTO pool's root from a schema version failed.
+	Migration(u64, CtoPoolAllocationError<InitializationError>),
+	
+	/// An existing CTO pool has been mapped at a different address to the one it was last mapped at, but these named roots are not registered (or are registered as a different type), and so it can not be relocated.
+	/// See `CtoPool::open_with_named_roots()`.
+	NamedRootsNotRelocated(Vec<String>),
 }
 
 impl<InitializationError: error::Error> Display for CtoPoolOpenError<InitializationError>
@@ -31,15 +51,23 @@ impl<InitializationError: error::Error> Display for CtoPoolOpenError<Initializat
 		
 		match *self
 		{
-			CreateFailed(ref pmdk_error) => Display::fmt(pmdk_error, formatter),
+			CreateFailed(ref pmdk_error) => write!(formatter, "Could not create CTO pool: {}", pmdk_error),
+			
+			ValidationFailed(ref pmdk_error) => write!(formatter, "Could not validate CTO pool: {}", pmdk_error),
 			
-			ValidationFailed(ref pmdk_error) => Display::fmt(pmdk_error, formatter),
+			OpenFailed(ref pmdk_error) => write!(formatter, "Could not open CTO pool: {}", pmdk_error),
 			
-			OpenFailed(ref pmdk_error) => Display::fmt(pmdk_error, formatter),
+			DifferentRootType { ref expected, ref found } => write!(formatter, "CTO pool root is of a different type; expected '{:?}' but found '{:?}'", expected, found),
 			
-			Invalid => write!(formatter, "Invalid"),
+			Invalid => write!(formatter, "CTO pool is inconsistent"),
 			
-			RootCreation(ref cto_pool_allocation_error) => Display::fmt(cto_pool_allocation_error, formatter),
+			RootCreation(ref cto_pool_allocation_error) => write!(formatter, "Could not create CTO pool root: {}", cto_pool_allocation_error),
+			
+			MissingMigration(from_schema_version, to_schema_version) => write!(formatter, "No migration of CTO pool root from schema version '{}' (to schema version '{}')", from_schema_version, to_schema_version),
+			
+			Migration(from_schema_version, ref cto_pool_allocation_error) => write!(formatter, "Could not migrate CTO pool root from schema version '{}': {}", from_schema_version, cto_pool_allocation_error),
+			
+			NamedRootsNotRelocated(ref names) => write!(formatter, "CTO pool has moved but can not be relocated as the named roots '{:?}' are not registered", names),
 		}
 	}
 }
@@ -65,9 +93,17 @@ impl<InitializationError: error::Error> error::Error for CtoPoolOpenError<Initia
 			
 			OpenFailed(ref pmdk_error) => Some(pmdk_error),
 			
+			DifferentRootType { .. } => None,
+			
 			Invalid => None,
 			
 			RootCreation(ref cto_pool_allocation_error) => Some(cto_pool_allocation_error),
+			
+			MissingMigration(_, _) => None,
+			
+			Migration(_, ref cto_pool_allocation_error) => Some(cto_pool_allocation_error),
+			
+			NamedRootsNotRelocated(_) => None,
 		}
 	}
 }
@@ -3,6 +3,7 @@
 
 
 /// An extension trait to make it easier to use a Path to access a CTO pool.
+/// The Path can be a regular file, a DAX device or a PMDK-compatible pool set file (see `PoolSet`).
 pub trait CtoPoolPathExt
 {
 	/// Validate that an existing CTO pool is consistent.
@@ -10,31 +11,474 @@ pub trait CtoPoolPathExt
 	fn validate_cto_pool_is_consistent(&self, layout_name: &CStr) -> Result<bool, PmdkError>;
 	
 	/// Open an existing CTO pool.
+	/// If the address the pool was last mapped at is now occupied, the pool is mapped elsewhere (not overlapping that address) and relocated when opened by `CtoPool::open()`.
+	/// If relocating the pool was interrupted, it must be mapped at one of the addresses involved, and fails if both are occupied.
 	#[inline(always)]
 	fn open_cto_pool(&self, layout_name: &CStr) -> Result<*mut PMEMctopool, PmdkError>;
 	
+	/// Open an existing CTO pool read-only; nothing is written to it, not even its shutdown state.
+	/// A read-only pool can not be relocated, so fails if the address the pool was last mapped at is now occupied.
+	#[inline(always)]
+	fn open_cto_pool_read_only(&self, layout_name: &CStr) -> Result<*mut PMEMctopool, PmdkError>;
+	
 	/// Create (and implicitly open) a new CTO pool.
 	#[inline(always)]
 	fn create_cto_pool(&self, layout_name: &CStr, pool_size: usize, mode: mode_t) -> Result<*mut PMEMctopool, PmdkError>;
+	
+	/// Create a new, empty CTO pool and then close it; returns `false` if there is already a pool.
+	#[inline(always)]
+	fn create_empty_cto_pool(&self, layout_name: &CStr, pool_size: usize, mode: mode_t) -> Result<bool, PmdkError>;
+	
+	/// What is recorded about an existing CTO pool in its header, and how its memory is used; the pool is opened read-only and closed again.
+	#[inline(always)]
+	fn cto_pool_information(&self, layout_name: &CStr) -> Result<CtoPoolInformation, PmdkError>;
+	
+	/// Checks the integrity of an existing CTO pool, which is opened read-only and closed again; returns what was found to be wrong, which is empty for a sound pool.
+	/// As the type of the root is not known, only the root directory and the pointers to the root and named roots are checked; block allocators are only found (and their metadata checked) by walking from a typed root, so use `ReadOnlyCtoPool::check()` to walk everything reachable from the root.
+	#[inline(always)]
+	fn check_cto_pool(&self, layout_name: &CStr) -> Result<Vec<CtoPoolCheckFinding>, PmdkError>;
+	
+	/// The raw header and heap metadata of an existing CTO pool, which is opened read-only and closed again.
+	#[inline(always)]
+	fn dump_cto_pool(&self, layout_name: &CStr) -> Result<CtoPoolDump, PmdkError>;
+	
+	/// Grow an open CTO pool by at least `additional_size` bytes (rounded up to the memory mapping alignment) without closing it.
+	/// A regular file is extended; for a pool set, the last part is extended, and so must be a regular file.
+	/// The memory the pool grows by is mapped immediately after the end of the pool's mapping, so growing fails if that address range is occupied.
+	/// `cto_pool` must have been created or opened from this path.
+	#[inline(always)]
+	fn grow_cto_pool(&self, cto_pool: *mut PMEMctopool, additional_size: usize) -> Result<(), PmdkError>;
+	
+	/// Grow an open CTO pool without closing it by adding a new part, `part_path`, to the end of this pool set file.
+	/// `part_path` must be absolute and must not exist; it is created as a regular file of `part_size` bytes.
+	/// The part is mapped immediately after the end of the pool's mapping, so growing fails if that address range is occupied.
+	/// `cto_pool` must have been created or opened from this pool set file.
+	#[inline(always)]
+	fn grow_cto_pool_by_adding_part(&self, cto_pool: *mut PMEMctopool, part_path: &Path, part_size: usize, mode: mode_t) -> Result<(), PmdkError>;
+	
+	#[doc(hidden)]
+	#[inline(always)]
+	fn with_cto_pool_read_only<R, User: FnOnce(&PMEMctopool) -> Result<R, PmdkError>>(&self, layout_name: &CStr, user: User) -> Result<R, PmdkError>;
+	
+	#[doc(hidden)]
+	#[inline(always)]
+	fn cto_pool_to_grow<'pool>(&self, cto_pool: *mut PMEMctopool) -> Result<&'pool PMEMctopool, PmdkError>;
+	
+	#[doc(hidden)]
+	#[inline(always)]
+	fn create_cto_pool_files(&self, pool_size: usize, mode: mode_t) -> Result<Option<Vec<PathBuf>>, PmdkError>;
+	
+	#[doc(hidden)]
+	#[inline(always)]
+	fn memory_map_cto_pool(&self, read_only: bool, memory_map_address: MemoryMapAddress) -> Result<MappedMemory, PmdkError>;
+	
+	/// `None` if the address range is occupied; an existing mapping is never replaced.
+	#[doc(hidden)]
+	#[inline(always)]
+	fn memory_map_cto_pool_at(&self, read_only: bool, unaligned_address: *mut u8) -> Result<Option<MappedMemory>, PmdkError>;
+	
+	#[doc(hidden)]
+	#[inline(always)]
+	fn memory_map_cto_pool_keeping_error(&self, read_only: bool, memory_map_address: MemoryMapAddress) -> Result<Result<MappedMemory, CouldNotMemoryMapError>, PmdkError>;
+	
+	/// `None` if it can not be found, eg because the pool is not on an NVDIMM.
+	#[doc(hidden)]
+	#[inline(always)]
+	fn nvdimm_unsafe_shutdown_count(&self) -> Option<u64>;
 }
 
 impl CtoPoolPathExt for Path
 {
 	#[inline(always)]
-	fn validate_cto_pool_is_consistent(&self, _layout_name: &CStr) -> Result<bool, PmdkError>
+	fn validate_cto_pool_is_consistent(&self, layout_name: &CStr) -> Result<bool, PmdkError>
+	{
+		let mapped_memory = self.memory_map_cto_pool(true, MemoryMapAddress::Random)?;
+		
+		PMEMctopool::is_consistent(&mapped_memory, layout_name)
+	}
+	
+	#[inline(always)]
+	fn open_cto_pool(&self, layout_name: &CStr) -> Result<*mut PMEMctopool, PmdkError>
+	{
+		const MaximumRelocationAlignmentLog2: u32 = 30;
+		const MaximumRelocationAttempts: usize = 8;
+		
+		// Pointers inside the pool are absolute, so the pool is mapped at the address it was last mapped at if possible.
+		let (mapped_base_address, relocating_to_address, length) =
+		{
+			let probe = self.memory_map_cto_pool(true, MemoryMapAddress::Random)?;
+			let (mapped_base_address, relocating_to_address) = PMEMctopool::mapped_base_addresses(&probe, layout_name)?;
+			(mapped_base_address as *mut u8, relocating_to_address.map(|relocating_to_address| relocating_to_address as *mut u8), probe.length())
+		};
+		
+		let mapped_memory = if let Some(mapped_memory) = self.memory_map_cto_pool_at(false, mapped_base_address)?
+		{
+			mapped_memory
+		}
+		else if let Some(relocating_to_address) = relocating_to_address
+		{
+			// An interrupted relocation can only be completed at one of the addresses involved, as pointers in the pool may be relative to either.
+			match self.memory_map_cto_pool_at(false, relocating_to_address)?
+			{
+				Some(mapped_memory) => mapped_memory,
+				None => return Err(PmdkError::MappingFailed(format!("relocating the pool from '{:?}' to '{:?}' was interrupted, and can only be completed at one of those addresses, but both are occupied", mapped_base_address, relocating_to_address))),
+			}
+		}
+		else
+		{
+			// Otherwise, relocate by a multiple of the old address's alignment, so that aligned allocations stay aligned, to an address that does not overlap the old mapping, so that pointers that have already been relocated can be told apart from those that have not.
+			let relocation_alignment = 1 << min((mapped_base_address as usize).trailing_zeros(), MaximumRelocationAlignmentLog2);
+			let find = |minimum_address: *mut u8| match find_lowest_unoccupied_address_in_process_map(minimum_address, length, relocation_alignment)
+			{
+				Ok(Some(unoccupied_address)) => Ok(unoccupied_address),
+				Ok(None) => Err(CouldNotMemoryMapError::CouldNotFindAContiguousRegionToMemoryMapInto(length as u64, relocation_alignment)),
+				Err(error) => Err(CouldNotMemoryMapError::CouldNotParseOccupiedMemoryMap(error)),
+			};
+			
+			// Another thread can map memory between finding an unoccupied address and mapping the pool at it, so try again a few times.
+			let mut attempts = 0;
+			loop
+			{
+				let unoccupied_address = find(null_mut())?;
+				let unoccupied_address = if (unoccupied_address as usize) + length > mapped_base_address as usize && (unoccupied_address as usize) < (mapped_base_address as usize) + length
+				{
+					find(((mapped_base_address as usize) + length) as *mut u8)?
+				}
+				else
+				{
+					unoccupied_address
+				};
+				
+				if let Some(mapped_memory) = self.memory_map_cto_pool_at(false, unoccupied_address)?
+				{
+					break mapped_memory
+				}
+				
+				attempts += 1;
+				if attempts == MaximumRelocationAttempts
+				{
+					return Err(CouldNotMemoryMapError::CouldNotFindAContiguousRegionToMemoryMapInto(length as u64, relocation_alignment).into())
+				}
+			}
+		};
+		
+		PMEMctopool::open(mapped_memory, layout_name, self.nvdimm_unsafe_shutdown_count())
+	}
+	
+	#[inline(always)]
+	fn open_cto_pool_read_only(&self, layout_name: &CStr) -> Result<*mut PMEMctopool, PmdkError>
+	{
+		let (mapped_base_address, length) =
+		{
+			let probe = self.memory_map_cto_pool(true, MemoryMapAddress::Random)?;
+			(PMEMctopool::mapped_base_addresses(&probe, layout_name)?.0, probe.length())
+		};
+		
+		let mapped_base_address = mapped_base_address as *mut u8;
+		let mapped_memory = match self.memory_map_cto_pool_at(true, mapped_base_address)?
+		{
+			Some(mapped_memory) => mapped_memory,
+			None => return Err(PmdkError::MappingFailed(format!("the address '{:?}' the pool was last mapped at is occupied, and a read-only pool can not be relocated", mapped_base_address))),
+		};
+		PMEMctopool::open_read_only(mapped_memory, layout_name, self.nvdimm_unsafe_shutdown_count())
+	}
+	
+	#[inline(always)]
+	fn create_cto_pool(&self, layout_name: &CStr, pool_size: usize, mode: mode_t) -> Result<*mut PMEMctopool, PmdkError>
+	{
+		let created_files = match self.create_cto_pool_files(pool_size, mode)?
+		{
+			None => return Ok(null_mut()),
+			Some(created_files) => created_files,
+		};
+		
+		let result = self.memory_map_cto_pool(false, MemoryMapAddress::Random).and_then(|mapped_memory|
+		{
+			// A DAX device always exists; it contains a pool if a pool was ever created on it.
+			if PMEMctopool::contains_a_pool(&mapped_memory)
+			{
+				Ok(null_mut())
+			}
+			else
+			{
+				PMEMctopool::create(mapped_memory, layout_name, self.nvdimm_unsafe_shutdown_count())
+			}
+		});
+		
+		if result.is_err()
+		{
+			for created_file in created_files.iter()
+			{
+				let _ = remove_file(created_file);
+			}
+		}
+		result
+	}
+	
+	#[inline(always)]
+	fn create_empty_cto_pool(&self, layout_name: &CStr, pool_size: usize, mode: mode_t) -> Result<bool, PmdkError>
+	{
+		let cto_pool = self.create_cto_pool(layout_name, pool_size, mode)?;
+		if cto_pool.is_null()
+		{
+			Ok(false)
+		}
+		else
+		{
+			cto_pool.close();
+			Ok(true)
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_information(&self, layout_name: &CStr) -> Result<CtoPoolInformation, PmdkError>
+	{
+		self.with_cto_pool_read_only(layout_name, |pool| Ok(pool.information()))
+	}
+	
+	#[inline(always)]
+	fn check_cto_pool(&self, layout_name: &CStr) -> Result<Vec<CtoPoolCheckFinding>, PmdkError>
+	{
+		self.with_cto_pool_read_only(layout_name, |pool| pool.check_untyped())
+	}
+	
+	#[inline(always)]
+	fn dump_cto_pool(&self, layout_name: &CStr) -> Result<CtoPoolDump, PmdkError>
+	{
+		self.with_cto_pool_read_only(layout_name, |pool| pool.dump())
+	}
+	
+	#[inline(always)]
+	fn grow_cto_pool(&self, cto_pool: *mut PMEMctopool, additional_size: usize) -> Result<(), PmdkError>
+	{
+		let pool = self.cto_pool_to_grow(cto_pool)?;
+		
+		pool.grow(|length|
+		{
+			let (part_path, offset, alignment) = if self.is_a_pool_set_file()
+			{
+				let pool_set = self.parse_pool_set_file()?;
+				let (contiguous_parts, alignment) = pool_set.master_replica().contiguous_parts(pool_set.headers(), PMEMctopool::alignment())?;
+				PMEMctopool::check_pool_set_is_unchanged(&contiguous_parts[..], length)?;
+				
+				let last_part = contiguous_parts.last().unwrap();
+				(last_part.path.to_path_buf(), last_part.offset + last_part.length as u64, alignment)
+			}
+			else
+			{
+				(self.to_path_buf(), length as u64, PMEMctopool::alignment())
+			};
+			
+			if part_path.is_this_a_dax_device()
+			{
+				return Err(PmdkError::CanNotGrow(format!("'{:?}' is a DAX device, which can not be extended", part_path)))
+			}
+			
+			if offset % (alignment as u64) != 0
+			{
+				return Err(PmdkError::CanNotGrow(format!("'{:?}' is mapped up to '{}' bytes, which is not a multiple of the alignment '{}'", part_path, offset, alignment)))
+			}
+			
+			let file_size = part_path.metadata()?.len();
+			if file_size >= offset + alignment as u64
+			{
+				return Err(PmdkError::CanNotGrow(format!("'{:?}' has grown since the pool was opened", part_path)))
+			}
+			
+			let additional_size = additional_size.round_up_to_alignment(alignment);
+			let file = OpenOptions::new().write(true).open(&part_path)?;
+			file.set_len(offset + additional_size as u64)?;
+			
+			let result = pool.memory_map_extension(length, ContiguousPart { path: &part_path, offset, length: additional_size }, alignment);
+			if result.is_err()
+			{
+				let _ = file.set_len(file_size);
+			}
+			result
+		})
+	}
+	
+	#[inline(always)]
+	fn grow_cto_pool_by_adding_part(&self, cto_pool: *mut PMEMctopool, part_path: &Path, part_size: usize, mode: mode_t) -> Result<(), PmdkError>
+	{
+		if !self.is_a_pool_set_file()
+		{
+			return Err(PmdkError::CanNotGrow(format!("'{:?}' is not a pool set file, so a part can not be added to it", self)))
+		}
+		
+		if !part_path.is_absolute()
+		{
+			return Err(PmdkError::CanNotGrow(format!("part_path '{:?}' is not absolute", part_path)))
+		}
+		
+		let pool = self.cto_pool_to_grow(cto_pool)?;
+		
+		pool.grow(|length|
+		{
+			let pool_set = self.parse_pool_set_file()?;
+			let (contiguous_parts, alignment) = pool_set.master_replica().contiguous_parts(pool_set.headers(), PMEMctopool::alignment())?;
+			PMEMctopool::check_pool_set_is_unchanged(&contiguous_parts[..], length)?;
+			
+			let offset = pool_set.headers().offset_of_part(contiguous_parts.len(), alignment);
+			let part_length = ((part_size as u64).saturating_sub(offset) as usize) & !(alignment - 1);
+			if part_length == 0
+			{
+				return Err(CouldNotMemoryMapError::PartIsTooSmall(part_path.to_path_buf()).into())
+			}
+			
+			let file = OpenOptions::new().write(true).create_new(true).mode(mode as u32).open(part_path)?;
+			let result = file.set_len(part_size as u64).map_err(PmdkError::from).and_then(|()|
+			{
+				let extension = pool.memory_map_extension(length, ContiguousPart { path: part_path, offset, length: part_length }, alignment)?;
+				
+				// Once the part is in the pool set file, the pool is grown even if a crash occurs before the heap has been told.
+				self.append_part_to_pool_set_file(part_size as u64, part_path)?;
+				Ok(extension)
+			});
+			
+			if result.is_err()
+			{
+				drop(file);
+				let _ = remove_file(part_path);
+			}
+			result
+		})
+	}
+	
+	#[inline(always)]
+	fn with_cto_pool_read_only<R, User: FnOnce(&PMEMctopool) -> Result<R, PmdkError>>(&self, layout_name: &CStr, user: User) -> Result<R, PmdkError>
+	{
+		let cto_pool = self.open_cto_pool_read_only(layout_name)?;
+		let result = user(cto_pool.pool());
+		cto_pool.close();
+		result
+	}
+	
+	#[inline(always)]
+	fn cto_pool_to_grow<'pool>(&self, cto_pool: *mut PMEMctopool) -> Result<&'pool PMEMctopool, PmdkError>
+	{
+		if cto_pool.is_null()
+		{
+			return Err(PmdkError::PoolClosed)
+		}
+		
+		if cto_pool.is_read_only()
+		{
+			return Err(PmdkError::ReadOnly)
+		}
+		
+		let pool = cto_pool.pool();
+		
+		let probe = self.memory_map_cto_pool(true, MemoryMapAddress::Random)?;
+		if pool.is_same_pool(&probe)
+		{
+			Ok(pool)
+		}
+		else
+		{
+			Err(PmdkError::CanNotGrow(format!("'{:?}' does not contain the pool being grown", self)))
+		}
+	}
+	
+	#[inline(always)]
+	fn create_cto_pool_files(&self, pool_size: usize, mode: mode_t) -> Result<Option<Vec<PathBuf>>, PmdkError>
+	{
+		if self.is_a_pool_set_file()
+		{
+			// Part sizes come from the pool set file, not `pool_size`.
+			let pool_set = self.parse_pool_set_file()?;
+			return Ok(pool_set.master_replica().create_part_files(mode)?)
+		}
+		
+		if self.is_this_a_dax_device()
+		{
+			return Ok(Some(Vec::new()))
+		}
+		
+		match OpenOptions::new().write(true).create_new(true).mode(mode as u32).open(self)
+		{
+			Err(ref error) if error.kind() == ErrorKind::AlreadyExists => Ok(None),
+			
+			Err(error) => Err(error.into()),
+			
+			Ok(file) => match file.set_len(pool_size as u64)
+			{
+				Err(error) =>
+				{
+					drop(file);
+					let _ = remove_file(self);
+					Err(error.into())
+				}
+				
+				Ok(()) => Ok(Some(vec![self.to_path_buf()])),
+			},
+		}
+	}
+	
+	#[inline(always)]
+	fn memory_map_cto_pool(&self, read_only: bool, memory_map_address: MemoryMapAddress) -> Result<MappedMemory, PmdkError>
+	{
+		Ok(self.memory_map_cto_pool_keeping_error(read_only, memory_map_address)??)
+	}
+	
+	#[inline(always)]
+	fn memory_map_cto_pool_at(&self, read_only: bool, unaligned_address: *mut u8) -> Result<Option<MappedMemory>, PmdkError>
+	{
+		match self.memory_map_cto_pool_keeping_error(read_only, MemoryMapAddress::Mandatory { unaligned_address })?
+		{
+			Ok(mapped_memory) => Ok(Some(mapped_memory)),
+			Err(CouldNotMemoryMapError::AddressOccupied(_, _)) => Ok(None),
+			Err(error) => Err(error.into()),
+		}
+	}
+	
+	#[inline(always)]
+	fn memory_map_cto_pool_keeping_error(&self, read_only: bool, memory_map_address: MemoryMapAddress) -> Result<Result<MappedMemory, CouldNotMemoryMapError>, PmdkError>
 	{
-		unimplemented!()
+		if self.is_a_pool_set_file()
+		{
+			let pool_set = self.parse_pool_set_file()?;
+			
+			// Like libpmemcto, replicas are not supported.
+			let number_of_replicas = pool_set.replicas().len();
+			if number_of_replicas != 1
+			{
+				return Err(PmdkError::InvalidPoolSet(format!("Replicas are not supported but there are '{}'", number_of_replicas)))
+			}
+			
+			Ok(pool_set.memory_map_master_replica(read_only, memory_map_address, PMEMctopool::alignment()))
+		}
+		else
+		{
+			Ok(self.memory_map(read_only, memory_map_address, 0, PMEMctopool::alignment()))
+		}
 	}
 	
+	#[cfg(any(target_os = "android", target_os = "linux"))]
 	#[inline(always)]
-	fn open_cto_pool(&self, _layout_name: &CStr) -> Result<*mut PMEMctopool, PmdkError>
+	fn nvdimm_unsafe_shutdown_count(&self) -> Option<u64>
 	{
-		unimplemented!()
+		if self.is_a_pool_set_file()
+		{
+			let pool_set = self.parse_pool_set_file().ok()?;
+			
+			let mut unsafe_shutdown_count = 0;
+			for part in pool_set.master_replica().parts().iter()
+			{
+				unsafe_shutdown_count += part.path().find_nvdimm_unsafe_shutdown_count().ok()?;
+			}
+			Some(unsafe_shutdown_count)
+		}
+		else
+		{
+			self.find_nvdimm_unsafe_shutdown_count().ok()
+		}
 	}
 	
+	#[cfg(not(any(target_os = "android", target_os = "linux")))]
 	#[inline(always)]
-	fn create_cto_pool(&self, _layout_name: &CStr, _pool_size: usize, _mode: mode_t) -> Result<*mut PMEMctopool, PmdkError>
+	fn nvdimm_unsafe_shutdown_count(&self) -> Option<u64>
 	{
-		unimplemented!()
+		None
 	}
 }
@@ -0,0 +1,49 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// The reference counts held by a reference counted value, and the number of strong references to it found by a `CtoPoolTraversal`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct CtoPoolReferenceCounts
+{
+	strong_count: usize,
+	found_strong_references: usize,
+	weak_count_and_expected_weak_count: Option<(usize, usize)>,
+}
+
+impl CtoPoolReferenceCounts
+{
+	#[inline(always)]
+	fn new(strong_count: usize, weak_count_and_expected_weak_count: Option<(usize, usize)>) -> Self
+	{
+		Self
+		{
+			strong_count,
+			found_strong_references: 0,
+			weak_count_and_expected_weak_count,
+		}
+	}
+	
+	#[inline(always)]
+	fn found_strong_reference(&mut self)
+	{
+		self.found_strong_references += 1
+	}
+	
+	#[inline(always)]
+	fn check(&self, address: usize, findings: &mut Vec<CtoPoolCheckFinding>)
+	{
+		if self.strong_count != self.found_strong_references
+		{
+			findings.push(CtoPoolCheckFinding::StrongCountMismatch(address, self.strong_count, self.found_strong_references))
+		}
+		
+		if let Some((weak_count, expected_weak_count)) = self.weak_count_and_expected_weak_count
+		{
+			if weak_count != expected_weak_count
+			{
+				findings.push(CtoPoolCheckFinding::WeakCountMismatch(address, weak_count, expected_weak_count))
+			}
+		}
+	}
+}
@@ -0,0 +1,30 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A pool in the `CtoPoolRegistry`, mapped from its inclusive start address (the key in the registry) to `exclusive_end_address`.
+/// `cto_pool_arc_inner` is that of the `CtoPoolArc` the pool was opened with, if any.
+/// `block_allocators` holds the block allocators in the pool, by the inclusive start address of their blocks, with the exclusive end address of their blocks, their address and the name of their type (which, as the registry is not persisted, need only be unique within a process).
+#[derive(Debug)]
+struct CtoPoolRegistration
+{
+	exclusive_end_address: usize,
+	pool_pointer: *mut PMEMctopool,
+	cto_pool_arc_inner: Option<NonNull<CtoPoolArcInner>>,
+	block_allocators: BTreeMap<usize, (usize, usize, &'static str)>,
+}
+
+impl CtoPoolRegistration
+{
+	#[inline(always)]
+	fn new(exclusive_end_address: usize, pool_pointer: *mut PMEMctopool) -> Self
+	{
+		Self
+		{
+			exclusive_end_address,
+			pool_pointer,
+			cto_pool_arc_inner: None,
+			block_allocators: BTreeMap::new(),
+		}
+	}
+}
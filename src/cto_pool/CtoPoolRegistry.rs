@@ -0,0 +1,251 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A process-wide registry of open pools, by the address range they are mapped at, so that the pool (and the `BlockAllocator`) that owns any pointer can be found.
+/// This allows, for example, a pointer to be checked as belonging to a particular pool before it is stored in that pool.
+///
+/// Pools are registered when created or opened (including when opened read-only), and unregistered when closed; their address ranges are updated when they are grown.
+/// A `BlockAllocator` is registered when created, grown or opened (ie when `cto_pool_opened()` is called on it), but only if it is inside a registered pool; it is unregistered when that pool is closed, or when the memory it is in is freed.
+#[derive(Debug)]
+pub struct CtoPoolRegistry
+{
+	pools: BTreeMap<usize, CtoPoolRegistration>,
+}
+
+unsafe impl Send for CtoPoolRegistry
+{
+}
+
+unsafe impl Sync for CtoPoolRegistry
+{
+}
+
+impl CtoPoolRegistry
+{
+	/// The `CtoPoolArc` of the open pool that `pointer` points into.
+	/// Returns `None` if `pointer` does not point into an open pool, or if the pool was not opened as a `CtoPool` or `ReadOnlyCtoPool`, or if it is being closed.
+	#[inline(always)]
+	pub fn cto_pool_arc_owning<T>(pointer: *const T) -> Option<CtoPoolArc>
+	{
+		let registry = Self::registry().read();
+		
+		let cto_pool_arc_inner = registry.registration_owning(pointer as usize)?.cto_pool_arc_inner?;
+		if unsafe { cto_pool_arc_inner.as_ref() }.try_acquire_reference()
+		{
+			Some
+			(
+				CtoPoolArc
+				{
+					cto_pool_arc_inner,
+				}
+			)
+		}
+		else
+		{
+			None
+		}
+	}
+	
+	/// The open pool that `pointer` points into, if any.
+	/// An object in a pool (eg a `CtoBox`) does not hold a `CtoPoolArc`, but uses this to find the pool to free itself from; if there is none, the pool has been closed, and the object is leaked rather than freed.
+	#[inline(always)]
+	pub fn pool_pointer_owning<T>(pointer: *const T) -> Option<*mut PMEMctopool>
+	{
+		Self::registry().read().registration_owning(pointer as usize).map(|registration| registration.pool_pointer)
+	}
+	
+	/// Do `pointer` and `other_pointer` point into the same open pool?
+	/// Returns `false` if either does not point into an open pool.
+	#[inline(always)]
+	pub fn are_in_same_pool<T, U>(pointer: *const T, other_pointer: *const U) -> bool
+	{
+		let registry = Self::registry().read();
+		
+		match (registry.registration_owning(pointer as usize), registry.registration_owning(other_pointer as usize))
+		{
+			(Some(registration), Some(other_registration)) => registration.pool_pointer == other_registration.pool_pointer,
+			_ => false,
+		}
+	}
+	
+	/// Checks that `target` can be stored at `location`: if `location` is in an open pool, `target` must point into the same pool.
+	/// Nothing is checked if `location` is not in an open pool (eg it is on the stack, being initialized before being moved into a pool).
+	/// Used by checked constructors and setters such as `CtoArcCell::checked_set()`, and by their unchecked equivalents in debug builds (or with the `pointer-checks` feature); use it for the same purpose in other `CtoSafe` types.
+	#[inline(always)]
+	pub fn check_pointer<L, T>(location: *const L, target: *const T) -> Result<(), CtoPointerError>
+	{
+		let location = location as usize;
+		let target = target as usize;
+		
+		let registry = Self::registry().read();
+		
+		match registry.registration_owning(location)
+		{
+			None => Ok(()),
+			Some(registration) => match registry.registration_owning(target)
+			{
+				None => Err(CtoPointerError::VolatileTarget(location, target)),
+				Some(target_registration) => if target_registration.pool_pointer == registration.pool_pointer
+				{
+					Ok(())
+				}
+				else
+				{
+					Err(CtoPointerError::DifferentPool(location, target))
+				},
+			},
+		}
+	}
+	
+	/// Checks that `target` points into the pool of `cto_pool_arc`.
+	#[inline(always)]
+	pub fn check_pointer_is_in_pool<T>(cto_pool_arc: &CtoPoolArc, target: *const T) -> Result<(), CtoPointerError>
+	{
+		Self::check_pointer(cto_pool_arc.pool_pointer().pool().mapped_memory.as_ptr(), target)
+	}
+	
+	/// The `BlockAllocator` whose blocks `pointer` points into, if any.
+	/// Returns `None` if the block allocator is not a `BlockAllocator<P>`.
+	#[inline(always)]
+	pub fn block_allocator_owning<P: Persistence, T>(pointer: *const T) -> Option<NonNull<BlockAllocator<P>>>
+	{
+		let address = pointer as usize;
+		
+		let registry = Self::registry().read();
+		let registration = registry.registration_owning(address)?;
+		
+		let (_, &(blocks_memory_exclusive_end_address, block_allocator_address, type_name)) = registration.block_allocators.range(.. address + 1).next_back()?;
+		if address < blocks_memory_exclusive_end_address && type_name == BlockAllocator::<P>::type_name()
+		{
+			Some(unsafe { NonNull::new_unchecked(block_allocator_address as *mut BlockAllocator<P>) })
+		}
+		else
+		{
+			None
+		}
+	}
+	
+	#[inline(always)]
+	fn register_pool(pool_pointer: *mut PMEMctopool, inclusive_start_address: usize, exclusive_end_address: usize)
+	{
+		Self::registry().write().pools.insert(inclusive_start_address, CtoPoolRegistration::new(exclusive_end_address, pool_pointer));
+	}
+	
+	#[inline(always)]
+	fn resize_pool(inclusive_start_address: usize, exclusive_end_address: usize)
+	{
+		if let Some(registration) = Self::registry().write().pools.get_mut(&inclusive_start_address)
+		{
+			registration.exclusive_end_address = exclusive_end_address
+		}
+	}
+	
+	#[inline(always)]
+	fn unregister_pool(inclusive_start_address: usize)
+	{
+		Self::registry().write().pools.remove(&inclusive_start_address);
+	}
+	
+	#[inline(always)]
+	fn register_cto_pool_arc(pool_pointer: *mut PMEMctopool, cto_pool_arc_inner: NonNull<CtoPoolArcInner>)
+	{
+		let mut registry = Self::registry().write();
+		if let Some(registration) = registry.pools.values_mut().find(|registration| registration.pool_pointer == pool_pointer)
+		{
+			registration.cto_pool_arc_inner = Some(cto_pool_arc_inner)
+		}
+	}
+	
+	#[inline(always)]
+	fn register_block_allocator(block_allocator_address: usize, blocks_memory_inclusive_start_address: usize, blocks_memory_exclusive_end_address: usize, type_name: &'static str) -> bool
+	{
+		let mut registry = Self::registry().write();
+		match registry.registration_owning_mut(block_allocator_address)
+		{
+			None => false,
+			Some(registration) => registration.block_allocators.insert(blocks_memory_inclusive_start_address, (blocks_memory_exclusive_end_address, block_allocator_address, type_name)).is_none(),
+		}
+	}
+	
+	/// Unregisters the block allocators in memory that is being freed.
+	#[inline(always)]
+	fn unregister_block_allocators_in(inclusive_start_address: usize, exclusive_end_address: usize)
+	{
+		let is_in = |block_allocator_address: usize| block_allocator_address >= inclusive_start_address && block_allocator_address < exclusive_end_address;
+		
+		// Nearly all memory freed holds no block allocator, so only take the write lock if there is one to unregister.
+		{
+			let registry = Self::registry().read();
+			match registry.registration_owning(inclusive_start_address)
+			{
+				None => return,
+				Some(registration) => if !registration.block_allocators.values().any(|&(_, block_allocator_address, _)| is_in(block_allocator_address))
+				{
+					return
+				},
+			}
+		}
+		
+		let mut registry = Self::registry().write();
+		if let Some(registration) = registry.registration_owning_mut(inclusive_start_address)
+		{
+			let freed: Vec<usize> = registration.block_allocators.iter().filter(|&(_, &(_, block_allocator_address, _))| is_in(block_allocator_address)).map(|(&blocks_memory_inclusive_start_address, _)| blocks_memory_inclusive_start_address).collect();
+			for blocks_memory_inclusive_start_address in freed
+			{
+				registration.block_allocators.remove(&blocks_memory_inclusive_start_address);
+			}
+		}
+	}
+	
+	#[inline(always)]
+	fn registration_owning(&self, address: usize) -> Option<&CtoPoolRegistration>
+	{
+		match self.pools.range(.. address + 1).next_back()
+		{
+			Some((_, registration)) if address < registration.exclusive_end_address => Some(registration),
+			_ => None,
+		}
+	}
+	
+	#[inline(always)]
+	fn registration_owning_mut(&mut self, address: usize) -> Option<&mut CtoPoolRegistration>
+	{
+		match self.pools.range_mut(.. address + 1).next_back()
+		{
+			Some((_, registration)) => if address < registration.exclusive_end_address
+			{
+				Some(registration)
+			}
+			else
+			{
+				None
+			},
+			None => None,
+		}
+	}
+	
+	/// Created when first used.
+	#[inline(always)]
+	fn registry() -> &'static RwLock<CtoPoolRegistry>
+	{
+		static Registry: AtomicPtr<RwLock<CtoPoolRegistry>> = AtomicPtr::new(null_mut());
+		
+		let registry = Registry.load(SeqCst);
+		if !registry.is_null()
+		{
+			return unsafe { & * registry }
+		}
+		
+		let new_registry = Box::into_raw(Box::new(RwLock::new(CtoPoolRegistry { pools: BTreeMap::new() })));
+		match Registry.compare_exchange(null_mut(), new_registry, SeqCst, SeqCst)
+		{
+			Ok(_) => unsafe { & * new_registry },
+			Err(registry) =>
+			{
+				drop(unsafe { Box::from_raw(new_registry) });
+				unsafe { & * registry }
+			}
+		}
+	}
+}
@@ -0,0 +1,114 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Describes how a CTO pool has moved since it was last opened.
+/// Passed to `CtoSafe::cto_pool_relocate()` so that absolute pointers into the pool can be rewritten.
+///
+/// Every changed pointer is persisted as it is rewritten, with the persistence suited to how the pool is mapped.
+#[derive(Debug)]
+pub struct CtoPoolRelocation
+{
+	old_inclusive_start_address: usize,
+	old_exclusive_end_address: usize,
+	new_inclusive_start_address: usize,
+	new_exclusive_end_address: usize,
+	visited: RefCell<HashSet<usize>>,
+	persistence: MappingPersistence,
+}
+
+impl CtoPoolRelocation
+{
+	/// Returns an error if the old and new mappings overlap, as pointers that have already been relocated could then not be told apart from those that have not.
+	#[inline(always)]
+	fn new(old_inclusive_start_address: usize, new_inclusive_start_address: usize, length: usize, persistence: MappingPersistence) -> Result<Self, PmdkError>
+	{
+		if old_inclusive_start_address < new_inclusive_start_address + length && new_inclusive_start_address < old_inclusive_start_address + length
+		{
+			return Err(PmdkError::MappingFailed(format!("the pool was last mapped at '0x{:X}' but is now mapped at '0x{:X}', which overlaps it, so it can not be relocated", old_inclusive_start_address, new_inclusive_start_address)))
+		}
+		
+		Ok
+		(
+			Self
+			{
+				old_inclusive_start_address,
+				old_exclusive_end_address: old_inclusive_start_address + length,
+				new_inclusive_start_address,
+				new_exclusive_end_address: new_inclusive_start_address + length,
+				visited: RefCell::new(HashSet::new()),
+				persistence,
+			}
+		)
+	}
+	
+	#[inline(always)]
+	fn old_inclusive_start_address(&self) -> usize
+	{
+		self.old_inclusive_start_address
+	}
+	
+	/// The relocated value of `pointer`.
+	/// A null pointer stays null, as does a pointer that has already been relocated (as happens when an interrupted relocation is completed); the old and new mappings never overlap, so the two can not be confused.
+	#[inline(always)]
+	pub fn relocated<T>(&self, pointer: *mut T) -> *mut T
+	{
+		if pointer.is_null()
+		{
+			return pointer
+		}
+		
+		let address = pointer as usize;
+		if address >= self.new_inclusive_start_address && address < self.new_exclusive_end_address
+		{
+			return pointer
+		}
+		
+		debug_assert!(address >= self.old_inclusive_start_address && address < self.old_exclusive_end_address, "pointer '{:?}' was not in the pool before relocation", pointer);
+		
+		(address - self.old_inclusive_start_address + self.new_inclusive_start_address) as *mut T
+	}
+	
+	/// Relocates a pointer in place, and persists it.
+	/// A null pointer stays null.
+	#[inline(always)]
+	pub fn relocate_pointer<T>(&self, pointer: &mut *mut T)
+	{
+		*pointer = self.relocated(*pointer);
+		self.persist(pointer)
+	}
+	
+	/// Relocates a pointer in place, and persists it.
+	#[inline(always)]
+	pub fn relocate_non_null<T>(&self, pointer: &mut NonNull<T>)
+	{
+		*pointer = unsafe { NonNull::new_unchecked(self.relocated(pointer.as_ptr())) };
+		self.persist(pointer)
+	}
+	
+	/// Relocates an atomic pointer in place, persists it and returns the relocated pointer.
+	/// A null pointer stays null.
+	#[inline(always)]
+	pub fn relocate_atomic_pointer<T>(&self, pointer: &AtomicPtr<T>) -> *mut T
+	{
+		let relocated = self.relocated(pointer.load(SeqCst));
+		pointer.store(relocated, SeqCst);
+		self.persist(pointer);
+		relocated
+	}
+	
+	/// Returns `true` the first time it is called for a (relocated) pointer, and `false` thereafter.
+	/// Anything that can be referred to more than once (eg the inside of a `CtoArc`) must check this before relocating its contents.
+	#[inline(always)]
+	pub fn is_first_visit<T>(&self, relocated_pointer: *const T) -> bool
+	{
+		self.visited.borrow_mut().insert(relocated_pointer as usize)
+	}
+	
+	/// Persists a value that has been changed by relocation.
+	#[inline(always)]
+	pub fn persist<T>(&self, value: &T)
+	{
+		self.persistence.persist_memory(value as *const T as usize, size_of::<T>())
+	}
+}
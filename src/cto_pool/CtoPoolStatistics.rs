@@ -0,0 +1,49 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A snapshot of how the memory in a CTO pool is used; see `CtoPool::statistics()`.
+/// Sizes are in bytes.
+/// `allocated_size` includes the overhead of each allocation (its chunk header and any padding); neither it nor `free_size` includes the pool's header.
+/// The numbers are consistent with each other when taken, but allocations by other threads may change them immediately afterwards.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct CtoPoolStatistics
+{
+	/// Size of the pool, including any memory it has grown by.
+	pub total_size: usize,
+	
+	/// Bytes used by allocations.
+	pub allocated_size: usize,
+	
+	/// Bytes available for allocations.
+	pub free_size: usize,
+	
+	/// Number of allocations.
+	pub number_of_allocations: usize,
+	
+	/// Size of the largest free extent; an allocation (plus its overhead) larger than this will fail.
+	pub largest_free_extent: usize,
+}
+
+impl CtoPoolStatistics
+{
+	/// Fragmentation ratio, from 0.0 (all free memory is in one extent, or there is no free memory) tending towards 1.0 (free memory is split into many small extents).
+	#[inline(always)]
+	pub fn fragmentation(&self) -> f64
+	{
+		Self::fragmentation_ratio(self.free_size, self.largest_free_extent)
+	}
+	
+	#[inline(always)]
+	pub(crate) fn fragmentation_ratio(free_size: usize, largest_free_extent: usize) -> f64
+	{
+		if free_size == 0
+		{
+			0.0
+		}
+		else
+		{
+			1.0 - (largest_free_extent as f64 / free_size as f64)
+		}
+	}
+}
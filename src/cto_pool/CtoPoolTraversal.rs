@@ -0,0 +1,224 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Walks the object graph of a pool, without changing it, to check its integrity.
+/// Passed to `CtoSafe::cto_pool_traverse()`, which must check every pointer into the pool with `is_valid_pointer()` before following it.
+///
+/// Every pointer must land inside an allocation in the pool, and the strong counts of `CtoArc`, `CtoRc` and `CtoStrongArc` must match the references to them that are found; anything else is recorded as a `CtoPoolCheckFinding`.
+/// A pointer that does not pass these checks is not followed, so a corrupt pool can be walked without faulting.
+///
+/// Every allocation a valid pointer lands in, and every block of a `BlockAllocator` that is free or in reachable `Chains`, is marked as reached; what is left is leaked (see `CtoPoolLeaks`).
+#[derive(Debug)]
+pub struct CtoPoolTraversal
+{
+	inclusive_start_address: usize,
+	exclusive_end_address: usize,
+	allocations: BTreeMap<usize, usize>,
+	visited: RefCell<HashSet<usize>>,
+	reference_counts: RefCell<HashMap<usize, CtoPoolReferenceCounts>>,
+	findings: RefCell<Vec<CtoPoolCheckFinding>>,
+	reached_allocations: RefCell<HashSet<usize>>,
+	block_allocators: RefCell<BTreeMap<usize, CtoPoolBlockAllocatorMarks>>,
+	block_allocators_with_unwalked_chains: RefCell<HashSet<usize>>,
+	named_roots: RefCell<Vec<(String, usize)>>,
+}
+
+impl CtoPoolTraversal
+{
+	/// `allocations` maps the address of each allocation in the pool to the address just after its end.
+	#[inline(always)]
+	fn new(inclusive_start_address: usize, exclusive_end_address: usize, allocations: BTreeMap<usize, usize>) -> Self
+	{
+		Self
+		{
+			inclusive_start_address,
+			exclusive_end_address,
+			allocations,
+			visited: RefCell::new(HashSet::new()),
+			reference_counts: RefCell::new(HashMap::new()),
+			findings: RefCell::new(Vec::new()),
+			reached_allocations: RefCell::new(HashSet::new()),
+			block_allocators: RefCell::new(BTreeMap::new()),
+			block_allocators_with_unwalked_chains: RefCell::new(HashSet::new()),
+			named_roots: RefCell::new(Vec::new()),
+		}
+	}
+	
+	/// Walks `pointer` and everything reachable from it, if it is valid and has not been walked already.
+	/// Use this with a named root (eg from `ReadOnlyCtoPool::root()`) in the closure passed to `ReadOnlyCtoPool::check_with()`.
+	#[inline(always)]
+	pub fn traverse<T: CtoSafe>(&self, pointer: *const T)
+	{
+		if self.is_valid_pointer(pointer) && self.is_first_visit(pointer)
+		{
+			unsafe { & * pointer }.cto_pool_traverse(self)
+		}
+	}
+	
+	/// Checks that `pointer` points to a `T` that lies within an allocation in the pool, recording a finding if it does not, and marking the allocation as reached if it does.
+	/// Returns `true` if it does, and so can be followed, and `false` if it does not or is null.
+	#[inline(always)]
+	pub fn is_valid_pointer<T>(&self, pointer: *const T) -> bool
+	{
+		if pointer.is_null()
+		{
+			return false
+		}
+		
+		let address = pointer as usize;
+		if address < self.inclusive_start_address || address >= self.exclusive_end_address
+		{
+			self.record_finding(CtoPoolCheckFinding::PointerOutsidePool(address));
+			return false
+		}
+		
+		let alignment = align_of::<T>();
+		if address % alignment != 0
+		{
+			self.record_finding(CtoPoolCheckFinding::MisalignedPointer(address, alignment));
+			return false
+		}
+		
+		let size = size_of::<T>();
+		match self.allocation_containing(address)
+		{
+			Some((allocation_address, allocation_exclusive_end_address)) if size <= allocation_exclusive_end_address - address =>
+			{
+				self.reached_allocations.borrow_mut().insert(allocation_address);
+				true
+			}
+			
+			_ =>
+			{
+				self.record_finding(CtoPoolCheckFinding::PointerOutsideAllocation(address, size));
+				false
+			}
+		}
+	}
+	
+	/// Returns `true` the first time it is called for a pointer, and `false` thereafter.
+	/// Anything that can be referred to more than once (eg the inside of a `CtoArc`) must check this before walking its contents.
+	#[inline(always)]
+	pub fn is_first_visit<T>(&self, pointer: *const T) -> bool
+	{
+		self.visited.borrow_mut().insert(pointer as usize)
+	}
+	
+	/// Records a strong reference to the (valid) reference counted value at `pointer`, which has a strong count of `strong_count`.
+	/// `weak_count_and_expected_weak_count` is its weak count, if it has one, and what that should be.
+	#[inline(always)]
+	pub(crate) fn found_strong_reference<T>(&self, pointer: *const T, strong_count: usize, weak_count_and_expected_weak_count: Option<(usize, usize)>)
+	{
+		let mut reference_counts = self.reference_counts.borrow_mut();
+		reference_counts.entry(pointer as usize).or_insert_with(|| CtoPoolReferenceCounts::new(strong_count, weak_count_and_expected_weak_count)).found_strong_reference()
+	}
+	
+	#[inline(always)]
+	pub(crate) fn record_finding(&self, finding: CtoPoolCheckFinding)
+	{
+		self.findings.borrow_mut().push(finding)
+	}
+	
+	/// Records the (consistent) block allocator at `block_allocator_address`, whose free blocks are marked in `free_blocks`; `recycle_blocks` is used to free its leaked blocks.
+	#[inline(always)]
+	pub(crate) fn found_block_allocator(&self, block_allocator_address: usize, free_blocks: Vec<bool>, recycle_blocks: fn(usize, usize, usize))
+	{
+		self.block_allocators.borrow_mut().insert(block_allocator_address, CtoPoolBlockAllocatorMarks::new(free_blocks, recycle_blocks));
+	}
+	
+	/// Marks the blocks of a used chain in the block allocator at `block_allocator_address` as reached.
+	/// Returns `false` if any of them is free or in another used chain; returns `true` if the block allocator was not recorded by `found_block_allocator()`.
+	#[inline(always)]
+	pub(crate) fn found_used_blocks(&self, block_allocator_address: usize, block_index: usize, length: usize) -> bool
+	{
+		match self.block_allocators.borrow_mut().get_mut(&block_allocator_address)
+		{
+			None => true,
+			Some(block_allocator_marks) => block_allocator_marks.mark(block_index, block_index + length),
+		}
+	}
+	
+	/// Records that the block allocator at `block_allocator_address` has handed out chains that can not be walked (eg through a `BlockAllocatorAlloc`), so that its unreached blocks can not be known to be leaked.
+	#[inline(always)]
+	pub(crate) fn found_unwalked_chains(&self, block_allocator_address: usize)
+	{
+		self.block_allocators_with_unwalked_chains.borrow_mut().insert(block_allocator_address);
+	}
+	
+	/// Records the (valid) named root `name` at `address`, so that it can be known if it was not traversed.
+	#[inline(always)]
+	pub(crate) fn found_named_root(&self, name: String, address: usize)
+	{
+		self.named_roots.borrow_mut().push((name, address))
+	}
+	
+	/// Everything found, including mismatched reference counts, which are only known once everything has been walked.
+	#[inline(always)]
+	fn findings(&self) -> Vec<CtoPoolCheckFinding>
+	{
+		let mut findings = self.findings.borrow().clone();
+		
+		let reference_counts = self.reference_counts.borrow();
+		let mut addresses: Vec<usize> = reference_counts.keys().cloned().collect();
+		addresses.sort();
+		for address in addresses
+		{
+			reference_counts[&address].check(address, &mut findings)
+		}
+		
+		findings
+	}
+	
+	/// Everything allocated that was not reached; only known once everything has been walked.
+	#[inline(always)]
+	fn leaks(&self) -> CtoPoolLeaks
+	{
+		let reached_allocations = self.reached_allocations.borrow();
+		let allocations = self.allocations.iter().filter(|&(allocation_address, _)| !reached_allocations.contains(allocation_address)).map(|(&allocation_address, &allocation_exclusive_end_address)| (allocation_address, allocation_exclusive_end_address - allocation_address)).collect();
+		
+		let block_allocators_with_unwalked_chains = self.block_allocators_with_unwalked_chains.borrow();
+		let mut blocks = Vec::new();
+		for (&block_allocator_address, block_allocator_marks) in self.block_allocators.borrow().iter()
+		{
+			if !block_allocators_with_unwalked_chains.contains(&block_allocator_address)
+			{
+				block_allocator_marks.unreached_blocks(block_allocator_address, &mut blocks)
+			}
+		}
+		let mut unwalked_block_allocators: Vec<usize> = block_allocators_with_unwalked_chains.iter().cloned().collect();
+		unwalked_block_allocators.sort();
+		
+		let visited = self.visited.borrow();
+		let untraversed_named_roots = self.named_roots.borrow().iter().filter(|&&(_, address)| !visited.contains(&address)).map(|&(ref name, _)| name.clone()).collect();
+		
+		CtoPoolLeaks
+		{
+			allocations,
+			blocks,
+			unwalked_block_allocators,
+			untraversed_named_roots,
+		}
+	}
+	
+	/// Returns the leaked blocks in `cto_pool_leaks` to their block allocators.
+	#[inline(always)]
+	fn recycle_leaked_blocks(&self, cto_pool_leaks: &CtoPoolLeaks)
+	{
+		let block_allocators = self.block_allocators.borrow();
+		for &(block_allocator_address, inclusive_from_block_index, number_of_blocks) in cto_pool_leaks.blocks.iter()
+		{
+			block_allocators[&block_allocator_address].recycle(block_allocator_address, inclusive_from_block_index, number_of_blocks)
+		}
+	}
+	
+	#[inline(always)]
+	fn allocation_containing(&self, address: usize) -> Option<(usize, usize)>
+	{
+		match self.allocations.range(..(address + 1)).next_back()
+		{
+			Some((&allocation_address, &allocation_exclusive_end_address)) if address < allocation_exclusive_end_address => Some((allocation_address, allocation_exclusive_end_address)),
+			_ => None,
+		}
+	}
+}
@@ -8,6 +8,33 @@ pub trait CtoSafe: Sized
 	#[doc(hidden)]
 	#[inline(always)]
 	fn cto_pool_opened(&mut self, cto_pool_arc: &CtoPoolArc);
+	
+	/// Called before `cto_pool_opened()` if a pool has been mapped at a different address to the one it was last mapped at.
+	/// Must relocate every pointer into the pool held by `self`, and then relocate whatever they point to (at most once; see `CtoPoolRelocation::is_first_visit()`).
+	/// Any `CtoPoolArc` held by `self` is not valid until `cto_pool_opened()` is called.
+	#[doc(hidden)]
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation);
+	
+	/// Called when checking the integrity of a pool (see `ReadOnlyCtoPool::check()`); must not change `self`.
+	/// Must check every pointer into the pool held by `self` with `CtoPoolTraversal::is_valid_pointer()`, and only if it is valid, traverse whatever it points to (at most once; see `CtoPoolTraversal::is_first_visit()`).
+	/// There is no default, as anything reachable only through a type that does not walk it would be freed by `CtoPool::collect_leaks()`; a type that holds no pointers into the pool should do nothing.
+	#[doc(hidden)]
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal);
+	
+	/// A hash of the schema of this type (eg of the names, types and order of its fields); it is recorded in a pool with a root of this type, so that the pool can not later be opened with a root type that has a different schema.
+	/// There is no default, as it can not be derived from the fields, and the names of types are not stable between releases of Rust; use `TypeFingerprint::schema_hash_of()` with a description of the fields that is changed whenever they are, and `TypeFingerprint::combine_schema_hashes()` to include the schema hashes of any type parameters.
+	#[inline(always)]
+	fn schema_hash() -> u64;
+	
+	/// The version of the schema of this type, for use as a root.
+	/// When a root type changes, its successor should have a greater schema version, so that pools with the old root can be migrated to it by `CtoPool::open_with_migrations()`.
+	#[inline(always)]
+	fn schema_version() -> u64
+	{
+		0
+	}
 }
 
 impl<'a, Value: CtoSafe> CtoSafe for &'a mut Value
@@ -18,6 +45,31 @@ impl<'a, Value: CtoSafe> CtoSafe for &'a mut Value
 		let x = &mut **self;
 		x.cto_pool_opened(cto_pool_arc)
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let x = &mut **self;
+		x.cto_pool_relocate(cto_pool_relocation)
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		(**self).cto_pool_traverse(cto_pool_traversal)
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		Value::schema_hash()
+	}
+	
+	#[inline(always)]
+	fn schema_version() -> u64
+	{
+		Value::schema_version()
+	}
 }
 
 impl CtoSafe for u8
@@ -26,6 +78,22 @@ impl CtoSafe for u8
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("u8")
+	}
 }
 
 impl CtoSafe for i8
@@ -34,6 +102,22 @@ impl CtoSafe for i8
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("i8")
+	}
 }
 
 impl CtoSafe for u16
@@ -42,6 +126,22 @@ impl CtoSafe for u16
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("u16")
+	}
 }
 
 impl CtoSafe for i16
@@ -50,6 +150,22 @@ impl CtoSafe for i16
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("i16")
+	}
 }
 
 impl CtoSafe for u32
@@ -58,6 +174,22 @@ impl CtoSafe for u32
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("u32")
+	}
 }
 
 impl CtoSafe for i32
@@ -66,6 +198,22 @@ impl CtoSafe for i32
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("i32")
+	}
 }
 
 impl CtoSafe for u64
@@ -74,6 +222,22 @@ impl CtoSafe for u64
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("u64")
+	}
 }
 
 impl CtoSafe for i64
@@ -82,6 +246,22 @@ impl CtoSafe for i64
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("i64")
+	}
 }
 
 impl CtoSafe for usize
@@ -90,6 +270,22 @@ impl CtoSafe for usize
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("usize")
+	}
 }
 
 impl CtoSafe for isize
@@ -98,6 +294,22 @@ impl CtoSafe for isize
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("isize")
+	}
 }
 
 impl CtoSafe for f32
@@ -106,6 +318,22 @@ impl CtoSafe for f32
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("f32")
+	}
 }
 
 impl CtoSafe for f64
@@ -114,6 +342,22 @@ impl CtoSafe for f64
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("f64")
+	}
 }
 
 impl CtoSafe for bool
@@ -122,6 +366,22 @@ impl CtoSafe for bool
 	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("bool")
+	}
 }
 
 impl<Value: CtoSafe> CtoSafe for Option<Value>
@@ -134,4 +394,28 @@ impl<Value: CtoSafe> CtoSafe for Option<Value>
 			value.cto_pool_opened(cto_pool_arc)
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		if let Some(ref mut value) = *self
+		{
+			value.cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		if let Some(ref value) = *self
+		{
+			value.cto_pool_traverse(cto_pool_traversal)
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("Option"), &[Value::schema_hash()])
+	}
 }
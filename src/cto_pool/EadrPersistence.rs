@@ -0,0 +1,24 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Persists on platforms with eADR (extended Asynchronous DRAM Refresh), where CPU caches are part of the persistence domain and are written back on power failure.
+/// Cache lines do not need to be written back, but stores must still be ordered with a fence.
+///
+/// Only correct for DAX devices and files mapped with `MAP_SYNC`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct EadrPersistence;
+
+impl Persistence for EadrPersistence
+{
+	#[inline(always)]
+	fn flush_memory(_address: *mut c_void, _length: usize)
+	{
+	}
+	
+	#[inline(always)]
+	fn drain_memory()
+	{
+		persistent_fence()
+	}
+}
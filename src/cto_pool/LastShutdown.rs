@@ -0,0 +1,29 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// How a CTO pool was last shut down; found when it is opened.
+///
+/// Recovery code can use this to decide whether an expensive consistency check is needed.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum LastShutdown
+{
+	/// The pool was closed cleanly, or was newly created.
+	Clean,
+	
+	/// The pool was not closed cleanly, eg because the process crashed; any operation in progress may be incomplete.
+	Dirty,
+	
+	/// The pool was not closed cleanly and the NVDIMM unsafe shutdown count has changed since it was opened, ie an NVDIMM lost power without writing back its buffers; data may have been lost.
+	UnsafeShutdown,
+}
+
+impl LastShutdown
+{
+	/// Should the pool be checked for consistency before use?
+	#[inline(always)]
+	pub fn requires_consistency_check(self) -> bool
+	{
+		self != LastShutdown::Clean
+	}
+}
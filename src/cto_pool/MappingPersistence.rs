@@ -0,0 +1,47 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// How writes to a pool's mapping are made persistent; chosen when the pool is mapped, as it depends on how the mapping was made.
+/// Flushing CPU caches only makes writes persistent if the memory was mapped with `MAP_SYNC` (which DAX devices and files on a DAX file system support); otherwise dirty pages have to be `msync`'d.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub(crate) enum MappingPersistence
+{
+	/// Use `BestAvailablePersistence`.
+	WriteBack,
+	
+	/// Use `MsyncPersistence`.
+	Msync,
+}
+
+impl MappingPersistence
+{
+	/// The persistence suitable for `mapped_memory`.
+	#[inline(always)]
+	pub(crate) fn of(mapped_memory: &MappedMemory) -> Self
+	{
+		use self::MappingPersistence::*;
+		
+		if mapped_memory.was_mapped_with_linux_sync_flag()
+		{
+			WriteBack
+		}
+		else
+		{
+			Msync
+		}
+	}
+	
+	/// Persists `length` bytes at `address`.
+	#[inline(always)]
+	pub(crate) fn persist_memory(self, address: usize, length: usize)
+	{
+		use self::MappingPersistence::*;
+		
+		match self
+		{
+			WriteBack => BestAvailablePersistence::persist_memory(address as *mut c_void, length),
+			Msync => MsyncPersistence::persist_memory(address as *mut c_void, length),
+		}
+	}
+}
@@ -0,0 +1,60 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Persists using `msync(MS_SYNC)`.
+/// The only correct choice for plain files that are not on a DAX file system or were not mapped with `MAP_SYNC`, as flushing CPU caches does not write dirty pages back to storage.
+///
+/// `msync` is synchronous, so there is nothing to drain.
+///
+/// A failed `msync` does not panic, but is counted (see `failures()`); a CTO pool open at the time is then not recorded as shut down cleanly, so it reports a dirty shutdown when next opened.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct MsyncPersistence;
+
+impl MsyncPersistence
+{
+	/// How many times `msync` has failed in this process.
+	#[inline(always)]
+	pub fn failures() -> usize
+	{
+		Self::failures_counter().load(SeqCst)
+	}
+	
+	#[inline(always)]
+	fn failures_counter() -> &'static AtomicUsize
+	{
+		static Failures: AtomicUsize = AtomicUsize::new(0);
+		&Failures
+	}
+}
+
+impl Persistence for MsyncPersistence
+{
+	#[inline(always)]
+	fn flush_memory(address: *mut c_void, length: usize)
+	{
+		if length == 0
+		{
+			return
+		}
+		
+		// msync requires a page aligned address.
+		let address = address as usize;
+		let page_address = address & !(memory_map_page_size() - 1);
+		let page_length = length + (address - page_address);
+		
+		let result = unsafe { msync(page_address as *mut c_void, page_length, MS_SYNC) };
+		if result != 0
+		{
+			Self::failures_counter().fetch_add(1, SeqCst);
+			return
+		}
+		
+		#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))] record_sync(address, length);
+	}
+	
+	#[inline(always)]
+	fn drain_memory()
+	{
+	}
+}
@@ -2,4 +2,815 @@
 // Copyright © 2017 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
 
 
-type PMEMctopool = ();
+/// Stored in Volatile Memory.
+/// An open CTO pool: a memory mapping starting with a `CtoPoolHeader` followed by a heap.
+/// Created by `CtoPoolPathExt` and destroyed by `PMEMctopoolExt::close()`; always handled as a `*mut PMEMctopool`.
+/// When the pool is grown, the memory it has grown by is mapped contiguously after the end of the last mapping, and is kept in `extensions`.
+/// `named_roots_opened` holds the indices, in the root directory, of the named roots that have had `cto_pool_opened()` called since the pool was opened; its lock also serializes changes to the root directory.
+/// `msync_failures_when_opened` is `MsyncPersistence::failures()` when the pool was opened; if it has changed when the pool is closed, a write may not have reached storage, so a clean shutdown is not recorded.
+#[derive(Debug)]
+pub struct PMEMctopool
+{
+	heap: Heap,
+	mapped_memory: MappedMemory,
+	extensions: Mutex<Vec<MappedMemory>>,
+	last_shutdown: LastShutdown,
+	read_only: bool,
+	named_roots_opened: Mutex<HashSet<usize>>,
+	msync_failures_when_opened: usize,
+}
+
+impl PMEMctopool
+{
+	/// Formats freshly mapped memory as an empty pool.
+	#[inline(always)]
+	fn create(mapped_memory: MappedMemory, layout_name: &CStr, unsafe_shutdown_count: Option<u64>) -> Result<*mut Self, PmdkError>
+	{
+		Self::check_length(&mapped_memory)?;
+		
+		let persistence = MappingPersistence::of(&mapped_memory);
+		
+		let heap_inclusive_start_address =
+		{
+			let header = CtoPoolHeader::from_mapped_memory_mut(&mapped_memory);
+			header.initialize(&mapped_memory, layout_name, unsafe_shutdown_count, persistence);
+			header.heap_inclusive_start_address()
+		};
+		
+		let heap = Heap::create(heap_inclusive_start_address, Self::exclusive_end_address(&mapped_memory), persistence)?;
+		
+		Ok(Self::new(heap, mapped_memory, LastShutdown::Clean, false))
+	}
+	
+	/// Opens memory mapped at any address; if it is not the address the pool was last mapped at, `relocation()` must then be used before pointers in the pool are used.
+	/// `unsafe_shutdown_count` is the current NVDIMM unsafe shutdown count, if known.
+	/// If growing the pool was interrupted after its file (or a part) was extended, the growth is completed.
+	/// If replacing the root (eg when migrating it) was interrupted, the replacement is completed.
+	#[inline(always)]
+	fn open(mapped_memory: MappedMemory, layout_name: &CStr, unsafe_shutdown_count: Option<u64>) -> Result<*mut Self, PmdkError>
+	{
+		let persistence = MappingPersistence::of(&mapped_memory);
+		
+		let (heap_inclusive_start_address, heap_exclusive_end_address) =
+		{
+			let header = Self::validated_header(&mapped_memory, layout_name)?;
+			(header.heap_inclusive_start_address(), header.heap_exclusive_end_address(&mapped_memory)?)
+		};
+		
+		let heap = Heap::open(heap_inclusive_start_address, heap_exclusive_end_address, persistence)?;
+		
+		let exclusive_end_address = Self::exclusive_end_address(&mapped_memory);
+		if heap_exclusive_end_address < exclusive_end_address
+		{
+			heap.grow(exclusive_end_address, || CtoPoolHeader::from_mapped_memory_mut(&mapped_memory).record_size(mapped_memory.length(), persistence));
+		}
+		
+		CtoPoolHeader::from_mapped_memory_mut(&mapped_memory).complete_pending_root(persistence);
+		
+		let last_shutdown = CtoPoolHeader::from_mapped_memory_mut(&mapped_memory).record_open(unsafe_shutdown_count, persistence);
+		
+		Ok(Self::new(heap, mapped_memory, last_shutdown, false))
+	}
+	
+	/// Opens memory mapped read-only (`PROT_READ`) at the address the pool was last mapped at; nothing is written to the pool.
+	/// Fails if replacing the root or relocating the pool was interrupted, as completing either requires writing to the pool.
+	#[inline(always)]
+	fn open_read_only(mapped_memory: MappedMemory, layout_name: &CStr, unsafe_shutdown_count: Option<u64>) -> Result<*mut Self, PmdkError>
+	{
+		let (heap_inclusive_start_address, heap_exclusive_end_address, last_shutdown) =
+		{
+			let header = Self::validated_header(&mapped_memory, layout_name)?;
+			debug_assert_eq!(header.mapped_base_address(), mapped_memory.as_ptr() as usize, "a read-only pool can not be relocated");
+			
+			// An interrupted replacement of the root, or relocation of the pool, can only be completed by writing to the pool.
+			if header.has_pending_root() || header.relocating_to_address().is_some()
+			{
+				return Err(PmdkError::ReadOnly)
+			}
+			
+			(header.heap_inclusive_start_address(), header.heap_exclusive_end_address(&mapped_memory)?, header.last_shutdown(unsafe_shutdown_count))
+		};
+		
+		let heap = Heap::open_read_only(heap_inclusive_start_address, heap_exclusive_end_address, MappingPersistence::of(&mapped_memory))?;
+		
+		Ok(Self::new(heap, mapped_memory, last_shutdown, true))
+	}
+	
+	/// Does this memory contain a pool (of any layout or version), even one that is corrupt?
+	#[inline(always)]
+	fn contains_a_pool(mapped_memory: &MappedMemory) -> bool
+	{
+		Self::check_length(mapped_memory).is_ok() && CtoPoolHeader::from_mapped_memory(mapped_memory).has_magic()
+	}
+	
+	/// Checks a pool without modifying it; `mapped_memory` need not be mapped at the address the pool was created at.
+	/// A header that fails validation is an error; a heap that can not be walked is inconsistent.
+	/// Memory after the end of the heap, left by growing the pool being interrupted, is not checked.
+	#[inline(always)]
+	fn is_consistent(mapped_memory: &MappedMemory, layout_name: &CStr) -> Result<bool, PmdkError>
+	{
+		let header = Self::validated_header(mapped_memory, layout_name)?;
+		
+		Ok(Heap::is_consistent(header.heap_inclusive_start_address(), header.heap_exclusive_end_address(mapped_memory)?))
+	}
+	
+	/// Is this memory (eg a probe of a pool's file) the same pool as this one?
+	#[inline(always)]
+	fn is_same_pool(&self, mapped_memory: &MappedMemory) -> bool
+	{
+		Self::contains_a_pool(mapped_memory) && CtoPoolHeader::from_mapped_memory(mapped_memory).uuid() == self.header().uuid()
+	}
+	
+	/// Memory mapping alignment for pools that are not on DAX devices.
+	#[inline(always)]
+	fn alignment() -> usize
+	{
+		memory_map_page_size()
+	}
+	
+	#[inline(always)]
+	fn new(heap: Heap, mapped_memory: MappedMemory, last_shutdown: LastShutdown, read_only: bool) -> *mut Self
+	{
+		let inclusive_start_address = mapped_memory.as_ptr() as usize;
+		let exclusive_end_address = Self::exclusive_end_address(&mapped_memory);
+		
+		let pool_pointer = Box::into_raw(Box::new
+		(
+			Self
+			{
+				heap,
+				mapped_memory,
+				extensions: Mutex::new(Vec::new()),
+				last_shutdown,
+				read_only,
+				named_roots_opened: Mutex::new(HashSet::new()),
+				msync_failures_when_opened: MsyncPersistence::failures(),
+			}
+		));
+		
+		CtoPoolRegistry::register_pool(pool_pointer, inclusive_start_address, exclusive_end_address);
+		
+		pool_pointer
+	}
+	
+	#[inline(always)]
+	fn validated_header<'mapped_memory>(mapped_memory: &'mapped_memory MappedMemory, layout_name: &CStr) -> Result<&'mapped_memory CtoPoolHeader, PmdkError>
+	{
+		Self::check_length(mapped_memory)?;
+		
+		let header = CtoPoolHeader::from_mapped_memory(mapped_memory);
+		header.validate(layout_name)?;
+		Ok(header)
+	}
+	
+	#[inline(always)]
+	fn check_length(mapped_memory: &MappedMemory) -> Result<(), CtoPoolHeaderValidationError>
+	{
+		let length = mapped_memory.length();
+		if length < size_of::<CtoPoolHeader>()
+		{
+			Err(CtoPoolHeaderValidationError::Truncated(length))
+		}
+		else
+		{
+			Ok(())
+		}
+	}
+	
+	#[inline(always)]
+	fn exclusive_end_address(mapped_memory: &MappedMemory) -> usize
+	{
+		mapped_memory.as_ptr() as usize + mapped_memory.length()
+	}
+	
+	/// `None` if the pool is mapped at the address it was last mapped at, and relocating it was not interrupted.
+	/// If relocating it was interrupted, the pool is mapped at one of the two addresses involved (see `CtoPoolPathExt::open_cto_pool()`), and pointers relative to the other are relocated.
+	/// Returns an error if the old and new mappings overlap.
+	#[inline(always)]
+	fn relocation(&self) -> Result<Option<CtoPoolRelocation>, PmdkError>
+	{
+		let header = self.header();
+		let mapped_base_address = header.mapped_base_address();
+		let new_inclusive_start_address = self.mapped_memory.as_ptr() as usize;
+		
+		let old_inclusive_start_address = match header.relocating_to_address()
+		{
+			None => if mapped_base_address == new_inclusive_start_address
+			{
+				return Ok(None)
+			}
+			else
+			{
+				mapped_base_address
+			},
+			
+			Some(relocating_to_address) => if mapped_base_address == new_inclusive_start_address
+			{
+				relocating_to_address
+			}
+			else
+			{
+				debug_assert_eq!(relocating_to_address, new_inclusive_start_address, "an interrupted relocation can only be completed at one of the addresses involved");
+				mapped_base_address
+			},
+		};
+		
+		CtoPoolRelocation::new(old_inclusive_start_address, new_inclusive_start_address, self.mapped_memory.length(), self.persistence()).map(Some)
+	}
+	
+	/// The addresses a pool was last mapped at and, if relocating it was interrupted, was being relocated to; validates the header first.
+	#[inline(always)]
+	fn mapped_base_addresses(mapped_memory: &MappedMemory, layout_name: &CStr) -> Result<(usize, Option<usize>), PmdkError>
+	{
+		Self::validated_header(mapped_memory, layout_name).map(|header| (header.mapped_base_address(), header.relocating_to_address()))
+	}
+	
+	/// Records, before anything is relocated, where pointers are being relocated from and to, so that the relocation can be completed if interrupted.
+	#[inline(always)]
+	fn record_relocating(&self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let header = CtoPoolHeader::from_mapped_memory_mut(&self.mapped_memory);
+		header.record_relocating(cto_pool_relocation.old_inclusive_start_address(), self.mapped_memory.as_ptr() as usize, self.persistence())
+	}
+	
+	#[inline(always)]
+	fn rebase(&self)
+	{
+		let header = CtoPoolHeader::from_mapped_memory_mut(&self.mapped_memory);
+		header.rebase(self.mapped_memory.as_ptr() as usize, self.persistence())
+	}
+	
+	/// Checks that the root, if there is one, is of type `T`; returns the fingerprint of the type it is if it is not.
+	#[inline(always)]
+	fn check_root_type<T: CtoSafe>(&self) -> Result<(), TypeFingerprint>
+	{
+		if self.header().get_root().is_null()
+		{
+			return Ok(())
+		}
+		
+		let expected = TypeFingerprint::of::<T>();
+		let found = self.header().root_type_fingerprint();
+		
+		if found == expected
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(found)
+		}
+	}
+	
+	/// Must be called before the root is set.
+	#[inline(always)]
+	fn record_root_type_fingerprint(&self, root_type_fingerprint: TypeFingerprint)
+	{
+		let header = CtoPoolHeader::from_mapped_memory_mut(&self.mapped_memory);
+		header.record_root_type_fingerprint(root_type_fingerprint, self.persistence())
+	}
+	
+	/// If the pool has been mapped at a different address to the one it was last mapped at, relocates the root with `relocate_root`, then the root directory and the named roots in it, and then records the new address.
+	/// All the roots are relocated together, so anything reachable from more than one of them is relocated once.
+	/// The relocation is recorded before it starts, and pointers that have already been relocated are left alone, so if it is interrupted it is completed when the pool is next opened.
+	/// If any named root is not registered in `named_roots` (or is registered as a different type), nothing is relocated and the names of such named roots are returned.
+	/// If the old and new mappings overlap, nothing is relocated and an error is returned.
+	#[inline(always)]
+	fn relocate_if_moved(&self, relocate_root: fn(*mut c_void, &CtoPoolRelocation), named_roots: &CtoPoolNamedRoots) -> Result<Result<(), Vec<String>>, PmdkError>
+	{
+		if let Some(cto_pool_relocation) = self.relocation()?
+		{
+			let root_directory = cto_pool_relocation.relocated(self.header().get_root_directory());
+			let named_root_relocators = if root_directory.is_null()
+			{
+				Vec::new()
+			}
+			else
+			{
+				match named_roots.relocators(unsafe { & * root_directory })
+				{
+					Err(names) => return Ok(Err(names)),
+					Ok(named_root_relocators) => named_root_relocators,
+				}
+			};
+			
+			self.record_relocating(&cto_pool_relocation);
+			
+			let root = cto_pool_relocation.relocated(self.header().get_root());
+			if root.is_not_null()
+			{
+				relocate_root(root, &cto_pool_relocation);
+				self.header().set_root(root, self.persistence());
+			}
+			
+			self.relocate_root_directory(&cto_pool_relocation);
+			for (index, relocate_named_root) in named_root_relocators
+			{
+				relocate_named_root(unsafe { & * root_directory }.entry(index).root(), &cto_pool_relocation)
+			}
+			
+			self.rebase();
+		}
+		
+		Ok(Ok(()))
+	}
+	
+	/// Relocates a (relocated) root of type `T` and everything reachable from it, unless it has already been relocated.
+	#[inline(always)]
+	fn relocate_root<T: CtoSafe>(root: *mut c_void, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let root = root as *mut T;
+		if cto_pool_relocation.is_first_visit(root)
+		{
+			unsafe { &mut * root }.cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	#[inline(always)]
+	fn root_type_fingerprint(&self) -> TypeFingerprint
+	{
+		self.header().root_type_fingerprint()
+	}
+	
+	/// The schema version of the root, if there is one and it is older than that of `T`, ie if it needs migrating to `T`.
+	#[inline(always)]
+	fn root_schema_version_if_older<T: CtoSafe>(&self) -> Option<u64>
+	{
+		if self.header().get_root().is_null()
+		{
+			return None
+		}
+		
+		let root_type_fingerprint = self.root_type_fingerprint();
+		if root_type_fingerprint.schema_version >= T::schema_version()
+		{
+			None
+		}
+		else
+		{
+			Some(root_type_fingerprint.schema_version)
+		}
+	}
+	
+	/// Replaces the root with `new_root`, of a type with the fingerprint `new_root_type_fingerprint`.
+	/// The replacement is crash-safe; if it is interrupted, it is completed when the pool is next opened.
+	#[inline(always)]
+	fn replace_root(&self, new_root: *mut c_void, new_root_type_fingerprint: TypeFingerprint)
+	{
+		let header = CtoPoolHeader::from_mapped_memory_mut(&self.mapped_memory);
+		header.record_pending_root(new_root, new_root_type_fingerprint, self.persistence());
+		header.complete_pending_root(self.persistence())
+	}
+	
+	/// Relocates the pointers to the root directory and to the named roots in it, but not the named roots themselves.
+	#[inline(always)]
+	fn relocate_root_directory(&self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let root_directory = self.header().relocate_root_directory(cto_pool_relocation);
+		if root_directory.is_not_null()
+		{
+			for entry in unsafe { & * root_directory }.entries().iter()
+			{
+				entry.relocate_root_pointer(cto_pool_relocation)
+			}
+		}
+	}
+	
+	/// Starts a traversal of the pool, having checked the pointers to the root directory and the named roots in it (but not what the named roots point to, as their types are not known), and to a root that was to replace the current root when interrupted.
+	#[inline(always)]
+	fn traversal(&self) -> Result<CtoPoolTraversal, PmdkError>
+	{
+		let inclusive_start_address = self.mapped_memory.as_ptr() as usize;
+		let exclusive_end_address = inclusive_start_address + Self::length_including_extensions(&self.mapped_memory, &self.extensions.lock());
+		let cto_pool_traversal = CtoPoolTraversal::new(inclusive_start_address, exclusive_end_address, self.heap.allocations()?);
+		
+		let _named_roots_opened = self.named_roots_opened.lock();
+		
+		let root_directory = self.header().get_root_directory();
+		if cto_pool_traversal.is_valid_pointer(root_directory)
+		{
+			for entry in unsafe { & * root_directory }.entries().iter().filter(|entry| entry.is_in_use())
+			{
+				let root = entry.root() as *const u8;
+				if cto_pool_traversal.is_valid_pointer(root)
+				{
+					cto_pool_traversal.found_named_root(String::from_utf8_lossy(entry.name()).into_owned(), root as usize);
+				}
+			}
+		}
+		
+		cto_pool_traversal.is_valid_pointer(self.header().get_pending_root() as *const u8);
+		
+		Ok(cto_pool_traversal)
+	}
+	
+	/// Frees everything that `cto_pool_traversal`, having walked the whole pool, did not reach; returns what was freed.
+	/// Nothing is freed if the traversal found the pool to be inconsistent, did not walk every named root or found a block allocator with chains that can not be walked, as then something still in use might not have been reached.
+	#[inline(always)]
+	fn collect_leaks(&self, cto_pool_traversal: &CtoPoolTraversal) -> Result<CtoPoolLeaks, PmdkError>
+	{
+		let number_of_findings = cto_pool_traversal.findings().len();
+		if number_of_findings != 0
+		{
+			return Err(PmdkError::CanNotCollectLeaks(format!("checking the pool found '{}' problems", number_of_findings)))
+		}
+		
+		let cto_pool_leaks = cto_pool_traversal.leaks();
+		if !cto_pool_leaks.untraversed_named_roots.is_empty()
+		{
+			return Err(PmdkError::CanNotCollectLeaks(format!("the named roots '{:?}' were not traversed", cto_pool_leaks.untraversed_named_roots)))
+		}
+		
+		if !cto_pool_leaks.unwalked_block_allocators.is_empty()
+		{
+			return Err(PmdkError::CanNotCollectLeaks(format!("the block allocators at '{:?}' have handed out chains that can not be walked", cto_pool_leaks.unwalked_block_allocators)))
+		}
+		
+		// Blocks first, as their block allocator might be in a leaked allocation.
+		cto_pool_traversal.recycle_leaked_blocks(&cto_pool_leaks);
+		for &(allocation_address, _size) in cto_pool_leaks.allocations.iter()
+		{
+			self.heap.free(allocation_address as *mut u8)
+		}
+		
+		Ok(cto_pool_leaks)
+	}
+	
+	/// Checks the pool without knowing the type of its root; as `traversal()`, but the root is also checked to be a valid pointer, although what it points to is not walked.
+	#[inline(always)]
+	fn check_untyped(&self) -> Result<Vec<CtoPoolCheckFinding>, PmdkError>
+	{
+		let cto_pool_traversal = self.traversal()?;
+		cto_pool_traversal.is_valid_pointer(self.header().get_root() as *const u8);
+		Ok(cto_pool_traversal.findings())
+	}
+	
+	/// What is recorded about the pool in its header, and how its memory is used.
+	#[inline(always)]
+	fn information(&self) -> CtoPoolInformation
+	{
+		let header = self.header();
+		
+		CtoPoolInformation
+		{
+			format_version: header.format_version(),
+			uuid: header.uuid(),
+			layout_name: String::from_utf8_lossy(header.layout_name()).into_owned(),
+			creation_size: header.creation_size(),
+			size: header.size(),
+			mapped_base_address: header.mapped_base_address(),
+			root_address: header.get_root() as usize,
+			root_type_fingerprint: header.root_type_fingerprint(),
+			named_roots: self.named_roots(),
+			last_shutdown: self.last_shutdown,
+			statistics: self.statistics(),
+		}
+	}
+	
+	/// The raw bytes of the header and the chunks of the heap.
+	#[inline(always)]
+	fn dump(&self) -> Result<CtoPoolDump, PmdkError>
+	{
+		let chunks = self.heap.chunks()?;
+		let mapped_base_address = self.mapped_memory.as_ptr() as usize;
+		let header = unsafe { from_raw_parts(mapped_base_address as *const u8, size_of::<CtoPoolHeader>()) }.to_vec();
+		
+		Ok(CtoPoolDump { mapped_base_address, header, chunks })
+	}
+	
+	/// Finds the named root `name`, which must be of type `T`; returns null if there is no such named root.
+	#[inline(always)]
+	fn named_root<T: CtoSafe>(&self, name: &str, cto_pool_arc: &CtoPoolArc) -> Result<*mut T, RootDirectoryError>
+	{
+		let name = Self::named_root_name(name)?;
+		
+		let mut named_roots_opened = self.named_roots_opened.lock();
+		
+		let root_directory = self.header().get_root_directory();
+		if root_directory.is_null()
+		{
+			return Ok(null_mut())
+		}
+		let root_directory = unsafe { &mut * root_directory };
+		
+		match root_directory.find(name)
+		{
+			None => Ok(null_mut()),
+			Some(index) => self.open_named_root(root_directory, index, &mut named_roots_opened, cto_pool_arc),
+		}
+	}
+	
+	/// As `named_root()`, but only through shared references, as for a pool opened read-only; `cto_pool_opened()` is not called on the named root.
+	#[inline(always)]
+	fn named_root_without_opening<T: CtoSafe>(&self, name: &str) -> Result<*const T, RootDirectoryError>
+	{
+		let name = Self::named_root_name(name)?;
+		
+		let root_directory = self.header().get_root_directory();
+		if root_directory.is_null()
+		{
+			return Ok(null())
+		}
+		let root_directory = unsafe { & * root_directory };
+		
+		match root_directory.find(name)
+		{
+			None => Ok(null()),
+			Some(index) =>
+			{
+				let entry = root_directory.entry(index);
+				entry.check_type::<T>()?;
+				Ok(entry.root() as *const T)
+			}
+		}
+	}
+	
+	/// Inserts `root_value` as the named root `name`.
+	/// A crash after the named root is allocated but before it is recorded in the root directory leaks it.
+	#[inline(always)]
+	fn insert_named_root<T: CtoSafe>(&self, name: &str, root_value: T) -> Result<(), RootDirectoryError>
+	{
+		if self.read_only
+		{
+			return Err(PmdkError::ReadOnly.into())
+		}
+		
+		let name = Self::named_root_name(name)?;
+		
+		let mut named_roots_opened = self.named_roots_opened.lock();
+		
+		let root_directory = self.root_directory_allocating_if_missing()?;
+		
+		if root_directory.find(name).is_some()
+		{
+			return Err(RootDirectoryError::AlreadyExists(String::from_utf8_lossy(name).into_owned()))
+		}
+		
+		let index = match root_directory.find_unused()
+		{
+			None => return Err(RootDirectoryError::TooManyNamedRoots),
+			Some(index) => index,
+		};
+		
+		let root = self.heap.allocate(align_of::<T>(), size_of::<T>())? as *mut T;
+		unsafe { write(root, root_value) };
+		self.persist_memory(root as usize, size_of::<T>());
+		
+		root_directory.entry_mut(index).initialize(name, TypeFingerprint::of::<T>(), root as *mut c_void, self.persistence());
+		named_roots_opened.insert(index);
+		
+		Ok(())
+	}
+	
+	/// Removes, drops and frees the named root `name`, which must be of type `T`; returns `false` if there is no such named root.
+	/// A crash after the named root is removed from the root directory but before it is freed leaks it.
+	#[inline(always)]
+	fn remove_named_root<T: CtoSafe>(&self, name: &str, cto_pool_arc: &CtoPoolArc) -> Result<bool, RootDirectoryError>
+	{
+		if self.read_only
+		{
+			return Err(PmdkError::ReadOnly.into())
+		}
+		
+		let name = Self::named_root_name(name)?;
+		
+		let mut named_roots_opened = self.named_roots_opened.lock();
+		
+		let root_directory = self.header().get_root_directory();
+		if root_directory.is_null()
+		{
+			return Ok(false)
+		}
+		let root_directory = unsafe { &mut * root_directory };
+		
+		let index = match root_directory.find(name)
+		{
+			None => return Ok(false),
+			Some(index) => index,
+		};
+		
+		// Calls `cto_pool_opened()` on the named root, if necessary, so that it can be dropped.
+		let root = self.open_named_root::<T>(root_directory, index, &mut named_roots_opened, cto_pool_arc)?;
+		
+		root_directory.entry(index).clear(self.persistence());
+		named_roots_opened.remove(&index);
+		
+		unsafe { drop_in_place(root) };
+		self.heap.free(root as *mut u8);
+		
+		Ok(true)
+	}
+	
+	/// The names and type fingerprints of all named roots.
+	#[inline(always)]
+	fn named_roots(&self) -> Vec<(String, TypeFingerprint)>
+	{
+		let _named_roots_opened = self.named_roots_opened.lock();
+		
+		let root_directory = self.header().get_root_directory();
+		if root_directory.is_null()
+		{
+			return Vec::new()
+		}
+		
+		unsafe { & * root_directory }.entries().iter().filter(|entry| entry.is_in_use()).map(|entry| (String::from_utf8_lossy(entry.name()).into_owned(), entry.type_fingerprint())).collect()
+	}
+	
+	/// The first time a named root is used after the pool is opened, `cto_pool_opened()` is called on it (but not for a read-only pool).
+	/// Named roots are relocated, if necessary, when the pool is opened (see `relocate_if_moved()`).
+	#[inline(always)]
+	fn open_named_root<T: CtoSafe>(&self, root_directory: &mut RootDirectory, index: usize, named_roots_opened: &mut HashSet<usize>, cto_pool_arc: &CtoPoolArc) -> Result<*mut T, RootDirectoryError>
+	{
+		let entry = root_directory.entry_mut(index);
+		entry.check_type::<T>()?;
+		
+		let root = entry.root() as *mut T;
+		
+		if !self.read_only && named_roots_opened.insert(index)
+		{
+			unsafe { &mut * root }.cto_pool_opened(cto_pool_arc);
+		}
+		
+		Ok(root)
+	}
+	
+	#[inline(always)]
+	fn root_directory_allocating_if_missing(&self) -> Result<&mut RootDirectory, PmdkError>
+	{
+		let header = self.header();
+		
+		let mut root_directory = header.get_root_directory();
+		if root_directory.is_null()
+		{
+			root_directory = self.heap.allocate(align_of::<RootDirectory>(), size_of::<RootDirectory>())? as *mut RootDirectory;
+			RootDirectory::initialize(root_directory, self.persistence());
+			header.set_root_directory(root_directory, self.persistence());
+		}
+		
+		Ok(unsafe { &mut * root_directory })
+	}
+	
+	#[inline(always)]
+	fn named_root_name(name: &str) -> Result<&[u8], RootDirectoryError>
+	{
+		let bytes = name.as_bytes();
+		if bytes.is_empty() || bytes.len() > MaximumNamedRootNameLength || bytes.contains(&0)
+		{
+			Err(RootDirectoryError::InvalidName(name.to_owned()))
+		}
+		else
+		{
+			Ok(bytes)
+		}
+	}
+	
+	/// Grows the pool.
+	///
+	/// `map_extension` is given the current length of the pool's mapping, and must make memory available immediately after its end, eg by extending the pool's file and memory mapping the extension.
+	/// Only one thread can grow the pool at a time.
+	#[inline(always)]
+	fn grow<MapExtension: FnOnce(usize) -> Result<MappedMemory, PmdkError>>(&self, map_extension: MapExtension) -> Result<(), PmdkError>
+	{
+		let mut extensions = self.extensions.lock();
+		
+		let length = Self::length_including_extensions(&self.mapped_memory, &extensions);
+		let extension = map_extension(length)?;
+		if self.persistence() == MappingPersistence::WriteBack && MappingPersistence::of(&extension) != MappingPersistence::WriteBack
+		{
+			return Err(PmdkError::CanNotGrow("the pool was memory mapped with MAP_SYNC but the memory it would grow by was not".to_owned()))
+		}
+		debug_assert_eq!(extension.as_ptr() as usize, self.mapped_memory.as_ptr() as usize + length, "extension is not contiguous with the end of the pool");
+		
+		let length = length + extension.length();
+		let exclusive_end_address = Self::exclusive_end_address(&extension);
+		extensions.push(extension);
+		
+		CtoPoolRegistry::resize_pool(self.mapped_memory.as_ptr() as usize, exclusive_end_address);
+		
+		self.heap.grow(exclusive_end_address, || CtoPoolHeader::from_mapped_memory_mut(&self.mapped_memory).record_size(length, self.persistence()));
+		Ok(())
+	}
+	
+	/// Memory maps `part` immediately after the first `length` bytes of the pool's mapping; fails if that address range is occupied.
+	#[inline(always)]
+	fn memory_map_extension(&self, length: usize, part: ContiguousPart, alignment: usize) -> Result<MappedMemory, PmdkError>
+	{
+		let address = (self.mapped_memory.as_ptr() as usize + length) as *mut u8;
+		
+		match memory_map_contiguously(&[part], false, MemoryMapAddress::Mandatory { unaligned_address: address }, alignment)
+		{
+			Ok(extension) => Ok(extension),
+			
+			Err(CouldNotMemoryMapError::AddressOccupied(_, _)) => Err(PmdkError::CanNotGrow(format!("the address '{:?}' after the end of the pool is occupied", address))),
+			
+			Err(error) => Err(error.into()),
+		}
+	}
+	
+	/// Checks that a pool set's parts, as they would be memory mapped now, are the same length as the pool's mapping.
+	#[inline(always)]
+	fn check_pool_set_is_unchanged(contiguous_parts: &[ContiguousPart], length: usize) -> Result<(), PmdkError>
+	{
+		let pool_set_length = contiguous_parts.iter().fold(0, |pool_set_length, part| pool_set_length + part.length);
+		if pool_set_length == length
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(PmdkError::CanNotGrow(format!("the pool set's parts are now '{}' bytes long but the pool is '{}' bytes long", pool_set_length, length)))
+		}
+	}
+	
+	#[inline(always)]
+	fn length_including_extensions(mapped_memory: &MappedMemory, extensions: &[MappedMemory]) -> usize
+	{
+		extensions.iter().fold(mapped_memory.length(), |length, extension| length + extension.length())
+	}
+	
+	/// Ensures every write to the pool has reached persistent storage.
+	#[inline(always)]
+	fn synchronize(&self)
+	{
+		Self::synchronize_mapped_memory(&self.mapped_memory);
+		
+		for extension in self.extensions.lock().iter()
+		{
+			Self::synchronize_mapped_memory(extension)
+		}
+	}
+	
+	/// Writing back CPU caches does not make memory mapped without `MAP_SYNC` (eg a plain file) persistent, so it is `msync`'d instead.
+	#[inline(always)]
+	fn synchronize_mapped_memory(mapped_memory: &MappedMemory)
+	{
+		if mapped_memory.was_mapped_with_linux_sync_flag()
+		{
+			persistent_sync()
+		}
+		else
+		{
+			MsyncPersistence::persist_memory(mapped_memory.as_ptr() as *mut c_void, mapped_memory.length())
+		}
+	}
+	
+	/// Persists everything and then records a clean shutdown, unless `msync` has failed since the pool was opened.
+	#[inline(always)]
+	fn shut_down_cleanly(&self)
+	{
+		self.synchronize();
+		
+		if MsyncPersistence::failures() != self.msync_failures_when_opened
+		{
+			return
+		}
+		
+		CtoPoolHeader::from_mapped_memory_mut(&self.mapped_memory).record_clean_shutdown(self.persistence());
+		self.synchronize()
+	}
+	
+	#[inline(always)]
+	fn last_shutdown(&self) -> LastShutdown
+	{
+		self.last_shutdown
+	}
+	
+	#[inline(always)]
+	fn is_read_only(&self) -> bool
+	{
+		self.read_only
+	}
+	
+	#[inline(always)]
+	fn statistics(&self) -> CtoPoolStatistics
+	{
+		let total_size = Self::length_including_extensions(&self.mapped_memory, &self.extensions.lock());
+		self.heap.statistics(total_size)
+	}
+	
+	#[inline(always)]
+	fn header(&self) -> &CtoPoolHeader
+	{
+		CtoPoolHeader::from_mapped_memory(&self.mapped_memory)
+	}
+	
+	/// How writes to the pool are made persistent.
+	#[inline(always)]
+	fn persistence(&self) -> MappingPersistence
+	{
+		self.heap.persistence()
+	}
+	
+	/// Persists `length` bytes at `address`, which must be in the pool.
+	#[inline(always)]
+	fn persist_memory(&self, address: usize, length: usize)
+	{
+		self.persistence().persist_memory(address, length)
+	}
+	
+	#[inline(always)]
+	fn heap(&self) -> &Heap
+	{
+		&self.heap
+	}
+}
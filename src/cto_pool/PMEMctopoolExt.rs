@@ -5,7 +5,8 @@
 /// A convenient way to use the methods on a cto pool.
 trait PMEMctopoolExt
 {
-	/// Close the CTO pool.
+	/// Close the CTO pool, first ensuring every write to it has reached persistent storage, and then recording a clean shutdown.
+	/// A read-only pool is closed without writing anything.
 	/// self can not be null.
 	#[inline(always)]
 	fn close(self);
@@ -23,19 +24,19 @@ trait PMEMctopoolExt
 	/// * `strdup()`
 	/// * `wcsdup()`
 	/// The persistent object must eventually be free'd with our `free()`.
-	/// self can not be null.
+	/// self can not be null; panics if the pool is read-only.
 	#[inline(always)]
 	fn set_root<T>(self, root: *mut T);
 	
 	/// The size_of::<T> must not be zero.
-	/// If memory can not be allocated, returns a PmdkError with `isENOMEM()` true. Never returns `Ok(null_mut())`.
-	/// self can not be null.
+	/// If memory can not be allocated, returns `PmdkError::OutOfSpace`. Never returns `Ok(null_mut())`.
+	/// If self is null, returns `PmdkError::PoolClosed`; if the pool is read-only, returns `PmdkError::ReadOnly`.
 	#[inline(always)]
 	fn malloc<T>(self) -> Result<*mut T, PmdkError>;
 	
 	/// Aligned allocation.
-	/// If memory can not be allocated, returns a PmdkError with `isENOMEM()` true. Never returns `Ok(null_mut())`.
-	/// self can not be null.
+	/// If memory can not be allocated, returns `PmdkError::OutOfSpace`; if alignment is not a power of two, returns `PmdkError::BadAlignment`. Never returns `Ok(null_mut())`.
+	/// If self is null, returns `PmdkError::PoolClosed`; if the pool is read-only, returns `PmdkError::ReadOnly`.
 	#[inline(always)]
 	fn aligned_alloc(self, alignment: usize, size: usize) -> Result<*mut c_void, PmdkError>;
 	
@@ -46,15 +47,50 @@ trait PMEMctopoolExt
 	
 	/// Pointer must not be null.
 	/// new_size can not be zero.
-	/// If memory can not be allocated, returns a PmdkError with `isENOMEM()` true. Never returns `Ok(null_mut())`.
-	/// self can not be null.
+	/// If memory can not be allocated, returns `PmdkError::OutOfSpace`. Never returns `Ok(null_mut())`.
+	/// If self is null, returns `PmdkError::PoolClosed`; if the pool is read-only, returns `PmdkError::ReadOnly`.
 	#[inline(always)]
 	fn realloc(self, pointer: *mut c_void, new_size: size_t) -> Result<*mut c_void, PmdkError>;
 	
+	/// Resizes an allocation without moving it.
 	/// Pointer must not be null.
-	/// self can not be null.
+	/// new_size can not be zero.
+	/// If the allocation can not be resized in place, returns `PmdkError::OutOfSpace`.
+	/// If self is null, returns `PmdkError::PoolClosed`; if the pool is read-only, returns `PmdkError::ReadOnly`.
+	#[inline(always)]
+	fn realloc_in_place(self, pointer: *mut c_void, new_size: size_t) -> Result<(), PmdkError>;
+	
+	/// Pointer must not be null.
+	/// self can not be null; panics if the pool is read-only.
 	#[inline(always)]
 	fn free<T>(self, pointer: *mut T);
+	
+	/// If the pool has been mapped at a different address to the one it was last mapped at, relocates the root (of type `T`), the named roots (of the types registered in `named_roots`) and everything reachable from them, and then records the new address.
+	/// Must be called before the root pointer or any named root is used.
+	/// Nothing is relocated if a named root is not registered in `named_roots`; the names of such named roots are returned.
+	/// Nothing is relocated, and an error is returned, if the pool is now mapped at an address that overlaps the one it was last mapped at.
+	/// self can not be null.
+	#[inline(always)]
+	fn relocate_if_moved<T: CtoSafe>(self, named_roots: &CtoPoolNamedRoots) -> Result<Result<(), Vec<String>>, PmdkError>;
+	
+	/// How the pool was last shut down before it was opened.
+	/// self can not be null.
+	#[inline(always)]
+	fn last_shutdown(self) -> LastShutdown;
+	
+	/// Was the pool opened read-only?
+	/// self can not be null.
+	#[inline(always)]
+	fn is_read_only(self) -> bool;
+	
+	/// A snapshot of how the pool's memory is used.
+	/// self can not be null.
+	#[inline(always)]
+	fn statistics(self) -> CtoPoolStatistics;
+	
+	#[doc(hidden)]
+	#[inline(always)]
+	fn pool<'pool>(self) -> &'pool PMEMctopool;
 }
 
 impl PMEMctopoolExt for *mut PMEMctopool
@@ -64,7 +100,14 @@ impl PMEMctopoolExt for *mut PMEMctopool
 	{
 		debug_assert!(self.is_not_null(), "self can not be null");
 		
-		unimplemented!()
+		if !self.is_read_only()
+		{
+			self.pool().shut_down_cleanly();
+		}
+		
+		CtoPoolRegistry::unregister_pool(self.pool().mapped_memory.as_ptr() as usize);
+		
+		drop(unsafe { Box::from_raw(self) })
 	}
 	
 	#[inline(always)]
@@ -72,7 +115,7 @@ impl PMEMctopoolExt for *mut PMEMctopool
 	{
 		debug_assert!(self.is_not_null(), "self can not be null");
 		
-		unimplemented!()
+		self.pool().header().get_root() as *mut T
 	}
 	
 	#[inline(always)]
@@ -80,37 +123,47 @@ impl PMEMctopoolExt for *mut PMEMctopool
 	{
 		debug_assert!(self.is_not_null(), "self can not be null");
 		debug_assert!(root.is_not_null(), "root can not be null");
+		assert!(!self.is_read_only(), "can not set the root of a read-only pool");
 		
-		unimplemented!()
+		let pool = self.pool();
+		pool.header().set_root(root as *mut c_void, pool.persistence())
 	}
 	
 	#[inline(always)]
 	fn malloc<T>(self) -> Result<*mut T, PmdkError>
 	{
-		debug_assert!(self.is_not_null(), "self can not be null");
+		if self.is_null()
+		{
+			return Err(PmdkError::PoolClosed)
+		}
+		
+		if self.is_read_only()
+		{
+			return Err(PmdkError::ReadOnly)
+		}
 		
 		let size = size_of::<T>() as size_t;
 		debug_assert!(size != 0, "size_of::<T>() can not be zero");
 		
-		unimplemented!()
+		self.pool().heap().allocate(align_of::<T>(), size).map(|pointer| pointer as *mut T)
 	}
 	
 	#[inline(always)]
 	fn aligned_alloc(self, alignment: usize, size: usize) -> Result<*mut c_void, PmdkError>
 	{
-		#[inline(always)]
-		fn is_power_of_two(value: size_t) -> bool
+		if self.is_null()
 		{
-			(value != 0) && ((value & (value - 1)) == 0)
+			return Err(PmdkError::PoolClosed)
 		}
 		
-		debug_assert!(self.is_not_null(), "self can not be null");
-		
-		debug_assert!(!is_power_of_two(alignment), "alignment must be a power of two");
+		if self.is_read_only()
+		{
+			return Err(PmdkError::ReadOnly)
+		}
 		
 		debug_assert!(size != 0, "size_of::<T>() can not be zero");
 		
-		unimplemented!()
+		self.pool().heap().allocate(alignment, size).map(|pointer| pointer as *mut c_void)
 	}
 	
 	#[inline(always)]
@@ -119,17 +172,52 @@ impl PMEMctopoolExt for *mut PMEMctopool
 		debug_assert!(self.is_not_null(), "self can not be null");
 		debug_assert!(pointer.is_not_null(), "pointer can not be null");
 		
-		unimplemented!()
+		self.pool().heap().usable_size(pointer as *mut u8)
 	}
 	
 	#[inline(always)]
 	fn realloc(self, pointer: *mut c_void, new_size: size_t) -> Result<*mut c_void, PmdkError>
 	{
-		debug_assert!(self.is_not_null(), "self can not be null");
+		if self.is_null()
+		{
+			return Err(PmdkError::PoolClosed)
+		}
+		
+		if self.is_read_only()
+		{
+			return Err(PmdkError::ReadOnly)
+		}
+		
 		debug_assert!(pointer.is_not_null(), "pointer can not be null");
 		debug_assert!(new_size != 0, "new_size can not be zero");
 		
-		unimplemented!()
+		self.pool().heap().reallocate(pointer as *mut u8, new_size).map(|pointer| pointer as *mut c_void)
+	}
+	
+	#[inline(always)]
+	fn realloc_in_place(self, pointer: *mut c_void, new_size: size_t) -> Result<(), PmdkError>
+	{
+		if self.is_null()
+		{
+			return Err(PmdkError::PoolClosed)
+		}
+		
+		if self.is_read_only()
+		{
+			return Err(PmdkError::ReadOnly)
+		}
+		
+		debug_assert!(pointer.is_not_null(), "pointer can not be null");
+		debug_assert!(new_size != 0, "new_size can not be zero");
+		
+		if self.pool().heap().reallocate_in_place(pointer as *mut u8, new_size)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(PmdkError::OutOfSpace(new_size))
+		}
 	}
 	
 	#[inline(always)]
@@ -137,7 +225,46 @@ impl PMEMctopoolExt for *mut PMEMctopool
 	{
 		debug_assert!(self.is_not_null(), "self can not be null");
 		debug_assert!(pointer.is_not_null(), "pointer can not be null");
+		assert!(!self.is_read_only(), "can not free in a read-only pool");
+		
+		self.pool().heap().free(pointer as *mut u8)
+	}
+	
+	#[inline(always)]
+	fn relocate_if_moved<T: CtoSafe>(self, named_roots: &CtoPoolNamedRoots) -> Result<Result<(), Vec<String>>, PmdkError>
+	{
+		debug_assert!(self.is_not_null(), "self can not be null");
 		
-		unimplemented!()
+		self.pool().relocate_if_moved(PMEMctopool::relocate_root::<T>, named_roots)
+	}
+	
+	#[inline(always)]
+	fn last_shutdown(self) -> LastShutdown
+	{
+		debug_assert!(self.is_not_null(), "self can not be null");
+		
+		self.pool().last_shutdown()
+	}
+	
+	#[inline(always)]
+	fn is_read_only(self) -> bool
+	{
+		debug_assert!(self.is_not_null(), "self can not be null");
+		
+		self.pool().is_read_only()
+	}
+	
+	#[inline(always)]
+	fn statistics(self) -> CtoPoolStatistics
+	{
+		debug_assert!(self.is_not_null(), "self can not be null");
+		
+		self.pool().statistics()
+	}
+	
+	#[inline(always)]
+	fn pool<'pool>(self) -> &'pool PMEMctopool
+	{
+		unsafe { & * self }
 	}
 }
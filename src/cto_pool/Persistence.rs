@@ -3,6 +3,13 @@
 
 
 /// A trait for persistence operations.
+///
+/// Implementations are chosen to suit the memory a pool or block allocator lives in:-
+///
+/// * `BestAvailablePersistence` for DAX devices and files mapped with `MAP_SYNC`, choosing the best instructions at runtime;
+/// * `ClwbPersistence`, `ClflushoptPersistence` and `ClflushPersistence` for the same, but for a known CPU, in decreasing order of performance;
+/// * `MsyncPersistence` for plain files, where flushing CPU caches does not reach storage;
+/// * `EadrPersistence` for platforms with eADR (extended Asynchronous DRAM Refresh), where CPU caches are inside the persistence domain.
 pub trait Persistence
 {
 	/// flush struct.
@@ -20,10 +27,52 @@ pub trait Persistence
 	}
 	
 	/// flush memory.
+	/// Starts writing back `length` bytes at `address`; the write back is only guaranteed to have completed after a subsequent `drain_memory()`.
 	#[inline(always)]
 	fn flush_memory(address: *mut c_void, length: usize);
 	
 	/// drain memory.
+	/// Waits for all preceding `flush_memory()` calls by this thread to complete.
 	#[inline(always)]
 	fn drain_memory();
+	
+	/// persist struct; a `flush_struct()` followed by a `drain_memory()`.
+	#[inline(always)]
+	fn persist_struct<T>(address: &T)
+	{
+		Self::flush_struct(address);
+		Self::drain_memory()
+	}
+	
+	/// persist NonNull; a `flush_non_null()` followed by a `drain_memory()`.
+	#[inline(always)]
+	fn persist_non_null<T>(address: NonNull<T>)
+	{
+		Self::flush_non_null(address);
+		Self::drain_memory()
+	}
+	
+	/// persist memory; a `flush_memory()` followed by a `drain_memory()`.
+	#[inline(always)]
+	fn persist_memory(address: *mut c_void, length: usize)
+	{
+		Self::flush_memory(address, length);
+		Self::drain_memory()
+	}
+	
+	#[doc(hidden)]
+	#[inline(always)]
+	fn for_each_cache_line<F: Fn(*mut u8)>(address: *mut c_void, length: usize, write_back: F)
+	{
+		let exclusive_end_address = address as usize + length;
+		
+		let cache_line_size = cache_line_size();
+		
+		let mut cache_line_address = round_address_down_to_start_of_cache_line(address as *mut u8) as usize;
+		while cache_line_address < exclusive_end_address
+		{
+			write_back(cache_line_address as *mut u8);
+			cache_line_address += cache_line_size;
+		}
+	}
 }
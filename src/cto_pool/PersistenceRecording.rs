@@ -0,0 +1,25 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+/// Records the persistence operations made anywhere in the process, such as the `CrashRecording` of a `CrashSimulator` or the `PersistencyRecording` of a `PersistencyChecker`; see `RecorderHook`.
+#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))]
+pub(crate) trait PersistenceRecording
+{
+	/// The cache line containing `address` has been written back (but not yet fenced) by this thread.
+	#[inline(always)]
+	fn write_back(&mut self, address: usize);
+	
+	/// This thread has fenced, which persists the cache lines it has written back.
+	#[inline(always)]
+	fn fence(&mut self);
+	
+	/// This thread has called `persistent_sync()`, which is a fence and a point at which everything stored should have been written back.
+	#[inline(always)]
+	fn persistent_sync(&mut self)
+	{
+		self.fence()
+	}
+	
+	/// `length` bytes at `address` have been persisted synchronously (eg by `msync`).
+	#[inline(always)]
+	fn sync(&mut self, address: usize, length: usize);
+}
@@ -4,14 +4,96 @@
 
 quick_error!
 {
-	/// Reason for failing to instantiate.
+	/// Reason for a CTO pool operation failing.
 	#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 	pub enum PmdkError
 	{
-		/// Reason for failing to instantiate.
-		X
+		/// There is no free space (currently) available for an allocation of this size in bytes.
+		OutOfSpace(size: usize)
 		{
 			description("No more space (currently) available")
+			display("No more space (currently) available for an allocation of '{}' bytes", size)
+		}
+		
+		/// An alignment was not a power of two.
+		BadAlignment(alignment: usize)
+		{
+			description("Alignment is not a power of two")
+			display("Alignment '{}' is not a power of two", alignment)
+		}
+		
+		/// The pool has been closed (or was never opened); the `*mut PMEMctopool` is null.
+		PoolClosed
+		{
+			description("CTO pool is closed")
+		}
+		
+		/// The pool was opened read-only and so can not be modified.
+		ReadOnly
+		{
+			description("CTO pool is read-only")
+		}
+		
+		/// Persistent metadata, such as a heap chunk header, is corrupt.
+		CorruptMetadata(what: &'static str, address: usize)
+		{
+			description("Corrupt CTO pool metadata")
+			display("Corrupt CTO pool metadata: {} at '0x{:X}'", what, address)
+		}
+		
+		/// An Input-Output error; `errno` is `None` if the error did not come from the operating system.
+		InputOutput(kind: ErrorKind, errno: Option<i32>)
+		{
+			description("Input/Output error")
+			display("Input/Output error of kind '{:?}' with errno '{:?}'", kind, errno)
+			from(error: ::std::io::Error) -> (error.kind(), error.raw_os_error())
+		}
+		
+		/// The pool could not be memory mapped.
+		MappingFailed(reason: String)
+		{
+			description("Could not memory map CTO pool")
+			display("Could not memory map CTO pool: {}", reason)
+			from(error: CouldNotMemoryMapError) -> (error.to_string())
+		}
+		
+		/// The pool could not be grown.
+		CanNotGrow(reason: String)
+		{
+			description("Could not grow CTO pool")
+			display("Could not grow CTO pool: {}", reason)
+		}
+		
+		/// Leaks could not be collected, as something still in use might have been freed.
+		CanNotCollectLeaks(reason: String)
+		{
+			description("Could not collect leaks in CTO pool")
+			display("Could not collect leaks in CTO pool: {}", reason)
+		}
+		
+		/// A pool set file could not be parsed or is not supported.
+		InvalidPoolSet(reason: String)
+		{
+			description("Invalid pool set file")
+			display("Invalid pool set file: {}", reason)
+			from(error: PoolSetParseError) -> (error.to_string())
+		}
+		
+		/// A layout name contained a NUL at this position.
+		InvalidLayoutName(position: usize)
+		{
+			description("Layout name contains a NUL")
+			display("Layout name contains a NUL at position '{}'", position)
+			from(error: NulError) -> (error.nul_position())
+		}
+		
+		/// The header at the start of the pool is invalid.
+		InvalidHeader(cause: CtoPoolHeaderValidationError)
+		{
+			cause(cause)
+			description(cause.description())
+			display("Invalid CTO pool header: {}", cause)
+			from()
 		}
 	}
 }
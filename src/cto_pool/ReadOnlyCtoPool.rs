@@ -0,0 +1,196 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A CTO pool of persistent memory opened read-only, for inspecting a pool without any risk of writing to it.
+/// The pool is memory mapped with `PROT_READ`, so any attempt to write to it faults.
+/// Unlike `CtoPool`, `cto_pool_opened()` is not called on the root, so volatile memory (eg mutex locks) is not re-initialized; such locks must not be used.
+/// Likewise, any `CtoPoolArc` held inside the pool's objects is stale and must not be used.
+/// To access the 'root' of the graph, use `deref()`; there is no mutable access.
+/// A read-only pool can not be relocated, so opening fails if the address the pool was last mapped at is occupied in this process.
+pub struct ReadOnlyCtoPool<RootValue: CtoSafe>(CtoPoolArc, PhantomData<RootValue>);
+
+impl<RootValue: CtoSafe> PartialEq for ReadOnlyCtoPool<RootValue>
+{
+	#[inline(always)]
+	fn eq(&self, other: &Self) -> bool
+	{
+		self.pool_pointer() == other.pool_pointer()
+	}
+}
+
+impl<RootValue: CtoSafe> Eq for ReadOnlyCtoPool<RootValue>
+{
+}
+
+impl<RootValue: CtoSafe> Debug for ReadOnlyCtoPool<RootValue>
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		f.write_str(&format!("ReadOnlyCtoPool({:?})", self.pool_pointer()))
+	}
+}
+
+unsafe impl<RootValue: CtoSafe> Send for ReadOnlyCtoPool<RootValue>
+{
+}
+
+unsafe impl<RootValue: CtoSafe> Sync for ReadOnlyCtoPool<RootValue>
+{
+}
+
+impl<RootValue: CtoSafe> Deref for ReadOnlyCtoPool<RootValue>
+{
+	type Target = RootValue;
+	
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		let existing_root = self.pool_pointer().get_root::<RootValue>();
+		if existing_root.is_null()
+		{
+			panic!("No root object");
+		}
+		else
+		{
+			unsafe { & * (existing_root as *const RootValue) }
+		}
+	}
+}
+
+impl<RootValue: CtoSafe> Borrow<RootValue> for ReadOnlyCtoPool<RootValue>
+{
+	#[inline(always)]
+	fn borrow(&self) -> &RootValue
+	{
+		self.deref()
+	}
+}
+
+impl<RootValue: CtoSafe> AsRef<RootValue> for ReadOnlyCtoPool<RootValue>
+{
+	#[inline(always)]
+	fn as_ref(&self) -> &RootValue
+	{
+		self.deref()
+	}
+}
+
+impl<RootValue: CtoSafe> ReadOnlyCtoPool<RootValue>
+{
+	/// Opens an existing pool read-only.
+	/// The pool is not validated; use `CtoPoolPathExt::validate_cto_pool_is_consistent()` first if `last_shutdown()` might require it.
+	/// Returns `CtoPoolOpenError::DifferentRootType` if the pool's root is of a different type (see `TypeFingerprint`).
+	/// As nothing is created or migrated, the only other error returned is `CtoPoolOpenError::OpenFailed`; the `InitializationError` of the error is never used.
+	#[inline(always)]
+	pub fn open(pool_set_file_path: &Path, layout_name: &str) -> Result<Self, CtoPoolOpenError<PmdkError>>
+	{
+		let layout_name = CString::new(layout_name).expect("Embedded NULs are not allowed in a layout name");
+		let length = layout_name.as_bytes().len();
+		assert!(length <= PMEMCTO_MAX_LAYOUT, "layout_name length exceeds PMEMCTO_MAX_LAYOUT, {}", PMEMCTO_MAX_LAYOUT);
+		
+		let pool_pointer = pool_set_file_path.open_cto_pool_read_only(layout_name.as_c_str()).map_err(CtoPoolOpenError::OpenFailed)?;
+		
+		if let Err(found) = pool_pointer.pool().check_root_type::<RootValue>()
+		{
+			pool_pointer.close();
+			return Err(CtoPoolOpenError::DifferentRootType { expected: TypeFingerprint::of::<RootValue>(), found })
+		}
+		
+		Ok(ReadOnlyCtoPool(CtoPoolArc::new(pool_pointer), PhantomData))
+	}
+	
+	/// Does the pool have a root object?
+	/// If not, `deref()` will panic.
+	#[inline(always)]
+	pub fn has_root(&self) -> bool
+	{
+		!self.pool_pointer().get_root::<RootValue>().is_null()
+	}
+	
+	/// How the pool was last shut down.
+	#[inline(always)]
+	pub fn last_shutdown(&self) -> LastShutdown
+	{
+		self.pool_pointer().last_shutdown()
+	}
+	
+	/// A snapshot of how the pool's memory is used.
+	#[inline(always)]
+	pub fn statistics(&self) -> CtoPoolStatistics
+	{
+		self.pool_pointer().statistics()
+	}
+	
+	/// Checks the integrity of the pool by walking everything reachable from the root (see `CtoPoolTraversal`); returns what was found to be wrong, which is empty for a sound pool.
+	/// Named roots are only checked to be valid pointers; use `check_with()` to walk them too.
+	/// Returns an error if the heap can not be walked.
+	#[inline(always)]
+	pub fn check(&self) -> Result<Vec<CtoPoolCheckFinding>, PmdkError>
+	{
+		self.check_with(|_cto_pool_traversal| {})
+	}
+	
+	/// As `check()`, but `traverse_named_roots` is also called to walk named roots (by passing a pointer to each, as obtained from `root()`, to `CtoPoolTraversal::traverse()`), as only it knows their types.
+	#[inline(always)]
+	pub fn check_with<TraverseNamedRoots: FnOnce(&CtoPoolTraversal)>(&self, traverse_named_roots: TraverseNamedRoots) -> Result<Vec<CtoPoolCheckFinding>, PmdkError>
+	{
+		Ok(self.traversal_with(traverse_named_roots)?.findings())
+	}
+	
+	/// Finds everything allocated in the pool that can not be reached from its root, without freeing it; see `CtoPool::collect_leaks()`.
+	/// Anything reachable only from a named root is included, as named roots are not walked; use `find_leaks_with()` to walk them too.
+	/// Returns an error if the heap can not be walked.
+	#[inline(always)]
+	pub fn find_leaks(&self) -> Result<CtoPoolLeaks, PmdkError>
+	{
+		self.find_leaks_with(|_cto_pool_traversal| {})
+	}
+	
+	/// As `find_leaks()`, but `traverse_named_roots` is also called to walk named roots, as for `check_with()`.
+	#[inline(always)]
+	pub fn find_leaks_with<TraverseNamedRoots: FnOnce(&CtoPoolTraversal)>(&self, traverse_named_roots: TraverseNamedRoots) -> Result<CtoPoolLeaks, PmdkError>
+	{
+		Ok(self.traversal_with(traverse_named_roots)?.leaks())
+	}
+	
+	/// The named root `name`, if there is one; see `CtoPool::root()`.
+	/// `cto_pool_opened()` is not called on it, and nothing in the pool is accessed mutably.
+	/// Returns an error if the named root was inserted as a different type.
+	#[inline(always)]
+	pub fn root<T: CtoSafe>(&self, name: &str) -> Result<Option<&T>, RootDirectoryError>
+	{
+		let root = self.pool_pointer().pool().named_root_without_opening::<T>(name)?;
+		Ok(if root.is_null()
+		{
+			None
+		}
+		else
+		{
+			Some(unsafe { & * root })
+		})
+	}
+	
+	/// The names and type fingerprints of all named roots.
+	#[inline(always)]
+	pub fn list_roots(&self) -> Vec<(String, TypeFingerprint)>
+	{
+		self.pool_pointer().pool().named_roots()
+	}
+	
+	#[inline(always)]
+	fn traversal_with<TraverseNamedRoots: FnOnce(&CtoPoolTraversal)>(&self, traverse_named_roots: TraverseNamedRoots) -> Result<CtoPoolTraversal, PmdkError>
+	{
+		let cto_pool_traversal = self.pool_pointer().pool().traversal()?;
+		cto_pool_traversal.traverse(self.pool_pointer().get_root::<RootValue>() as *const RootValue);
+		traverse_named_roots(&cto_pool_traversal);
+		Ok(cto_pool_traversal)
+	}
+	
+	#[inline(always)]
+	fn pool_pointer(&self) -> *mut PMEMctopool
+	{
+		self.0.pool_pointer()
+	}
+}
@@ -0,0 +1,60 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+/// Where a `CrashSimulator` or a `PersistencyChecker` installs what it records to; it is process-wide, as persistence operations are not specific to a pool, and so holds at most one recording.
+#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))]
+pub(crate) struct RecorderHook<Recording>(AtomicPtr<Mutex<Recording>>);
+
+#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))]
+impl<Recording> RecorderHook<Recording>
+{
+	/// For use in a `static`.
+	#[inline(always)]
+	pub(crate) const fn new() -> Self
+	{
+		RecorderHook(AtomicPtr::new(null_mut()))
+	}
+	
+	/// Returns `None`, dropping `recording`, if a recording is already installed.
+	#[inline(always)]
+	pub(crate) fn install(&self, recording: Recording) -> Option<*mut Mutex<Recording>>
+	{
+		let installed = Box::into_raw(Box::new(Mutex::new(recording)));
+		
+		if self.0.compare_exchange(null_mut(), installed, SeqCst, SeqCst).is_err()
+		{
+			drop(unsafe { Box::from_raw(installed) });
+			None
+		}
+		else
+		{
+			Some(installed)
+		}
+	}
+	
+	/// Returns the recording if `installed` is still installed, ie has not already been uninstalled.
+	#[inline(always)]
+	pub(crate) fn uninstall(&self, installed: *mut Mutex<Recording>) -> Option<Recording>
+	{
+		match self.0.compare_exchange(installed, null_mut(), SeqCst, SeqCst)
+		{
+			Ok(installed) =>
+			{
+				let installed: Mutex<Recording> = *unsafe { Box::from_raw(installed) };
+				Some(installed.into_inner())
+			}
+			Err(_) => None,
+		}
+	}
+	
+	/// Does nothing if no recording is installed.
+	#[inline(always)]
+	pub(crate) fn with<User: FnOnce(&mut Recording)>(&self, user: User)
+	{
+		let installed = self.0.load(SeqCst);
+		if !installed.is_null()
+		{
+			let mut recording = unsafe { & * installed }.lock();
+			user(&mut *recording)
+		}
+	}
+}
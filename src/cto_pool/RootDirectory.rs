@@ -0,0 +1,55 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Stored in Persistent Memory.
+/// A fixed-size table of named roots, so that independent parts of a program can each keep their own root in one pool.
+/// Allocated from the pool the first time a named root is inserted, and referred to by the pool's header.
+#[repr(C)]
+pub(crate) struct RootDirectory
+{
+	entries: [RootDirectoryEntry; MaximumNumberOfNamedRoots],
+}
+
+impl RootDirectory
+{
+	/// All entries are initially not in use.
+	#[inline(always)]
+	fn initialize(root_directory: *mut Self, persistence: MappingPersistence)
+	{
+		unsafe { write_bytes(root_directory, 0, 1) };
+		persistence.persist_memory(root_directory as usize, size_of::<Self>())
+	}
+	
+	/// Index of the entry in use with this name, if any.
+	#[inline(always)]
+	fn find(&self, name: &[u8]) -> Option<usize>
+	{
+		self.entries.iter().position(|entry| entry.is_in_use() && entry.name() == name)
+	}
+	
+	/// Index of an entry not in use, if any.
+	#[inline(always)]
+	fn find_unused(&self) -> Option<usize>
+	{
+		self.entries.iter().position(|entry| !entry.is_in_use())
+	}
+	
+	#[inline(always)]
+	fn entries(&self) -> &[RootDirectoryEntry]
+	{
+		&self.entries[..]
+	}
+	
+	#[inline(always)]
+	fn entry(&self, index: usize) -> &RootDirectoryEntry
+	{
+		&self.entries[index]
+	}
+	
+	#[inline(always)]
+	fn entry_mut(&mut self, index: usize) -> &mut RootDirectoryEntry
+	{
+		&mut self.entries[index]
+	}
+}
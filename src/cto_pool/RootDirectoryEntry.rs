@@ -0,0 +1,99 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Stored in Persistent Memory.
+/// A slot in a `RootDirectory`; it is in use if `root` is not null.
+#[repr(C)]
+pub(crate) struct RootDirectoryEntry
+{
+	name: [u8; MaximumNamedRootNameLength],
+	type_fingerprint: TypeFingerprint,
+	root: AtomicPtr<c_void>,
+}
+
+impl RootDirectoryEntry
+{
+	/// Everything else is persisted before `root`, which makes the entry in use.
+	#[inline(always)]
+	fn initialize(&mut self, name: &[u8], type_fingerprint: TypeFingerprint, root: *mut c_void, persistence: MappingPersistence)
+	{
+		debug_assert!(!self.is_in_use(), "entry is already in use");
+		debug_assert!(root.is_not_null(), "root can not be null");
+		
+		let mut padded_name = [0u8; MaximumNamedRootNameLength];
+		padded_name[.. name.len()].copy_from_slice(name);
+		
+		unsafe
+		{
+			write(&mut self.name, padded_name);
+			write(&mut self.type_fingerprint, type_fingerprint);
+		}
+		persistence.persist_memory(self as *const Self as usize, size_of::<Self>());
+		
+		self.root.store(root, Release);
+		self.persist_root(persistence)
+	}
+	
+	#[inline(always)]
+	fn clear(&self, persistence: MappingPersistence)
+	{
+		self.root.store(null_mut(), Release);
+		self.persist_root(persistence)
+	}
+	
+	#[inline(always)]
+	fn is_in_use(&self) -> bool
+	{
+		self.root().is_not_null()
+	}
+	
+	/// The name, without trailing NUL padding.
+	#[inline(always)]
+	fn name(&self) -> &[u8]
+	{
+		let length = self.name.iter().position(|byte| *byte == 0).unwrap_or(MaximumNamedRootNameLength);
+		&self.name[.. length]
+	}
+	
+	#[inline(always)]
+	fn type_fingerprint(&self) -> TypeFingerprint
+	{
+		self.type_fingerprint
+	}
+	
+	/// Checks that the root is of type `T`.
+	#[inline(always)]
+	fn check_type<T>(&self) -> Result<(), RootDirectoryError>
+	{
+		let expected = TypeFingerprint::of::<T>();
+		let found = self.type_fingerprint();
+		if expected == found
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(RootDirectoryError::DifferentType(String::from_utf8_lossy(self.name()).into_owned(), expected, found))
+		}
+	}
+	
+	#[inline(always)]
+	fn root(&self) -> *mut c_void
+	{
+		self.root.load(Acquire)
+	}
+	
+	/// Relocates the pointer to the root, but not the root itself.
+	#[inline(always)]
+	fn relocate_root_pointer(&self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		cto_pool_relocation.relocate_atomic_pointer(&self.root);
+	}
+	
+	#[inline(always)]
+	fn persist_root(&self, persistence: MappingPersistence)
+	{
+		persistence.persist_memory(&self.root as *const AtomicPtr<c_void> as usize, size_of::<AtomicPtr<c_void>>())
+	}
+}
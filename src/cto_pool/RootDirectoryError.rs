@@ -0,0 +1,48 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+quick_error!
+{
+	/// Reason for an operation on a named root failing.
+	#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	pub enum RootDirectoryError
+	{
+		/// A name was empty, longer than `MaximumNamedRootNameLength` or contained a NUL.
+		InvalidName(name: String)
+		{
+			description("Invalid named root name")
+			display("Named root name '{}' is empty, longer than '{}' bytes or contains a NUL", name, MaximumNamedRootNameLength)
+		}
+		
+		/// A named root with this name already exists.
+		AlreadyExists(name: String)
+		{
+			description("Named root already exists")
+			display("Named root '{}' already exists", name)
+		}
+		
+		/// The pool already has `MaximumNumberOfNamedRoots`.
+		TooManyNamedRoots
+		{
+			description("Too many named roots")
+			display("The pool already has the maximum number of named roots, '{}'", MaximumNumberOfNamedRoots)
+		}
+		
+		/// A named root was inserted with a different type to the one it is being used as.
+		DifferentType(name: String, expected: TypeFingerprint, found: TypeFingerprint)
+		{
+			description("Named root is of a different type")
+			display("Named root '{}' is of a different type; expected '{:?}' but found '{:?}'", name, expected, found)
+		}
+		
+		/// The pool could not allocate a named root (or the root directory), or is read-only.
+		Pool(cause: PmdkError)
+		{
+			cause(cause)
+			description(cause.description())
+			display("Named root pool error: {}", cause)
+			from()
+		}
+	}
+}
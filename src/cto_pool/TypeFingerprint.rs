@@ -0,0 +1,64 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Stored in Persistent Memory.
+/// Identifies the type of a root, so that a root is not reinterpreted as a different type.
+/// A fingerprint of all zeros (the default) is recorded when there is no root.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct TypeFingerprint
+{
+	/// `size_of()` the type.
+	pub size: u64,
+	
+	/// `align_of()` the type.
+	pub alignment: u64,
+	
+	/// `CtoSafe::schema_hash()` of the type.
+	pub schema_hash: u64,
+	
+	/// `CtoSafe::schema_version()` of the type.
+	pub schema_version: u64,
+}
+
+impl TypeFingerprint
+{
+	/// The fingerprint of `T`.
+	#[inline(always)]
+	pub fn of<T: CtoSafe>() -> Self
+	{
+		Self
+		{
+			size: size_of::<T>() as u64,
+			alignment: align_of::<T>() as u64,
+			schema_hash: T::schema_hash(),
+			schema_version: T::schema_version(),
+		}
+	}
+	
+	/// A schema hash of `description`, using 64-bit FNV-1a, which, unlike the hashers in `std`, is stable between releases of Rust.
+	/// For use by `CtoSafe::schema_hash()`; `description` should change whenever the schema does, eg by listing the fields, in order, with their types.
+	#[inline(always)]
+	pub fn schema_hash_of(description: &str) -> u64
+	{
+		Self::fnv1a(Self::FnvOffsetBasis, description.bytes())
+	}
+	
+	/// Combines the schema hash of a generic type with those of its type parameters, for use by `CtoSafe::schema_hash()`.
+	#[inline(always)]
+	pub fn combine_schema_hashes(schema_hash: u64, type_parameter_schema_hashes: &[u64]) -> u64
+	{
+		type_parameter_schema_hashes.iter().fold(schema_hash, |hash, type_parameter_schema_hash| Self::fnv1a(hash, (0 .. size_of::<u64>()).map(|index| (type_parameter_schema_hash >> (index * 8)) as u8)))
+	}
+	
+	const FnvOffsetBasis: u64 = 0xCBF2_9CE4_8422_2325;
+	
+	const FnvPrime: u64 = 0x0000_0100_0000_01B3;
+	
+	#[inline(always)]
+	fn fnv1a<Bytes: Iterator<Item=u8>>(hash: u64, bytes: Bytes) -> u64
+	{
+		bytes.fold(hash, |hash, byte| (hash ^ (byte as u64)).wrapping_mul(Self::FnvPrime))
+	}
+}
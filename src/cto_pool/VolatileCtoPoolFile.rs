@@ -0,0 +1,121 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A pool in volatile memory, for tests: a `memfd_create()` file, which lives only as long as this object.
+/// The whole `CtoPool` API works as for a pool on a DAX device or in a regular file, but nothing survives the process.
+///
+/// `reopen()` simulates a restart of the process, keeping the bytes of the pool and calling `cto_pool_opened()` again on everything reachable from the root.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Debug)]
+pub struct VolatileCtoPoolFile
+{
+	// Keeps the `memfd_create()` file, and so its bytes, alive; it is opened (and memory mapped) using `path`.
+	#[allow(dead_code)]
+	file: File,
+	path: PathBuf,
+	layout_name: String,
+	pool_size: usize,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl VolatileCtoPoolFile
+{
+	const Mode: mode_t = 0o600;
+	
+	/// Creates an empty pool of `pool_size` bytes with the layout `layout_name`.
+	#[inline(always)]
+	pub fn new(layout_name: &str, pool_size: usize) -> Result<Self, PmdkError>
+	{
+		const MFD_CLOEXEC: c_uint = 0x0001;
+		
+		let layout_name_c_string = CString::new(layout_name)?;
+		let name = CString::new(format!("cto-pool-{}", layout_name))?;
+		let file_descriptor = unsafe { syscall(SYS_memfd_create, name.as_ptr(), MFD_CLOEXEC) };
+		if file_descriptor == -1
+		{
+			return Err(io::Error::last_os_error().into())
+		}
+		let file = unsafe { File::from_raw_fd(file_descriptor as RawFd) };
+		file.set_len(pool_size as u64)?;
+		
+		let this = Self
+		{
+			path: PathBuf::from(format!("/proc/self/fd/{}", file_descriptor)),
+			file,
+			layout_name: layout_name.to_owned(),
+			pool_size,
+		};
+		
+		let mapped_memory = this.path.memory_map_cto_pool(false, MemoryMapAddress::Random)?;
+		PMEMctopool::create(mapped_memory, &layout_name_c_string, None)?.close();
+		
+		Ok(this)
+	}
+	
+	/// The path of the file, which can be used wherever a pool set file path can, eg with `CtoPool::open_with_migrations()` or `CtoPoolPathExt`, but only by this process.
+	#[inline(always)]
+	pub fn path(&self) -> &Path
+	{
+		&self.path
+	}
+	
+	/// Opens the pool; see `CtoPool::open()`.
+	#[inline(always)]
+	pub fn open<RootValue: CtoSafe, InitializationError: error::Error, RootValueInitializer: FnOnce(&mut RootValue, &CtoPoolArc) -> Result<(), InitializationError>>(&self, root_value_initializer: RootValueInitializer) -> Result<CtoPool<RootValue>, CtoPoolOpenError<InitializationError>>
+	{
+		CtoPool::open(&self.path, &self.layout_name, self.pool_size, Self::Mode, root_value_initializer)
+	}
+	
+	/// Opens the pool, migrating its root with `migrations` if it has an older schema version; see `CtoPool::open_with_migrations()`.
+	#[inline(always)]
+	pub fn open_with_migrations<RootValue: CtoSafe, InitializationError: error::Error, RootValueInitializer: FnOnce(&mut RootValue, &CtoPoolArc) -> Result<(), InitializationError>>(&self, migrations: &CtoPoolMigrations<RootValue, InitializationError>, root_value_initializer: RootValueInitializer) -> Result<CtoPool<RootValue>, CtoPoolOpenError<InitializationError>>
+	{
+		CtoPool::open_with_migrations(&self.path, &self.layout_name, self.pool_size, Self::Mode, migrations, root_value_initializer)
+	}
+	
+	/// Opens the pool read-only; see `ReadOnlyCtoPool::open()`.
+	/// The pool must not be open.
+	#[inline(always)]
+	pub fn open_read_only<RootValue: CtoSafe>(&self) -> Result<ReadOnlyCtoPool<RootValue>, CtoPoolOpenError<PmdkError>>
+	{
+		ReadOnlyCtoPool::open(&self.path, &self.layout_name)
+	}
+	
+	/// Simulates a restart of the process: `cto_pool` is closed cleanly and unmapped, and the pool is then opened again, as if by a new process.
+	///
+	/// `cto_pool` is closed even though objects in the pool (eg a `CtoVec` or a `FreeList`) still hold a `CtoPoolArc` (or a `CtoPoolAlloc`) to it, as they would in a process that exits.
+	///
+	/// # Safety
+	///
+	/// Nothing obtained from `cto_pool` may be used afterwards, eg a `CtoArc` cloned from its root, or a `CtoPoolArc`; its memory is no longer mapped.
+	#[inline(always)]
+	pub unsafe fn reopen<RootValue: CtoSafe, InitializationError: error::Error, RootValueInitializer: FnOnce(&mut RootValue, &CtoPoolArc) -> Result<(), InitializationError>>(&self, cto_pool: CtoPool<RootValue>, root_value_initializer: RootValueInitializer) -> Result<CtoPool<RootValue>, CtoPoolOpenError<InitializationError>>
+	{
+		cto_pool.close_regardless_of_references();
+		
+		self.open(root_value_initializer)
+	}
+	
+	/// As `reopen()`, but the address range `cto_pool` was mapped at is occupied while the pool is opened again, so that it is mapped elsewhere and has to be relocated.
+	///
+	/// # Safety
+	///
+	/// As for `reopen()`.
+	#[inline(always)]
+	pub unsafe fn reopen_relocated<RootValue: CtoSafe, InitializationError: error::Error, RootValueInitializer: FnOnce(&mut RootValue, &CtoPoolArc) -> Result<(), InitializationError>>(&self, cto_pool: CtoPool<RootValue>, root_value_initializer: RootValueInitializer) -> Result<CtoPool<RootValue>, CtoPoolOpenError<InitializationError>>
+	{
+		let (unaligned_address, length) =
+		{
+			let mapped_memory = &cto_pool.allocator().pool_pointer().pool().mapped_memory;
+			(mapped_memory.as_ptr(), mapped_memory.length())
+		};
+		
+		cto_pool.close_regardless_of_references();
+		
+		// Un-mapped once the pool has been opened again.
+		let _occupied = MappedMemory::reserve(length, MemoryMapAddress::Mandatory { unaligned_address }).map_err(|error| CtoPoolOpenError::OpenFailed(error.into()))?;
+		
+		self.open(root_value_initializer)
+	}
+}
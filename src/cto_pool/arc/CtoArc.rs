@@ -5,6 +5,8 @@
 /// A thread-safe reference-counting pointer.
 /// 'Arc' stands for 'Atomically Reference Counted'.
 /// See Rust stdlib documentation.
+/// It does not keep its pool open, as it finds its pool with the `CtoPoolRegistry`; it must not be used after its pool has been closed.
+/// If it is dropped after its pool has been closed, nothing is done and what it refers to is leaked (see `CtoPool::collect_leaks()`), as the pool's memory is no longer mapped.
 pub struct CtoArc<Value: CtoSafe>
 {
 	persistent_memory_pointer: NonNull<CtoArcInner<Value>>,
@@ -42,6 +44,28 @@ impl<Value: CtoSafe> CtoSafe for CtoArc<Value>
 	{
 		self.persistent_memory_mut().cto_pool_opened(cto_pool_arc)
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		cto_pool_relocation.relocate_non_null(&mut self.persistent_memory_pointer);
+		self.cto_pool_relocate_persistent_memory(cto_pool_relocation)
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		if cto_pool_traversal.is_valid_pointer(self.persistent_memory_pointer())
+		{
+			self.cto_pool_traverse_persistent_memory(cto_pool_traversal)
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::arc::CtoArc"), &[Value::schema_hash()])
+	}
 }
 
 unsafe impl<Value: CtoSafe + Sync + Send> Send for CtoArc<Value>
@@ -57,6 +81,11 @@ impl<Value: CtoSafe> Drop for CtoArc<Value>
 	#[inline(always)]
 	fn drop(&mut self)
 	{
+		if CtoPoolRegistry::pool_pointer_owning(self.persistent_memory_pointer()).is_none()
+		{
+			return;
+		}
+		
 		// Because `fetch_sub` is already atomic, we do not need to synchronize with other threads unless we are going to delete the object.
 		// This same logic applies to the below `fetch_sub` to the `weak` count.
 		if self.persistent_memory().decrement_strong_reference_count() != 1
@@ -236,40 +265,45 @@ impl<Value: CtoSafe> Clone for CtoArc<Value>
 impl<Value: CtoSafe + Clone> CtoArc<Value>
 {
 	/// Produces a clone of the data
+	/// Returns `PmdkError::PoolClosed` if the pool is being closed.
 	#[inline(always)]
-	pub(crate) fn deep_clone(&self) -> Self
+	pub(crate) fn deep_clone(&self) -> Result<Self, PmdkError>
 	{
 		let cto_arc_inner = self.persistent_memory();
-		let cto_pool_arc = &cto_arc_inner.cto_pool_arc;
-		let persistent_memory_pointer = cto_pool_arc.pool_pointer().aligned_allocate().unwrap();
+		let cto_pool_arc = &CtoPoolRegistry::cto_pool_arc_owning(cto_arc_inner as *const CtoArcInner<Value>).ok_or(PmdkError::PoolClosed)?;
+		let persistent_memory_pointer = cto_pool_arc.pool_pointer().aligned_allocate()?;
 		
-		unsafe
-		{
-			Self::initialize_persistent_memory::<(), _>(persistent_memory_pointer, cto_pool_arc, |value_mut_pointer, _cto_pool_arc|
+		Ok
+		(
+			unsafe
 			{
-				write(value_mut_pointer, cto_arc_inner.value.clone());
-				Ok(())
-			}).unwrap()
-		}
+				Self::initialize_persistent_memory::<(), _>(persistent_memory_pointer, cto_pool_arc, |value_mut_pointer, _cto_pool_arc|
+				{
+					write(value_mut_pointer, cto_arc_inner.value.clone());
+					Ok(())
+				}).unwrap()
+			}
+		)
 	}
 }
 
 impl<Value: CtoSafe> CtoArc<Value>
 {
 	/// Produces a clone of the data, customized.
+	/// Returns `CtoPoolAllocationError::Allocation(PmdkError::PoolClosed)` if the pool is being closed.
 	#[inline(always)]
-	pub(crate) fn deep_clone_customized<CallbackError, DeepCloneCallback: FnOnce(*mut Value, &CtoPoolArc, &Value) -> Result<(), CallbackError>>(&self, deep_clone_initializer: DeepCloneCallback) -> Result<Self, CallbackError>
+	pub(crate) fn deep_clone_customized<CallbackError, DeepCloneCallback: FnOnce(*mut Value, &CtoPoolArc, &Value) -> Result<(), CallbackError>>(&self, deep_clone_initializer: DeepCloneCallback) -> Result<Self, CtoPoolAllocationError<CallbackError>>
 	{
 		let cto_arc_inner = self.persistent_memory();
-		let cto_pool_arc = &cto_arc_inner.cto_pool_arc;
-		let persistent_memory_pointer = cto_pool_arc.pool_pointer().aligned_allocate().unwrap();
+		let cto_pool_arc = &CtoPoolRegistry::cto_pool_arc_owning(cto_arc_inner as *const CtoArcInner<Value>).ok_or(PmdkError::PoolClosed)?;
+		let persistent_memory_pointer = cto_pool_arc.pool_pointer().aligned_allocate()?;
 		
 		unsafe
 		{
 			Self::initialize_persistent_memory(persistent_memory_pointer, cto_pool_arc, |value_mut_pointer, cto_pool_arc|
 			{
 				deep_clone_initializer(value_mut_pointer, cto_pool_arc, &cto_arc_inner.value)
-			})
+			}).map_err(CtoPoolAllocationError::Initialization)
 		}
 	}
 	
@@ -299,6 +333,15 @@ impl<Value: CtoSafe> CtoArc<Value>
 		}
 	}
 	
+	/// As `from_raw()`, but first checks that `raw_value_pointer` points into the pool of `cto_pool_arc` (see `CtoPoolRegistry::check_pointer_is_in_pool()`).
+	#[inline(always)]
+	pub unsafe fn checked_from_raw(raw_value_pointer: *mut Value, cto_pool_arc: &CtoPoolArc) -> Result<Self, CtoPointerError>
+	{
+		CtoPoolRegistry::check_pointer_is_in_pool(cto_pool_arc, raw_value_pointer)?;
+		
+		Ok(Self::from_raw(raw_value_pointer))
+	}
+	
 	/// Creates a new [`WeakCtoArc`][weak] pointer to this value.
 	///
 	/// [weak]: struct.WeakCtoArc.html
@@ -430,9 +473,33 @@ impl<Value: CtoSafe> CtoArc<Value>
 		{
 			fence(Acquire);
 			
-			let pool_pointer = self.persistent_memory().cto_pool_arc.pool_pointer();
-			
-			pool_pointer.free(ptr);
+			if let Some(pool_pointer) = CtoPoolRegistry::pool_pointer_owning(ptr)
+			{
+				pool_pointer.free(ptr);
+			}
+		}
+	}
+	
+	// Other references to the same persistent memory may have already relocated it.
+	#[inline(always)]
+	fn cto_pool_relocate_persistent_memory(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		if cto_pool_relocation.is_first_visit(self.persistent_memory_pointer())
+		{
+			self.persistent_memory_mut().cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	// Every reference to the same persistent memory is counted, but only the first traverses it.
+	#[inline(always)]
+	fn cto_pool_traverse_persistent_memory(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		let persistent_memory_pointer = self.persistent_memory_pointer();
+		let persistent_memory = self.persistent_memory();
+		cto_pool_traversal.found_strong_reference(persistent_memory_pointer, persistent_memory.strong_count_relaxed(), Some((persistent_memory.weak_count_relaxed(), CtoArcInner::<Value>::WeakCountJustBeforeLock)));
+		if cto_pool_traversal.is_first_visit(persistent_memory_pointer)
+		{
+			persistent_memory.cto_pool_traverse(cto_pool_traversal)
 		}
 	}
 	
@@ -35,6 +35,46 @@ impl<Value: CtoSafe> CtoSafe for CtoArcCell<Value>
 			}
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		match self.0.load(SeqCst)
+		{
+			Self::InvalidValueForCtoArc => panic!("InvalidValueForCtoArc was persisted"),
+			bytes =>
+			{
+				let bytes = cto_pool_relocation.relocated(bytes as *mut u8) as usize;
+				self.0.store(bytes, SeqCst);
+				cto_pool_relocation.persist(&self.0);
+				
+				let mut cto_arc = Self::usize_to_cto_arc(bytes);
+				cto_arc.cto_pool_relocate_persistent_memory(cto_pool_relocation);
+				forget(cto_arc);
+			}
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		match self.0.load(SeqCst)
+		{
+			Self::InvalidValueForCtoArc => cto_pool_traversal.record_finding(CtoPoolCheckFinding::PointerOutsidePool(Self::InvalidValueForCtoArc)),
+			bytes =>
+			{
+				let cto_arc = Self::usize_to_cto_arc(bytes);
+				cto_arc.cto_pool_traverse(cto_pool_traversal);
+				forget(cto_arc);
+			}
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::arc::CtoArcCell"), &[Value::schema_hash()])
+	}
 }
 
 impl<Value: CtoSafe> CtoArcCell<Value>
@@ -63,8 +103,35 @@ impl<Value: CtoSafe> CtoArcCell<Value>
 	}
 	
 	/// Stores a new value in the `CtoArcCell`, returning the previous value.
+	/// In debug builds (or with the `pointer-checks` feature), panics if `cto_arc` is not in the same pool as this `CtoArcCell`; see `checked_set()`.
 	#[inline(always)]
 	pub fn set(&self, cto_arc: CtoArc<Value>) -> CtoArc<Value>
+	{
+		#[cfg(any(debug_assertions, feature = "pointer-checks"))]
+		{
+			if let Err(error) = CtoPoolRegistry::check_pointer(self, CtoArc::as_ptr(&cto_arc))
+			{
+				panic!("{}", error)
+			}
+		}
+		
+		self.replace(cto_arc)
+	}
+	
+	/// As `set()`, but first checks that `cto_arc` is in the same pool as this `CtoArcCell` (see `CtoPoolRegistry::check_pointer()`); if not, nothing is stored and `cto_arc` is handed back with the error.
+	#[inline(always)]
+	pub fn checked_set(&self, cto_arc: CtoArc<Value>) -> Result<CtoArc<Value>, (CtoPointerError, CtoArc<Value>)>
+	{
+		if let Err(error) = CtoPoolRegistry::check_pointer(self, CtoArc::as_ptr(&cto_arc))
+		{
+			return Err((error, cto_arc))
+		}
+		
+		Ok(self.replace(cto_arc))
+	}
+	
+	#[inline(always)]
+	fn replace(&self, cto_arc: CtoArc<Value>) -> CtoArc<Value>
 	{
 		let old_cto_arc = self.take();
 		self.put(cto_arc);
@@ -10,7 +10,6 @@ pub(crate) struct CtoArcInner<Value: CtoSafe>
 	value: Value,
 	strong_counter: AtomicUsize,
 	weak_counter: AtomicUsize,
-	cto_pool_arc: CtoPoolArc,
 }
 
 unsafe impl<Value: CtoSafe + Sync + Send> Send for CtoArcInner<Value>
@@ -43,12 +42,6 @@ impl<Value: CtoSafe> DerefMut for CtoArcInner<Value>
 
 impl<Value: CtoSafe> CtoArcInner<Value>
 {
-	#[inline(always)]
-	fn common_initialization(&mut self, cto_pool_arc: &CtoPoolArc)
-	{
-		cto_pool_arc.write(&mut self.cto_pool_arc);
-	}
-	
 	#[inline(always)]
 	fn allocated<InitializationError, Initializer: FnOnce(*mut Value, &CtoPoolArc) -> Result<(), InitializationError>>(&mut self, cto_pool_arc: &CtoPoolArc, initializer: Initializer) -> Result<(), InitializationError>
 	{
@@ -57,19 +50,27 @@ impl<Value: CtoSafe> CtoArcInner<Value>
 		// Start the weak pointer count as 1 which is the weak pointer that's held by all the strong pointers.
 		unsafe { write(&mut self.weak_counter, AtomicUsize::new(Self::WeakCountJustBeforeLock)) };
 		
-		self.common_initialization(cto_pool_arc);
-		
 		initializer(&mut self.value, cto_pool_arc)
 	}
 	
 	#[inline(always)]
 	fn cto_pool_opened(&mut self, cto_pool_arc: &CtoPoolArc)
 	{
-		self.common_initialization(cto_pool_arc);
-		
 		self.value.cto_pool_opened(cto_pool_arc)
 	}
 	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.value.cto_pool_relocate(cto_pool_relocation)
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		self.value.cto_pool_traverse(cto_pool_traversal)
+	}
+	
 	#[inline(always)]
 	fn into_raw_value_pointer(&mut self) -> *mut Value
 	{
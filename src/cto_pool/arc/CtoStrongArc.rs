@@ -21,6 +21,37 @@ impl<I: CtoStrongArcInner> CtoSafe for CtoStrongArc<I>
 	{
 		self.deref_mut().cto_pool_opened(cto_pool_arc)
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		cto_pool_relocation.relocate_non_null(&mut self.0);
+		if cto_pool_relocation.is_first_visit(self.0.as_ptr())
+		{
+			self.deref_mut().cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	// Every reference to the inner data structure is counted, but only the first traverses it.
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		let inner = self.0.as_ptr();
+		if cto_pool_traversal.is_valid_pointer(inner)
+		{
+			cto_pool_traversal.found_strong_reference(inner, self.deref().reference_counter().load(SeqCst), None);
+			if cto_pool_traversal.is_first_visit(inner)
+			{
+				self.deref().cto_pool_traverse(cto_pool_traversal)
+			}
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::arc::CtoStrongArc"), &[I::schema_hash()])
+	}
 }
 
 impl<I: CtoStrongArcInner> Drop for CtoStrongArc<I>
@@ -172,13 +203,13 @@ impl<I: CtoStrongArcInner + Hasher> Hasher for CtoStrongArc<I>
 	{
 		(**self).write_u64(i)
 	}
-	
+
 //	#[inline(always)]
 //	fn write_u128(&mut self, i: u128)
 //	{
 //		(**self).write_u128(i)
 //	}
-	
+
 	#[inline(always)]
 	fn write_usize(&mut self, i: usize)
 	{
@@ -208,13 +239,13 @@ impl<I: CtoStrongArcInner + Hasher> Hasher for CtoStrongArc<I>
 	{
 		(**self).write_i64(i)
 	}
-	
+
 //	#[inline(always)]
 //	fn write_i128(&mut self, i: i128)
 //	{
 //		(**self).write_i128(i)
 //	}
-	
+
 	#[inline(always)]
 	fn write_isize(&mut self, i: isize)
 	{
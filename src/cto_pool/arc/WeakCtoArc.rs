@@ -16,6 +16,8 @@
 /// [`CtoArc`]: struct.Arc.html
 /// [`CtoArc::downgrade`]: struct.Arc.html#method.downgrade
 /// [`upgrade`]: struct.Weak.html#method.upgrade
+///
+/// As for a `CtoArc`, if it is dropped after its pool has been closed, nothing is done.
 pub struct WeakCtoArc<Value: CtoSafe>
 {
 	persistent_memory_pointer: Option<NonNull<CtoArcInner<Value>>>,
@@ -80,6 +82,12 @@ impl<Value: CtoSafe> Drop for WeakCtoArc<Value>
 		{
 			let ptr = persistent_memory_pointer.as_ptr();
 			
+			let pool_pointer = match CtoPoolRegistry::pool_pointer_owning(ptr)
+			{
+				None => return,
+				Some(pool_pointer) => pool_pointer,
+			};
+			
 			let cto_arc_inner = unsafe { persistent_memory_pointer.as_ref() };
 			
 			// If we find out that we were the last weak pointer, then its time to deallocate the data entirely.
@@ -90,8 +98,6 @@ impl<Value: CtoSafe> Drop for WeakCtoArc<Value>
 			{
 				fence(Acquire);
 				
-				let pool_pointer = cto_arc_inner.cto_pool_arc.pool_pointer();
-				
 				pool_pointer.free(ptr);
 			}
 		}
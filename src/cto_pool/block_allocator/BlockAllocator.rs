@@ -5,10 +5,14 @@
 /// Stored in Persistent Memory.
 /// Uses `#[repr(C)]` to prevent reordering of fields.
 /// Variable-sized data is stored after this struct, and so it can not be placed on the stack.
+/// `P` is the strategy used to persist block meta data and data copied into chains; it must suit the memory the block allocator is placed in.
+/// A block allocator created with `new_growable()` can later be grown with `grow()`, up to its `maximum_number_of_blocks`.
+/// Its free list is rebuilt from the persisted block meta data whenever its pool is opened, so that the process can be killed at any point without corrupting it.
 #[repr(C, align(4096))] // 4096 is the same as the `Self::Alignment` constant below - the value of `align(X)` (ie `X`) must be kept the same with the constant `Self::Alignment`.
-pub struct BlockAllocator
+pub struct BlockAllocator<P: Persistence>
 {
 	number_of_blocks: usize,
+	maximum_number_of_blocks: usize,
 	block_size: BlockSize,
 	
 	// Can be computed every time but stored for efficiency.
@@ -19,21 +23,64 @@ pub struct BlockAllocator
 	// A free list.
 	bags: Bags,
 	
-	// We store variable length Blocks at a Self::Alignment byte alignment after the BlockAllocator, ie immediately after the end.
+	persistence: PhantomData<P>,
 	
-	// We store variable length BlockMetaDataItems at a Self::Alignment byte alignment after the Blocks.
+	// We store variable length BlockMetaDataItems, enough for maximum_number_of_blocks, at a Self::Alignment byte alignment after the BlockAllocator, ie immediately after the end.
+	
+	// We store variable length Blocks at a Self::Alignment byte alignment after the BlockMetaDataItems; being last, they can grow.
 }
 
-impl CtoSafe for BlockAllocator
+impl<P: Persistence> CtoSafe for BlockAllocator<P>
 {
 	#[inline(always)]
-	fn cto_pool_opened(&mut self, cto_pool_arc: &CtoPoolArc)
+	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
+	{
+		// Only the first time, as it may be shared by several roots, each of which is opened as it is first used.
+		if self.register()
+		{
+			self.rebuild_bags()
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		cto_pool_relocation.relocate_non_null(&mut self.blocks_memory_inclusive_start_pointer);
+		cto_pool_relocation.relocate_non_null(&mut self.blocks_memory_exclusive_end_pointer);
+		cto_pool_relocation.relocate_non_null(&mut self.blocks_meta_data_items_inclusive_start_pointer);
+		
+		self.bags.cto_pool_relocate(cto_pool_relocation)
+	}
+	
+	// Checks the free chains in the bags; every block should be in at most one of them.
+	// The free blocks are then recorded, so that the used chains can be marked as they are found, and whatever is left is leaked.
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		if !self.has_consistent_layout()
+		{
+			cto_pool_traversal.record_finding(CtoPoolCheckFinding::BlockAllocatorLayoutInconsistent(self.address()));
+			return
+		}
+		
+		if !cto_pool_traversal.is_valid_pointer((self.blocks_memory_exclusive_end_pointer.as_ptr() as usize - 1) as *const u8)
+		{
+			return
+		}
+		
+		let mut free_blocks = vec![false; self.number_of_blocks];
+		self.bags.check(self.address(), self.block_meta_data_items(), &mut free_blocks, cto_pool_traversal);
+		cto_pool_traversal.found_block_allocator(self.address(), free_blocks, Self::recycle_leaked_blocks)
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
 	{
-		self.bags.cto_pool_opened(cto_pool_arc)
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::block_allocator::BlockAllocator")
 	}
 }
 
-impl BlockAllocator
+impl<P: Persistence> BlockAllocator<P>
 {
 	const Alignment: usize = 4096;
 	
@@ -45,7 +92,8 @@ impl BlockAllocator
 		// We assume a block size for the purposes of calculation that matches Self::Alignment.
 		// This means we can calculate without having to worry about alignment rounding-up rules.
 		
-		let struct_size = Self::offset_to_start_of_variable_length_memory();
+		// The BlockMetaDataItems are rounded up to Self::Alignment.
+		let struct_size = Self::offset_to_start_of_variable_length_memory() + Self::Alignment;
 		if memory_capacity_available_in_bytes <= struct_size
 		{
 			return 0;
@@ -65,48 +113,103 @@ impl BlockAllocator
 	/// Size of this object.
 	#[inline(always)]
 	pub fn size_of(number_of_blocks: usize, block_size: BlockSize) -> usize
+	{
+		Self::size_of_growable(number_of_blocks, number_of_blocks, block_size)
+	}
+	
+	/// Size of this object when it can grow to `maximum_number_of_blocks`; only the meta data for the blocks is reserved up front.
+	#[inline(always)]
+	pub fn size_of_growable(number_of_blocks: usize, maximum_number_of_blocks: usize, block_size: BlockSize) -> usize
 	{
 		let aligned_struct = Self::offset_to_start_of_variable_length_memory();
-		let blocks_capacity = Self::blocks_capacity(number_of_blocks, block_size).round_up_to_alignment(Self::Alignment);
-		let meta_data = BlockMetaDataItems::size_of(number_of_blocks);
-		aligned_struct + blocks_capacity + meta_data
+		let meta_data = Self::meta_data_capacity(maximum_number_of_blocks);
+		let blocks_capacity = Self::blocks_capacity(number_of_blocks, block_size);
+		aligned_struct + meta_data + blocks_capacity
 	}
 	
 	/// block_size is a minimum of 64 and could be 512 for systems with AVX512 CPU instructions.
 	pub fn new(unaligned_address: usize, number_of_blocks: usize, block_size: BlockSize) -> NonNull<Self>
+	{
+		Self::new_growable(unaligned_address, number_of_blocks, number_of_blocks, block_size)
+	}
+	
+	/// As `new()`, but the block allocator can later be grown with `grow()` to `maximum_number_of_blocks`.
+	/// The memory at `unaligned_address` need only be `size_of_growable()`.
+	pub fn new_growable(unaligned_address: usize, number_of_blocks: usize, maximum_number_of_blocks: usize, block_size: BlockSize) -> NonNull<Self>
 	{
 		let aligned_address = unaligned_address.round_up_to_alignment(Self::Alignment);
 		
 		let mut this = (aligned_address as *mut Self).to_non_null();
 		
-		this.mutable_reference().initialize(aligned_address, number_of_blocks, block_size);
+		this.mutable_reference().initialize(aligned_address, number_of_blocks, maximum_number_of_blocks, block_size);
+		this.reference().register();
 		
 		this
 	}
 	
+	/// Grows this block allocator by `additional_number_of_blocks` blocks, and adds them to the free list.
+	/// Can not grow beyond the `maximum_number_of_blocks` given to `new_growable()`.
+	///
+	/// This block allocator must be at the start of an allocation from the pool of `cto_pool_arc` (eg one made with an alignment of 4096 and a size of `size_of_growable()`).
+	/// That allocation is resized in place, which is usually only possible if it is the last allocation in the pool and the pool has grown (see `CtoPool::grow()`); if not possible, returns `PmdkError::OutOfSpace`.
+	pub fn grow(&mut self, cto_pool_arc: &CtoPoolArc, additional_number_of_blocks: usize) -> Result<(), PmdkError>
+	{
+		let old_number_of_blocks = self.number_of_blocks;
+		let new_number_of_blocks = old_number_of_blocks + additional_number_of_blocks;
+		assert!(new_number_of_blocks <= self.maximum_number_of_blocks, "number_of_blocks '{}' can not exceed maximum_number_of_blocks '{}'", new_number_of_blocks, self.maximum_number_of_blocks);
+		
+		if additional_number_of_blocks == 0
+		{
+			return Ok(())
+		}
+		
+		let new_size = Self::size_of_growable(new_number_of_blocks, self.maximum_number_of_blocks, self.block_size);
+		cto_pool_arc.pool_pointer().realloc_in_place(self as *mut Self as *mut c_void, new_size)?;
+		
+		let blocks_memory_exclusive_end_pointer = self.blocks_memory_inclusive_start_pointer.as_ptr() as usize + Self::blocks_capacity(new_number_of_blocks, self.block_size);
+		
+		unsafe { self.block_meta_data_items_mut().initialize(old_number_of_blocks, new_number_of_blocks) };
+		P::flush_memory(self.block_meta_data_items().get_unchecked_raw(old_number_of_blocks).as_ptr() as *mut c_void, BlockMetaDataItems::size_of(additional_number_of_blocks));
+		
+		self.number_of_blocks = new_number_of_blocks;
+		self.blocks_memory_exclusive_end_pointer = (blocks_memory_exclusive_end_pointer as *mut u8).to_non_null();
+		P::flush_struct(&self.number_of_blocks);
+		P::flush_struct(&self.blocks_memory_exclusive_end_pointer);
+		P::drain_memory();
+		
+		self.add_chains(old_number_of_blocks, new_number_of_blocks);
+		
+		self.register();
+		
+		Ok(())
+	}
+	
 	#[inline(always)]
-	fn initialize(&mut self, aligned_address: usize, number_of_blocks: usize, block_size: BlockSize)
+	fn initialize(&mut self, aligned_address: usize, number_of_blocks: usize, maximum_number_of_blocks: usize, block_size: BlockSize)
 	{
 		assert_ne!(number_of_blocks, 0, "number_of_blocks must not be zero");
-		assert!(number_of_blocks < BlockPointer::InclusiveMaximumNumberOfBlocks, "number_of_blocks '{}' can not exceed InclusiveMaximumNumberOfBlocks '{}'", number_of_blocks, BlockPointer::InclusiveMaximumNumberOfBlocks);
+		assert!(number_of_blocks <= maximum_number_of_blocks, "number_of_blocks '{}' can not exceed maximum_number_of_blocks '{}'", number_of_blocks, maximum_number_of_blocks);
+		assert!(maximum_number_of_blocks < BlockPointer::InclusiveMaximumNumberOfBlocks, "maximum_number_of_blocks '{}' can not exceed InclusiveMaximumNumberOfBlocks '{}'", maximum_number_of_blocks, BlockPointer::InclusiveMaximumNumberOfBlocks);
 		
-		let blocks_memory_inclusive_start_pointer = aligned_address + Self::offset_to_start_of_variable_length_memory();
+		let blocks_meta_data_items_inclusive_start_pointer = aligned_address + Self::offset_to_start_of_variable_length_memory();
+		let blocks_memory_inclusive_start_pointer = blocks_meta_data_items_inclusive_start_pointer + Self::meta_data_capacity(maximum_number_of_blocks);
 		let blocks_capacity = Self::blocks_capacity(number_of_blocks, block_size);
 		let blocks_memory_exclusive_end_pointer = blocks_memory_inclusive_start_pointer + blocks_capacity;
-		let blocks_meta_data_items_inclusive_start_pointer = blocks_memory_exclusive_end_pointer.round_up_to_alignment(Self::Alignment);
 		
 		unsafe
 		{
 			write(&mut self.number_of_blocks, number_of_blocks);
+			write(&mut self.maximum_number_of_blocks, maximum_number_of_blocks);
 			write(&mut self.block_size, block_size);
 			write(&mut self.blocks_memory_inclusive_start_pointer, (blocks_memory_inclusive_start_pointer as *mut u8).to_non_null());
 			write(&mut self.blocks_memory_exclusive_end_pointer, (blocks_memory_exclusive_end_pointer as *mut u8).to_non_null());
 			write(&mut self.blocks_meta_data_items_inclusive_start_pointer, (blocks_meta_data_items_inclusive_start_pointer as *mut BlockMetaDataItems).to_non_null());
 			write(&mut self.bags, Bags::default());
+			write(&mut self.persistence, PhantomData);
 			
-			self.block_meta_data_items_mut().initialize(number_of_blocks);
+			self.block_meta_data_items_mut().initialize(0, number_of_blocks);
 			
-			self.initialize_chains(number_of_blocks);
+			self.add_chains(0, number_of_blocks);
 		}
 	}
 	
@@ -124,7 +227,7 @@ impl BlockAllocator
 	}
 	
 	/// Allocate chains.
-	pub fn allocate_chains(&self, requested_size: usize, cto_pool_arc: &CtoPoolArc) -> Result<NonNull<Chains>, ()>
+	pub fn allocate_chains(&self, requested_size: usize, cto_pool_arc: &CtoPoolArc) -> Result<NonNull<Chains<P>>, ()>
 	{
 		let mut chains = Chains::new(self, cto_pool_arc)?;
 		
@@ -155,24 +258,141 @@ impl BlockAllocator
 			if next_chain.is_null()
 			{
 				// If this isn't done, then who knows what we might free in `drop()`.
-				previous_chain_block_meta_data.set_next_chain(BlockPointer::Null);
+				previous_chain_block_meta_data.set_next_chain::<P>(BlockPointer::Null);
 				unsafe { drop_in_place(chains.as_ptr()) };
 				
 				return Err(())
 			}
-			previous_chain_block_meta_data.set_next_chain(next_chain);
+			previous_chain_block_meta_data.set_next_chain::<P>(next_chain);
 			
 			previous_chain = next_chain;
 			number_of_blocks_remaining_to_find -= chain_length;
 		}
 		
-		self.block_meta_data_unchecked(previous_chain).set_next_chain(BlockPointer::Null);
+		self.block_meta_data_unchecked(previous_chain).set_next_chain::<P>(BlockPointer::Null);
 		
-		flush_non_null(chains);
+		P::persist_non_null(chains);
 		
 		Ok(chains)
 	}
 	
+	/// Grows the chain starting at `block_pointer` in place to `number_of_blocks`, by merging it with the free chains that follow it.
+	/// Returns false, leaving the chain as it was, if there are not enough free blocks immediately after it.
+	pub(crate) fn grow_chain_in_place(&self, block_pointer: BlockPointer, number_of_blocks: usize) -> bool
+	{
+		if number_of_blocks > InclusiveMaximumChainLength
+		{
+			return false
+		}
+		
+		let block_meta_data = self.block_meta_data_unchecked(block_pointer);
+		let original_chain_length = block_meta_data.chain_length();
+		
+		let mut chain_length = original_chain_length;
+		while chain_length.as_length() < number_of_blocks
+		{
+			let subsequent_chain_start_address = block_pointer.subsequent_chain_start_address(self.blocks_memory_inclusive_start_pointer, chain_length, self.block_size);
+			
+			if subsequent_chain_start_address.as_ptr() == self.blocks_memory_exclusive_end_pointer.as_ptr()
+			{
+				break
+			}
+			
+			let cut_chain_block_pointer = BlockPointer::block_address_to_block_pointer(self.blocks_memory_inclusive_start_pointer, subsequent_chain_start_address, self.block_size);
+			if !self.bags.try_to_cut::<P>(self.block_meta_data_items(), cut_chain_block_pointer)
+			{
+				break
+			}
+			
+			let cut_chain_block_meta_data = self.block_meta_data_unchecked(cut_chain_block_pointer);
+			let cut_chain_length = cut_chain_block_meta_data.chain_length();
+			match chain_length.add_if_maximum_length_not_exceeded(cut_chain_length)
+			{
+				None =>
+				{
+					cut_chain_block_meta_data.reset_before_add_to_bag();
+					self.bags.add::<P>(self.block_meta_data_items(), cut_chain_length, cut_chain_block_pointer);
+					break
+				},
+				
+				Some(combined_chain_length) => chain_length = combined_chain_length,
+			}
+			
+			block_meta_data.acquire::<P>(chain_length);
+		}
+		
+		if chain_length.as_length() < number_of_blocks
+		{
+			// Give back whatever was merged.
+			if chain_length != original_chain_length
+			{
+				block_meta_data.snap_off_back_if_longer_than_required_capacity_and_recycle_into_block_allocator(block_pointer, self.blocks_memory_inclusive_start_pointer, original_chain_length, self);
+			}
+			return false
+		}
+		
+		self.shrink_chain_in_place(block_pointer, number_of_blocks);
+		true
+	}
+	
+	/// Shrinks the chain starting at `block_pointer` in place to `number_of_blocks`, returning the blocks after it to the free list.
+	/// Does nothing if the chain is not longer than `number_of_blocks`.
+	pub(crate) fn shrink_chain_in_place(&self, block_pointer: BlockPointer, number_of_blocks: usize)
+	{
+		let block_meta_data = self.block_meta_data_unchecked(block_pointer);
+		let shorter_chain_length = ChainLength::from_length(number_of_blocks);
+		if block_meta_data.chain_length() > shorter_chain_length
+		{
+			block_meta_data.snap_off_back_if_longer_than_required_capacity_and_recycle_into_block_allocator(block_pointer, self.blocks_memory_inclusive_start_pointer, shorter_chain_length, self);
+		}
+	}
+	
+	/// A snapshot of how the blocks are used, taken from the free chains in the bags, per chain length.
+	pub fn statistics(&self) -> BlockAllocatorStatistics
+	{
+		BlockAllocatorStatistics
+		{
+			block_size: self.block_size.as_usize(),
+			number_of_blocks: self.number_of_blocks,
+			number_of_free_chains: self.bags.number_of_chains(),
+		}
+	}
+	
+	// The block meta data and blocks must follow this struct as laid out by `initialize()`.
+	#[inline(always)]
+	fn has_consistent_layout(&self) -> bool
+	{
+		let blocks_meta_data_items_inclusive_start_pointer = self.address() + Self::offset_to_start_of_variable_length_memory();
+		let blocks_memory_inclusive_start_pointer = blocks_meta_data_items_inclusive_start_pointer + Self::meta_data_capacity(self.maximum_number_of_blocks);
+		let blocks_memory_exclusive_end_pointer = blocks_memory_inclusive_start_pointer + Self::blocks_capacity(self.number_of_blocks, self.block_size);
+		
+		self.number_of_blocks != 0 && self.number_of_blocks <= self.maximum_number_of_blocks && self.maximum_number_of_blocks < BlockPointer::InclusiveMaximumNumberOfBlocks
+		&& self.blocks_meta_data_items_inclusive_start_pointer.as_ptr() as usize == blocks_meta_data_items_inclusive_start_pointer
+		&& self.blocks_memory_inclusive_start_pointer.as_ptr() as usize == blocks_memory_inclusive_start_pointer
+		&& self.blocks_memory_exclusive_end_pointer.as_ptr() as usize == blocks_memory_exclusive_end_pointer
+	}
+	
+	#[inline(always)]
+	fn address(&self) -> usize
+	{
+		self as *const Self as usize
+	}
+	
+	/// Registers (or re-registers, when the blocks' memory has moved or grown) this block allocator with the `CtoPoolRegistry`, if it is in an open pool.
+	/// Returns true if it had not been registered since the pool was opened.
+	#[inline(always)]
+	fn register(&self) -> bool
+	{
+		CtoPoolRegistry::register_block_allocator(self.address(), self.blocks_memory_inclusive_start_pointer.as_ptr() as usize, self.blocks_memory_exclusive_end_pointer.as_ptr() as usize, Self::type_name())
+	}
+	
+	/// Distinguishes block allocators with different persistence in the `CtoPoolRegistry`.
+	#[inline(always)]
+	pub(crate) fn type_name() -> &'static str
+	{
+		unsafe { type_name::<Self>() }
+	}
+	
 	#[inline(always)]
 	pub(crate) fn to_non_null(&self) -> NonNull<Self>
 	{
@@ -185,6 +405,12 @@ impl BlockAllocator
 		size_of::<Self>().round_up_to_alignment(Self::Alignment)
 	}
 	
+	#[inline(always)]
+	fn meta_data_capacity(maximum_number_of_blocks: usize) -> usize
+	{
+		BlockMetaDataItems::size_of(maximum_number_of_blocks).round_up_to_alignment(Self::Alignment)
+	}
+	
 	#[inline(always)]
 	fn blocks_capacity(number_of_blocks: usize, block_size: BlockSize) -> usize
 	{
@@ -203,14 +429,99 @@ impl BlockAllocator
 		self.blocks_meta_data_items_inclusive_start_pointer.mutable_reference()
 	}
 	
+	#[inline(always)]
+	fn block_pointer_for(&self, chain_address: *mut u8) -> BlockPointer
+	{
+		BlockPointer::block_address_to_block_pointer(self.blocks_memory_inclusive_start_pointer, chain_address.to_non_null(), self.block_size)
+	}
+	
+	#[inline(always)]
+	fn chain_address(&self, block_pointer: BlockPointer) -> *mut u8
+	{
+		block_pointer.expand_to_pointer_to_memory_unchecked(self.blocks_memory_inclusive_start_pointer, self.block_size).as_ptr()
+	}
+	
 	#[inline(always)]
 	fn block_meta_data_unchecked(&self, block_pointer: BlockPointer) -> &BlockMetaData
 	{
 		block_pointer.expand_to_pointer_to_meta_data_unchecked(self.block_meta_data_items())
 	}
 	
-	fn initialize_chains(&mut self, number_of_blocks: usize)
+	// Rebuilds the bags from the block meta data, as a crash (eg `kill -9`) part way through adding, removing, cutting or snapping off a chain can leave a bag's links broken or a chain half-merged.
+	// The blocks are walked as a sequence of chains, using the chain length recorded in the first block of each; a chain marked as being in a bag is free, and is merged with the free chains that follow it, up to `InclusiveMaximumChainLength`.
+	// Each merge is a single persisted store of a chain length, and free chains stay marked as being in a bag until they are re-added, so a crash whilst rebuilding leaves nothing for the next rebuild to lose.
+	// A chain not marked as being in a bag is in use; if it was being freed when the crash happened, it is leaked and can be reclaimed with `CtoPool::collect_leaks()`.
+	fn rebuild_bags(&mut self)
+	{
+		unsafe { write(&mut self.bags, Bags::default()) };
+		P::persist_struct(&self.bags);
+		
+		let number_of_blocks = self.number_of_blocks;
+		let block_meta_data_items = self.block_meta_data_items();
+		
+		// The first block, bag stripe index and length of the free chain being merged into.
+		let mut merging_into: Option<(usize, BagStripeIndex, usize)> = None;
+		
+		let mut block_index = 0;
+		while block_index < number_of_blocks
+		{
+			let chain_length_and_bag_stripe_index = block_meta_data_items.get_unchecked(block_index).chain_length_and_bag_stripe_index();
+			let length = min(chain_length_and_bag_stripe_index.chain_length().as_length(), number_of_blocks - block_index);
+			
+			merging_into = match (chain_length_and_bag_stripe_index.bag_stripe_index(), merging_into)
+			{
+				(None, None) => None,
+				
+				(None, Some((merge_into_block_index, _, merged_length))) =>
+				{
+					self.add_rebuilt_chain(merge_into_block_index, merged_length);
+					None
+				}
+				
+				(Some(bag_stripe_index), None) => Some((block_index, bag_stripe_index, length)),
+				
+				(Some(bag_stripe_index), Some((merge_into_block_index, merge_into_bag_stripe_index, merged_length))) =>
+				{
+					let combined_length = merged_length + length;
+					if combined_length <= InclusiveMaximumChainLength
+					{
+						block_meta_data_items.get_unchecked(merge_into_block_index).release::<P>(ChainLength::from_length(combined_length), merge_into_bag_stripe_index);
+						Some((merge_into_block_index, merge_into_bag_stripe_index, combined_length))
+					}
+					else
+					{
+						self.add_rebuilt_chain(merge_into_block_index, merged_length);
+						Some((block_index, bag_stripe_index, length))
+					}
+				}
+			};
+			
+			block_index += length;
+		}
+		
+		if let Some((merge_into_block_index, _, merged_length)) = merging_into
+		{
+			self.add_rebuilt_chain(merge_into_block_index, merged_length)
+		}
+	}
+	
+	#[inline(always)]
+	fn add_rebuilt_chain(&self, block_index: usize, length: usize)
+	{
+		self.bags.add_recovered::<P>(self.block_meta_data_items(), ChainLength::from_length(length), BlockPointer::new(block_index as u32))
+	}
+	
+	// Returns blocks that were found to be leaked to the bags; see `CtoPool::collect_leaks()`.
+	fn recycle_leaked_blocks(block_allocator_address: usize, inclusive_from_block_index: usize, exclusive_to_block_index: usize)
+	{
+		let this = unsafe { & * (block_allocator_address as *const Self) };
+		this.add_chains(inclusive_from_block_index, exclusive_to_block_index)
+	}
+	
+	// The blocks must not be in a bag; any `next`, `previous` or `next_chain` left over from a previous use is reset.
+	fn add_chains(&self, inclusive_from_block_index: usize, exclusive_to_block_index: usize)
 	{
+		let number_of_blocks = exclusive_to_block_index - inclusive_from_block_index;
 		let number_of_chains_of_maximum_length = number_of_blocks / InclusiveMaximumChainLength;
 		
 		let maximum_chain_length = ChainLength::from_length(InclusiveMaximumChainLength);
@@ -218,10 +529,11 @@ impl BlockAllocator
 		let mut chain_index = 0;
 		while chain_index < number_of_chains_of_maximum_length
 		{
-			let block_index = chain_index * InclusiveMaximumChainLength;
+			let block_index = inclusive_from_block_index + chain_index * InclusiveMaximumChainLength;
 			let add_block = BlockPointer::new(block_index as u32);
 			
-			self.bags.add(self.block_meta_data_items(), maximum_chain_length, add_block);
+			self.block_meta_data_unchecked(add_block).reset_before_add_to_bag();
+			self.bags.add::<P>(self.block_meta_data_items(), maximum_chain_length, add_block);
 			
 			chain_index += 1;
 		}
@@ -229,10 +541,11 @@ impl BlockAllocator
 		let odd_length_chain = number_of_blocks % InclusiveMaximumChainLength;
 		if odd_length_chain != 0
 		{
-			let block_index = number_of_blocks - odd_length_chain;
+			let block_index = exclusive_to_block_index - odd_length_chain;
 			let add_block = BlockPointer::new(block_index as u32);
 			
-			self.bags.add(self.block_meta_data_items(), ChainLength::from_length(odd_length_chain), add_block);
+			self.block_meta_data_unchecked(add_block).reset_before_add_to_bag();
+			self.bags.add::<P>(self.block_meta_data_items(), ChainLength::from_length(odd_length_chain), add_block);
 		}
 	}
 	
@@ -255,7 +568,7 @@ impl BlockAllocator
 			}
 			
 			let cut_chain_block_pointer = BlockPointer::block_address_to_block_pointer(self.blocks_memory_inclusive_start_pointer, subsequent_chain_start_address, self.block_size);
-			if self.bags.try_to_cut(self.block_meta_data_items(), cut_chain_block_pointer)
+			if self.bags.try_to_cut::<P>(self.block_meta_data_items(), cut_chain_block_pointer)
 			{
 				let cut_chain_block_meta_data = self.block_meta_data_unchecked(cut_chain_block_pointer);
 				
@@ -267,14 +580,14 @@ impl BlockAllocator
 					None =>
 					{
 						cut_chain_block_meta_data.reset_before_add_to_bag();
-						self.bags.add(self.block_meta_data_items(), cut_chain_length, cut_chain_block_pointer);
+						self.bags.add::<P>(self.block_meta_data_items(), cut_chain_length, cut_chain_block_pointer);
 						break
 					},
 					
 					Some(combined_chain_length) => solitary_chain_length = combined_chain_length,
 				}
 				
-				solitary_chain_block_meta_data.acquire(solitary_chain_length);
+				solitary_chain_block_meta_data.acquire::<P>(solitary_chain_length);
 			}
 			else
 			{
@@ -290,7 +603,7 @@ impl BlockAllocator
 	fn nothing_to_merge_with_so_add_to_free_list(&self, solitary_chain_block_pointer: BlockPointer, solitary_chain_block_meta_data: &BlockMetaData, solitary_chain_length: ChainLength)
 	{
 		solitary_chain_block_meta_data.reset_before_add_to_bag();
-		self.bags.add(self.block_meta_data_items(), solitary_chain_length, solitary_chain_block_pointer)
+		self.bags.add::<P>(self.block_meta_data_items(), solitary_chain_length, solitary_chain_block_pointer)
 	}
 	
 	#[inline(always)]
@@ -304,7 +617,7 @@ impl BlockAllocator
 		while search_for_chain_length <= InclusiveMaximumChainLength
 		{
 			let our_shorter_chain_length = ChainLength::from_length(search_for_chain_length);
-			let chain = self.bags.remove(self.block_meta_data_items(), our_shorter_chain_length);
+			let chain = self.bags.remove::<P>(self.block_meta_data_items(), our_shorter_chain_length);
 			if chain.is_not_null()
 			{
 				if search_for_chain_length != capped_chain_length
@@ -321,7 +634,7 @@ impl BlockAllocator
 		let mut search_for_chain_length = capped_chain_length;
 		while search_for_chain_length > 0
 		{
-			let chain = self.bags.remove(self.block_meta_data_items(), ChainLength::from_length(search_for_chain_length));
+			let chain = self.bags.remove::<P>(self.block_meta_data_items(), ChainLength::from_length(search_for_chain_length));
 			if chain.is_not_null()
 			{
 				return (chain, search_for_chain_length)
@@ -347,7 +660,7 @@ impl BlockAllocator
 		while search_for_chain_length <= InclusiveMaximumChainLength
 		{
 			let our_shorter_chain_length = ChainLength::from_length(search_for_chain_length);
-			let chain = self.bags.remove(self.block_meta_data_items(), our_shorter_chain_length);
+			let chain = self.bags.remove::<P>(self.block_meta_data_items(), our_shorter_chain_length);
 			if chain.is_not_null()
 			{
 				if search_for_chain_length != number_of_blocks
@@ -0,0 +1,207 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A Rust `Alloc` allocator over a `BlockAllocator`, to be used with `RawVec` and other collection objects.
+/// Each allocation is a single chain, so it can be no larger than `InclusiveMaximumChainLength` (usually 1024) blocks nor aligned to more than the block size; like `BlockAllocator::allocate_chain()`, allocation is lock-free.
+/// Growing in place merges an allocation with the free chains that follow it.
+/// The chains handed out are not recorded, so `CtoPool::collect_leaks()` refuses to run on a pool in which one of these is reachable.
+pub struct BlockAllocatorAlloc<P: Persistence>
+{
+	cto_pool_arc: CtoPoolArc,
+	block_allocator: NonNull<BlockAllocator<P>>,
+}
+
+impl<P: Persistence> CtoSafe for BlockAllocatorAlloc<P>
+{
+	#[inline(always)]
+	fn cto_pool_opened(&mut self, cto_pool_arc: &CtoPoolArc)
+	{
+		cto_pool_arc.write(&mut self.cto_pool_arc);
+		self.block_allocator_mut().cto_pool_opened(cto_pool_arc)
+	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		cto_pool_relocation.relocate_non_null(&mut self.block_allocator);
+		if cto_pool_relocation.is_first_visit(self.block_allocator.as_ptr())
+		{
+			self.block_allocator_mut().cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		cto_pool_traversal.traverse(self.block_allocator.as_ptr() as *const BlockAllocator<P>);
+		cto_pool_traversal.found_unwalked_chains(self.block_allocator.as_ptr() as usize)
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::block_allocator::BlockAllocatorAlloc")
+	}
+}
+
+impl<P: Persistence> Clone for BlockAllocatorAlloc<P>
+{
+	#[inline(always)]
+	fn clone(&self) -> Self
+	{
+		Self
+		{
+			cto_pool_arc: self.cto_pool_arc.clone(),
+			block_allocator: self.block_allocator,
+		}
+	}
+}
+
+impl<P: Persistence> PartialEq for BlockAllocatorAlloc<P>
+{
+	#[inline(always)]
+	fn eq(&self, other: &Self) -> bool
+	{
+		self.block_allocator == other.block_allocator
+	}
+}
+
+impl<P: Persistence> Eq for BlockAllocatorAlloc<P>
+{
+}
+
+impl<P: Persistence> Debug for BlockAllocatorAlloc<P>
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		f.write_str(&format!("BlockAllocatorAlloc({:?})", self.block_allocator))
+	}
+}
+
+unsafe impl<P: Persistence> Send for BlockAllocatorAlloc<P>
+{
+}
+
+unsafe impl<P: Persistence> Sync for BlockAllocatorAlloc<P>
+{
+}
+
+unsafe impl<P: Persistence> Alloc for BlockAllocatorAlloc<P>
+{
+	#[inline(always)]
+	unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr>
+	{
+		self.allocate(&layout).map(|(allocation_pointer, _capacity)| allocation_pointer)
+	}
+	
+	#[inline(always)]
+	unsafe fn dealloc(&mut self, ptr: *mut u8, _layout: Layout)
+	{
+		let block_allocator = self.block_allocator();
+		block_allocator.receive_solitary_chain_back(block_allocator.block_pointer_for(ptr))
+	}
+	
+	#[inline(always)]
+	fn usable_size(&self, layout: &Layout) -> (usize, usize)
+	{
+		let block_size = self.block_allocator().block_size;
+		let size = layout.size();
+		(size, block_size.size_of_chain_in_bytes(block_size.number_of_blocks_required(size)))
+	}
+	
+	#[inline(always)]
+	unsafe fn alloc_excess(&mut self, layout: Layout) -> Result<Excess, AllocErr>
+	{
+		self.allocate(&layout).map(|(allocation_pointer, capacity)| Excess(allocation_pointer, capacity))
+	}
+	
+	#[inline(always)]
+	unsafe fn grow_in_place(&mut self, ptr: *mut u8, _old_layout: Layout, new_layout: Layout) -> Result<(), CannotReallocInPlace>
+	{
+		let block_allocator = self.block_allocator();
+		let block_size = block_allocator.block_size;
+		if new_layout.align() > block_size.as_usize()
+		{
+			return Err(CannotReallocInPlace)
+		}
+		
+		if block_allocator.grow_chain_in_place(block_allocator.block_pointer_for(ptr), block_size.number_of_blocks_required(new_layout.size()))
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(CannotReallocInPlace)
+		}
+	}
+	
+	#[inline(always)]
+	unsafe fn shrink_in_place(&mut self, ptr: *mut u8, _old_layout: Layout, new_layout: Layout) -> Result<(), CannotReallocInPlace>
+	{
+		let block_allocator = self.block_allocator();
+		let number_of_blocks = block_allocator.block_size.number_of_blocks_required(new_layout.size());
+		if number_of_blocks == 0
+		{
+			return Err(CannotReallocInPlace)
+		}
+		
+		block_allocator.shrink_chain_in_place(block_allocator.block_pointer_for(ptr), number_of_blocks);
+		Ok(())
+	}
+}
+
+impl<P: Persistence> BlockAllocatorAlloc<P>
+{
+	/// Creates a new allocator over `block_allocator`, which must be in the pool of `cto_pool_arc` (or in volatile memory that outlives the allocator).
+	#[inline(always)]
+	pub fn new(block_allocator: &BlockAllocator<P>, cto_pool_arc: &CtoPoolArc) -> Self
+	{
+		Self
+		{
+			cto_pool_arc: cto_pool_arc.clone(),
+			block_allocator: block_allocator.to_non_null(),
+		}
+	}
+	
+	// Returns the allocation and its capacity in bytes.
+	#[inline(always)]
+	fn allocate(&self, layout: &Layout) -> Result<(*mut u8, usize), AllocErr>
+	{
+		let block_allocator = self.block_allocator();
+		let block_size = block_allocator.block_size;
+		if layout.align() > block_size.as_usize()
+		{
+			return Err(AllocErr::Unsupported { details: "alignment is greater than the block size" })
+		}
+		
+		let number_of_blocks = block_size.number_of_blocks_required(layout.size());
+		if number_of_blocks > InclusiveMaximumChainLength
+		{
+			return Err(AllocErr::Unsupported { details: "allocation is larger than the maximum chain length" })
+		}
+		
+		let (chain, chain_length) = block_allocator.allocate_chain(layout.size());
+		if chain.is_null()
+		{
+			Err(AllocErr::Exhausted { request: layout.clone() })
+		}
+		else
+		{
+			Ok((block_allocator.chain_address(chain), block_size.size_of_chain_in_bytes(chain_length)))
+		}
+	}
+	
+	#[inline(always)]
+	fn block_allocator(&self) -> &BlockAllocator<P>
+	{
+		self.block_allocator.reference()
+	}
+	
+	#[inline(always)]
+	fn block_allocator_mut(&mut self) -> &mut BlockAllocator<P>
+	{
+		self.block_allocator.mutable_reference()
+	}
+}
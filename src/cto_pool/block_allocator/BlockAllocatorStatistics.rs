@@ -0,0 +1,69 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A snapshot of how the blocks in a block allocator are used, taken from its free lists (bags); see `BlockAllocator::statistics()`.
+/// Sizes are in bytes.
+/// The number of allocations is not known, as an allocation of `Chains` can be of many chains, and so is not reported.
+/// Whilst other threads are allocating, the counts are approximate.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct BlockAllocatorStatistics
+{
+	/// Size of a block.
+	pub block_size: usize,
+	
+	/// Number of blocks.
+	pub number_of_blocks: usize,
+	
+	/// Number of free chains of each chain length; index 0 is for chains one block long, and the last index is for chains of the maximum chain length.
+	pub number_of_free_chains: Vec<usize>,
+}
+
+impl BlockAllocatorStatistics
+{
+	/// Size of all blocks.
+	#[inline(always)]
+	pub fn total_size(&self) -> usize
+	{
+		self.number_of_blocks * self.block_size
+	}
+	
+	/// Bytes used by allocations.
+	#[inline(always)]
+	pub fn allocated_size(&self) -> usize
+	{
+		self.total_size().saturating_sub(self.free_size())
+	}
+	
+	/// Bytes available for allocations.
+	#[inline(always)]
+	pub fn free_size(&self) -> usize
+	{
+		self.number_of_free_blocks() * self.block_size
+	}
+	
+	/// Number of free blocks.
+	#[inline(always)]
+	pub fn number_of_free_blocks(&self) -> usize
+	{
+		self.number_of_free_chains.iter().enumerate().fold(0, |number_of_free_blocks, (index, number_of_free_chains)| number_of_free_blocks + (index + 1) * number_of_free_chains)
+	}
+	
+	/// Size of the longest free chain.
+	#[inline(always)]
+	pub fn largest_free_extent(&self) -> usize
+	{
+		match self.number_of_free_chains.iter().rposition(|number_of_free_chains| *number_of_free_chains != 0)
+		{
+			None => 0,
+			Some(index) => (index + 1) * self.block_size,
+		}
+	}
+	
+	/// Fragmentation ratio, from 0.0 (all free blocks are in one chain, or there are no free blocks) tending towards 1.0 (free blocks are split into many short chains).
+	#[inline(always)]
+	pub fn fragmentation(&self) -> f64
+	{
+		CtoPoolStatistics::fragmentation_ratio(self.free_size(), self.largest_free_extent())
+	}
+}
@@ -33,7 +33,7 @@ impl BlockMetaData
 {
 	// Part of Drop logic for Chains struct.
 	#[inline(always)]
-	pub(crate) fn recycle_chains_into_block_allocator(&self, block_allocator: &BlockAllocator, our_block_pointer: BlockPointer)
+	pub(crate) fn recycle_chains_into_block_allocator<P: Persistence>(&self, block_allocator: &BlockAllocator<P>, our_block_pointer: BlockPointer)
 	{
 		let next_chain = self.get_next_chain();
 		if next_chain.is_not_null()
@@ -51,18 +51,21 @@ impl BlockMetaData
 	// For the snapped off chain, we know it (inclusive) start address and exclusive end address. The exclusive end address might be the start of another chain.
 	// NOTE: On entry, this chain MUST have been 'taken', ie the memory must be in use, ie we have 'locked' it for use by us.
 	#[inline(always)]
-	pub(crate) fn snap_off_back_if_longer_than_required_capacity_and_recycle_into_block_allocator(&self, our_block_pointer: BlockPointer, memory_base_pointer: NonNull<u8>, our_shorter_chain_length: ChainLength, block_allocator: &BlockAllocator)
+	pub(crate) fn snap_off_back_if_longer_than_required_capacity_and_recycle_into_block_allocator<P: Persistence>(&self, our_block_pointer: BlockPointer, memory_base_pointer: NonNull<u8>, our_shorter_chain_length: ChainLength, block_allocator: &BlockAllocator<P>)
 	{
 		let our_chain_length = self.chain_length();
 		
 		debug_assert!(&our_chain_length > &our_shorter_chain_length, "our_shorter_chain_length '{:?}' is equal to or greater than self.chain_length() '{:?}'", our_shorter_chain_length, our_chain_length);
 		
 		let snapped_off_chain_length = our_chain_length.subtract(our_shorter_chain_length);
-		self.acquire(our_shorter_chain_length);
 		
+		// The snapped off chain's length is persisted before ours is shortened, so that a crash can never leave the chains unwalkable; see `BlockAllocator::rebuild_bags()`.
 		let snapped_off_chain_block_pointer = BlockPointer::block_address_to_block_pointer(memory_base_pointer, our_block_pointer.subsequent_chain_start_address(memory_base_pointer, our_shorter_chain_length, block_allocator.block_size), block_allocator.block_size);
 		let snapped_off_chain_block_meta_data = block_allocator.block_meta_data_unchecked(snapped_off_chain_block_pointer);
-		snapped_off_chain_block_meta_data.acquire(snapped_off_chain_length);
+		snapped_off_chain_block_meta_data.acquire::<P>(snapped_off_chain_length);
+		
+		self.acquire::<P>(our_shorter_chain_length);
+		
 		block_allocator.receive_solitary_chain_back(snapped_off_chain_block_pointer);
 	}
 	
@@ -71,6 +74,13 @@ impl BlockMetaData
 	{
 		debug_assert!(self.chain_length_and_bag_stripe_index().bag_stripe_index().is_none(), "can not ask for reset_before_add_to_bag when in a bag");
 		
+		self.reset_links()
+	}
+	
+	// Also used when rebuilding the bags, when this may still be marked as being in a bag.
+	#[inline(always)]
+	fn reset_links(&self)
+	{
 		self.next.set_relaxed(BlockPointer::Null);
 		self.previous.set_relaxed(BlockPointer::Null);
 		self.next_chain.set(BlockPointer::Null);
@@ -88,11 +98,11 @@ impl BlockMetaData
 	// Valid only if not in a bag.
 	// Is permitted to accept a null BlockPointer.
 	#[inline(always)]
-	fn set_next_chain(&self, new_next_chain: BlockPointer)
+	fn set_next_chain<P: Persistence>(&self, new_next_chain: BlockPointer)
 	{
 		debug_assert!(self.chain_length_and_bag_stripe_index().bag_stripe_index().is_none(), "can not ask for next_chain when in a bag");
 		self.next_chain.set(new_next_chain);
-		self.persist()
+		self.persist::<P>()
 	}
 	
 	// Valid only if not in a bag.
@@ -113,17 +123,17 @@ impl BlockMetaData
 	}
 	
 	#[inline(always)]
-	fn release(&self, chain_length: ChainLength, next_bag_stripe_index: BagStripeIndex)
+	fn release<P: Persistence>(&self, chain_length: ChainLength, next_bag_stripe_index: BagStripeIndex)
 	{
 		self.chain_length_and_bag_stripe_index.set(ChainLengthAndBagStripeIndex::new(chain_length, Some(next_bag_stripe_index)));
-		self.persist()
+		self.persist::<P>()
 	}
 	
 	#[inline(always)]
-	fn acquire(&self, chain_length: ChainLength)
+	fn acquire<P: Persistence>(&self, chain_length: ChainLength)
 	{
 		self.chain_length_and_bag_stripe_index.set(ChainLengthAndBagStripeIndex::new(chain_length, None));
-		self.persist()
+		self.persist::<P>()
 	}
 	
 	#[inline(always)]
@@ -151,8 +161,8 @@ impl BlockMetaData
 	}
 	
 	#[inline(always)]
-	fn persist(&self)
+	fn persist<P: Persistence>(&self)
 	{
-		flush_struct(self);
+		P::persist_struct(self)
 	}
 }
@@ -14,10 +14,10 @@ impl BlockMetaDataItems
 	}
 	
 	#[inline(always)]
-	unsafe fn initialize(&mut self, number_of_blocks: usize)
+	unsafe fn initialize(&mut self, inclusive_from_block_index: usize, exclusive_to_block_index: usize)
 	{
-		let mut index = 0;
-		while index < number_of_blocks
+		let mut index = inclusive_from_block_index;
+		while index < exclusive_to_block_index
 		{
 			let mut block_meta_data_borrow_check_hack = self.get(index);
 			let block_meta_data = block_meta_data_borrow_check_hack.as_mut();
@@ -3,14 +3,14 @@
 
 
 /// Stored in Persistent Memory
-pub struct Chains
+pub struct Chains<P: Persistence>
 {
 	cto_pool_arc: CtoPoolArc,
-	block_allocator: NonNull<BlockAllocator>,
+	block_allocator: NonNull<BlockAllocator<P>>,
 	head_of_chains_linked_list: BlockPointer,
 }
 
-impl Drop for Chains
+impl<P: Persistence> Drop for Chains<P>
 {
 	#[inline(always)]
 	fn drop(&mut self)
@@ -25,19 +25,96 @@ impl Drop for Chains
 	}
 }
 
-impl CtoSafe for Chains
+impl<P: Persistence> CtoSafe for Chains<P>
 {
 	#[inline(always)]
 	fn cto_pool_opened(&mut self, cto_pool_arc: &CtoPoolArc)
 	{
 		self.block_allocator_mut().cto_pool_opened(cto_pool_arc)
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		cto_pool_relocation.relocate_non_null(&mut self.block_allocator);
+		if cto_pool_relocation.is_first_visit(self.block_allocator.as_ptr())
+		{
+			self.block_allocator_mut().cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	// Each chain must not be in a bag, must not run past the last block, and must not overlap a free chain or another used chain.
+	// Inconsistencies in the block allocator itself are found when it is traversed.
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		let block_allocator = self.block_allocator.as_ptr();
+		if !cto_pool_traversal.is_valid_pointer(block_allocator)
+		{
+			return
+		}
+		
+		if cto_pool_traversal.is_first_visit(block_allocator)
+		{
+			self.block_allocator().cto_pool_traverse(cto_pool_traversal)
+		}
+		
+		let block_allocator = self.block_allocator();
+		if !block_allocator.has_consistent_layout()
+		{
+			return
+		}
+		
+		let number_of_blocks = block_allocator.number_of_blocks;
+		let mut number_of_chains = 0;
+		let mut chain = self.head_of_chains_linked_list;
+		while chain.is_not_null()
+		{
+			let block_index = chain.0 as usize;
+			
+			// More chains than blocks means the chains form a cycle.
+			if block_index >= number_of_blocks || number_of_chains == number_of_blocks
+			{
+				cto_pool_traversal.record_finding(CtoPoolCheckFinding::BlockAllocatorChainLengthInconsistent(block_allocator.address(), block_index, 0));
+				return
+			}
+			
+			let block_meta_data = block_allocator.block_meta_data_unchecked(chain);
+			if !cto_pool_traversal.is_valid_pointer(block_meta_data as *const BlockMetaData)
+			{
+				return
+			}
+			
+			let chain_length_and_bag_stripe_index = block_meta_data.chain_length_and_bag_stripe_index();
+			let length = chain_length_and_bag_stripe_index.chain_length().as_length();
+			if chain_length_and_bag_stripe_index.bag_stripe_index().is_some() || length > number_of_blocks - block_index
+			{
+				cto_pool_traversal.record_finding(CtoPoolCheckFinding::BlockAllocatorChainLengthInconsistent(block_allocator.address(), block_index, length));
+				return
+			}
+			
+			if !cto_pool_traversal.found_used_blocks(block_allocator.address(), block_index, length)
+			{
+				cto_pool_traversal.record_finding(CtoPoolCheckFinding::BlockAllocatorChainLengthInconsistent(block_allocator.address(), block_index, length));
+				return
+			}
+			
+			number_of_chains += 1;
+			chain = block_meta_data.get_next_chain();
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::block_allocator::Chains")
+	}
 }
 
-impl Chains
+impl<P: Persistence> Chains<P>
 {
 	#[inline(always)]
-	fn new(block_allocator: &BlockAllocator, cto_pool_arc: &CtoPoolArc) -> Result<NonNull<Self>, ()>
+	fn new(block_allocator: &BlockAllocator<P>, cto_pool_arc: &CtoPoolArc) -> Result<NonNull<Self>, ()>
 	{
 		match cto_pool_arc.pool_pointer().aligned_alloc(size_of::<Self>(), size_of::<Self>())
 		{
@@ -54,7 +131,7 @@ impl Chains
 	}
 	
 	#[inline(always)]
-	fn initialize(&mut self, block_allocator: NonNull<BlockAllocator>, cto_pool_arc: &CtoPoolArc)
+	fn initialize(&mut self, block_allocator: NonNull<BlockAllocator<P>>, cto_pool_arc: &CtoPoolArc)
 	{
 		unsafe
 		{
@@ -65,20 +142,20 @@ impl Chains
 	}
 	
 	#[inline(always)]
-	fn block_allocator(&self) -> &BlockAllocator
+	fn block_allocator(&self) -> &BlockAllocator<P>
 	{
 		self.block_allocator.reference()
 	}
 	
 	#[inline(always)]
-	fn block_allocator_mut(&mut self) -> &mut BlockAllocator
+	fn block_allocator_mut(&mut self) -> &mut BlockAllocator<P>
 	{
 		self.block_allocator.mutable_reference()
 	}
 	
 	/// Copy bytes into chains.
 	#[inline(always)]
-	pub fn copy_bytes_into_chains_start<'block_meta_data>(&'block_meta_data self) -> RestartCopyIntoAt<'block_meta_data>
+	pub fn copy_bytes_into_chains_start<'block_meta_data>(&'block_meta_data self) -> RestartCopyIntoAt<'block_meta_data, P>
 	{
 		let block_allocator = self.block_allocator();
 		RestartCopyIntoAt::new(block_allocator.block_size, block_allocator.blocks_memory_inclusive_start_pointer, self.head_of_chains_linked_list, &block_allocator.block_meta_data_items())
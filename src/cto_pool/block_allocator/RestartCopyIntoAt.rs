@@ -4,14 +4,15 @@
 
 /// Stored in Volatile Memory
 #[derive(Debug)]
-pub struct RestartCopyIntoAt<'block_meta_data>
+pub struct RestartCopyIntoAt<'block_meta_data, P: Persistence>
 {
 	chain: Chain,
 	offset: usize,
 	block_meta_data_items: &'block_meta_data BlockMetaDataItems,
+	persistence: PhantomData<P>,
 }
 
-impl<'block_meta_data> RestartCopyIntoAt<'block_meta_data>
+impl<'block_meta_data, P: Persistence> RestartCopyIntoAt<'block_meta_data, P>
 {
 	/// head_of_chains_linked_list can be null; any copy must then only be for zero bytes.
 	#[inline(always)]
@@ -28,6 +29,7 @@ impl<'block_meta_data> RestartCopyIntoAt<'block_meta_data>
 			},
 			offset: 0,
 			block_meta_data_items,
+			persistence: PhantomData,
 		}
 	}
 	
@@ -73,13 +75,13 @@ impl<'block_meta_data> RestartCopyIntoAt<'block_meta_data>
 	fn _copy_bytes_into_chains_inner(&mut self, copy_from_address: NonNull<u8>, copy_from_capacity: usize, copy_into_chain_address: NonNull<u8>, remaining_capacity: usize, offset: usize)
 	{
 		#[inline(always)]
-		fn copy_and_flush_persistent_memory(copy_from_address: NonNull<u8>, copy_into_chain_address: NonNull<u8>, capacity: usize)
+		fn copy_and_flush_persistent_memory<P: Persistence>(copy_from_address: NonNull<u8>, copy_into_chain_address: NonNull<u8>, capacity: usize)
 		{
 			unsafe { copy_nonoverlapping(copy_from_address.as_ptr() as *const _, copy_into_chain_address.as_ptr(), capacity) };
-			flush_memory(copy_into_chain_address.as_ptr() as *mut c_void, capacity);
+			P::persist_memory(copy_into_chain_address.as_ptr() as *mut c_void, capacity);
 		}
 		
-		copy_and_flush_persistent_memory(copy_from_address, copy_into_chain_address, copy_from_capacity);
+		copy_and_flush_persistent_memory::<P>(copy_from_address, copy_into_chain_address, copy_from_capacity);
 		
 		if copy_from_capacity <= remaining_capacity
 		{
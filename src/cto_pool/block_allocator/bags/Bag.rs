@@ -44,13 +44,34 @@ impl CtoSafe for Bag
 			bag_stripe.cto_pool_opened(cto_pool_arc)
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		for bag_stripe in self.bag_stripe_array.iter_mut()
+		{
+			bag_stripe.cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	// Walked by `BlockAllocator::cto_pool_traverse()` with `Bags::check()`, which needs to know the block allocator.
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::block_allocator::bags::Bag")
+	}
 }
 
 impl Bag
 {
 	// add tries to ensure a round-robin, uniform distribution amongst stripes.
 	#[inline(always)]
-	pub(crate) fn add(&self, chain_length: ChainLength, add_block: BlockPointer, block_meta_data_items: &BlockMetaDataItems)
+	pub(crate) fn add<P: Persistence>(&self, chain_length: ChainLength, add_block: BlockPointer, block_meta_data_items: &BlockMetaDataItems)
 	{
 		debug_assert!(add_block.is_not_null(), "add_block can not be null");
 		
@@ -63,12 +84,27 @@ impl Bag
 		let next_bag_stripe_index = self.obtain_next_bag_stripe_index();
 		let bag_stripe = next_bag_stripe_index.get_bag_stripe(&self.bag_stripe_array);
 		
-		bag_stripe.add(chain_length, add_block, block_meta_data_items, add_block_meta_data, next_bag_stripe_index)
+		bag_stripe.add::<P>(chain_length, add_block, block_meta_data_items, add_block_meta_data, next_bag_stripe_index)
+	}
+	
+	// As `add()`, but `add_block` may still be marked as being in a bag stripe, and its links are reset; it stays marked until it is added, so that a crash whilst rebuilding the bags does not lose it.
+	#[inline(always)]
+	pub(crate) fn add_recovered<P: Persistence>(&self, chain_length: ChainLength, add_block: BlockPointer, block_meta_data_items: &BlockMetaDataItems)
+	{
+		debug_assert!(add_block.is_not_null(), "add_block can not be null");
+		
+		let add_block_meta_data = add_block.expand_to_pointer_to_meta_data_unchecked(block_meta_data_items);
+		add_block_meta_data.reset_links();
+		
+		let next_bag_stripe_index = self.obtain_next_bag_stripe_index();
+		let bag_stripe = next_bag_stripe_index.get_bag_stripe(&self.bag_stripe_array);
+		
+		bag_stripe.add_unchecked::<P>(chain_length, add_block, block_meta_data_items, add_block_meta_data, next_bag_stripe_index)
 	}
 	
 	// remove tries to ensure a round-robin, uniform distribution amongst stripes by always trying to remove from the oldest added to stripe.
 	#[inline(always)]
-	pub(crate) fn remove(&self, chain_length: ChainLength, block_meta_data_items: &BlockMetaDataItems) -> BlockPointer
+	pub(crate) fn remove<P: Persistence>(&self, chain_length: ChainLength, block_meta_data_items: &BlockMetaDataItems) -> BlockPointer
 	{
 		let mut added_count = self.number_of_blocks_added_over_all_time();
 		let mut removed_count = self.number_of_blocks_removed_over_all_time();
@@ -95,7 +131,7 @@ impl Bag
 				
 				let bag_stripe = bag_stripe_index.get_bag_stripe(&self.bag_stripe_array);
 				
-				let removed = bag_stripe.remove(chain_length, block_meta_data_items);
+				let removed = bag_stripe.remove::<P>(chain_length, block_meta_data_items);
 				if removed.is_not_null()
 				{
 					self.increment_number_of_blocks_removed_over_all_time();
@@ -115,10 +151,40 @@ impl Bag
 	}
 	
 	#[inline(always)]
-	pub(crate) fn try_to_cut(&self, chain_length: ChainLength, probably_in_bag_block: BlockPointer, probably_in_bag_block_meta_data: &BlockMetaData, block_meta_data_items: &BlockMetaDataItems, bag_stripe_index: BagStripeIndex) -> bool
+	pub(crate) fn try_to_cut<P: Persistence>(&self, chain_length: ChainLength, probably_in_bag_block: BlockPointer, probably_in_bag_block_meta_data: &BlockMetaData, block_meta_data_items: &BlockMetaDataItems, bag_stripe_index: BagStripeIndex) -> bool
 	{
 		let bag_stripe = bag_stripe_index.get_bag_stripe(&self.bag_stripe_array);
-		bag_stripe.try_to_cut(chain_length, probably_in_bag_block, probably_in_bag_block_meta_data, block_meta_data_items)
+		let cut = bag_stripe.try_to_cut::<P>(chain_length, probably_in_bag_block, probably_in_bag_block_meta_data, block_meta_data_items);
+		if cut
+		{
+			self.increment_number_of_blocks_removed_over_all_time();
+		}
+		cut
+	}
+	
+	#[inline(always)]
+	pub(crate) fn check(&self, chain_length: ChainLength, block_allocator_address: usize, block_meta_data_items: &BlockMetaDataItems, free_blocks: &mut [bool], cto_pool_traversal: &CtoPoolTraversal)
+	{
+		let mut found = 0;
+		for (bag_stripe_index, bag_stripe) in self.bag_stripe_array.iter().enumerate()
+		{
+			found += bag_stripe.check(chain_length, bag_stripe_index, block_allocator_address, block_meta_data_items, free_blocks, cto_pool_traversal)
+		}
+		
+		let recorded = self.number_of_chains();
+		if recorded != found
+		{
+			cto_pool_traversal.record_finding(CtoPoolCheckFinding::BlockAllocatorBagCountMismatch(block_allocator_address, chain_length.as_length(), recorded, found))
+		}
+	}
+	
+	/// Approximate if other threads are adding or removing.
+	#[inline(always)]
+	pub(crate) fn number_of_chains(&self) -> usize
+	{
+		let removed_count = self.number_of_blocks_removed_over_all_time();
+		let added_count = self.number_of_blocks_added_over_all_time();
+		added_count.saturating_sub(removed_count) as usize
 	}
 	
 	#[inline(always)]
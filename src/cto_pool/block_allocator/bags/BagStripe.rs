@@ -30,18 +30,43 @@ impl CtoSafe for BagStripe
 	{
 		self.spin_lock.forcibly_unlock_spin_lock()
 	}
+	
+	// Block pointers are offsets relative to the start of the blocks memory, and so do not need relocating.
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	// Walked by `BlockAllocator::cto_pool_traverse()` with `Bags::check()`, which needs to know the block allocator.
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::block_allocator::bags::BagStripe")
+	}
 }
 
 impl BagStripe
 {
 	#[inline(always)]
-	fn add(&self, chain_length: ChainLength, add_block: BlockPointer, block_meta_data_items: &BlockMetaDataItems, add_block_meta_data: &BlockMetaData, next_bag_stripe_index: BagStripeIndex)
+	fn add<P: Persistence>(&self, chain_length: ChainLength, add_block: BlockPointer, block_meta_data_items: &BlockMetaDataItems, add_block_meta_data: &BlockMetaData, next_bag_stripe_index: BagStripeIndex)
 	{
 		debug_assert!(add_block.is_not_null(), "add_block can not be null");
 		debug_assert!(add_block_meta_data.get_next().is_null(), "add_block `next` can not be non-null");
 		debug_assert!(add_block_meta_data.get_previous().is_null(), "add_block `previous` can not be non-null");
 		debug_assert!(add_block_meta_data.chain_length_and_bag_stripe_index().bag_stripe_index().is_none(), "add_block should not be in a bag already");
 		
+		self.add_unchecked::<P>(chain_length, add_block, block_meta_data_items, add_block_meta_data, next_bag_stripe_index)
+	}
+	
+	// `add_block` may still be marked as being in a bag stripe, as when rebuilding the bags.
+	#[inline(always)]
+	fn add_unchecked<P: Persistence>(&self, chain_length: ChainLength, add_block: BlockPointer, block_meta_data_items: &BlockMetaDataItems, add_block_meta_data: &BlockMetaData, next_bag_stripe_index: BagStripeIndex)
+	{
 		self.acquire_spin_lock();
 		{
 			let old_head = self.get_head_relaxed();
@@ -55,16 +80,16 @@ impl BagStripe
 				old_head_block_meta_data.set_next(add_block);
 			}
 			
-			flush_struct(self);
+			P::persist_struct(self);
 		}
 		
 		self.unlock_spin_lock();
 		
-		add_block_meta_data.release(chain_length, next_bag_stripe_index)
+		add_block_meta_data.release::<P>(chain_length, next_bag_stripe_index)
 	}
 	
 	#[inline(always)]
-	fn remove(&self, chain_length: ChainLength, block_meta_data_items: &BlockMetaDataItems) -> BlockPointer
+	fn remove<P: Persistence>(&self, chain_length: ChainLength, block_meta_data_items: &BlockMetaDataItems) -> BlockPointer
 	{
 		if !self.try_to_acquire_spin_lock()
 		{
@@ -84,9 +109,9 @@ impl BagStripe
 				
 				self.set_head_relaxed(old_head_block_meta_data.get_previous());
 				
-				old_head_block_meta_data.acquire(chain_length);
+				old_head_block_meta_data.acquire::<P>(chain_length);
 				
-				flush_struct(self);
+				P::persist_struct(self);
 				
 				old_head
 			}
@@ -98,7 +123,7 @@ impl BagStripe
 	}
 	
 	#[inline(always)]
-	fn try_to_cut(&self, chain_length: ChainLength, cut_block: BlockPointer, cut_block_meta_data: &BlockMetaData, block_meta_data_items: &BlockMetaDataItems) -> bool
+	fn try_to_cut<P: Persistence>(&self, chain_length: ChainLength, cut_block: BlockPointer, cut_block_meta_data: &BlockMetaData, block_meta_data_items: &BlockMetaDataItems) -> bool
 	{
 		if !self.try_to_acquire_spin_lock()
 		{
@@ -118,8 +143,8 @@ impl BagStripe
 				
 				self.set_head_relaxed(cut_block_meta_data.get_previous());
 				
-				cut_block_meta_data.acquire(chain_length);
-				flush_struct(self);
+				cut_block_meta_data.acquire::<P>(chain_length);
+				P::persist_struct(self);
 				true
 			}
 			else
@@ -134,8 +159,8 @@ impl BagStripe
 				let after_block_meta_data = cut_block_meta_data.get_next().expand_to_pointer_to_meta_data_unchecked(block_meta_data_items);
 				after_block_meta_data.set_previous(before_block);
 				
-				cut_block_meta_data.acquire(chain_length);
-				flush_struct(self);
+				cut_block_meta_data.acquire::<P>(chain_length);
+				P::persist_struct(self);
 				true
 			}
 		};
@@ -146,6 +171,63 @@ impl BagStripe
 	}
 	
 	#[doc(hidden)]
+	// Walks the chains in this stripe from the head, checking each links back to the one after it, is marked as being in this stripe, is of `chain_length` and does not overlap a chain already found (ie one marked in `free_blocks`).
+	// Stops at the first broken link, as what follows it can not be trusted.
+	// Returns the number of chains found.
+	#[inline(always)]
+	fn check(&self, chain_length: ChainLength, bag_stripe_index: usize, block_allocator_address: usize, block_meta_data_items: &BlockMetaDataItems, free_blocks: &mut [bool], cto_pool_traversal: &CtoPoolTraversal) -> usize
+	{
+		let number_of_blocks = free_blocks.len();
+		
+		let mut found = 0;
+		let mut after_block = BlockPointer::Null;
+		let mut block = self.get_head_relaxed();
+		while block.is_not_null()
+		{
+			let block_index = block.0 as usize;
+			if block_index >= number_of_blocks || free_blocks[block_index]
+			{
+				cto_pool_traversal.record_finding(CtoPoolCheckFinding::BlockAllocatorBagLinkBroken(block_allocator_address, block_index));
+				break
+			}
+			
+			let block_meta_data = block.expand_to_pointer_to_meta_data_unchecked(block_meta_data_items);
+			let chain_length_and_bag_stripe_index = block_meta_data.chain_length_and_bag_stripe_index();
+			let is_in_this_bag_stripe = match chain_length_and_bag_stripe_index.bag_stripe_index()
+			{
+				None => false,
+				Some(its_bag_stripe_index) => its_bag_stripe_index.as_index() == bag_stripe_index,
+			};
+			if !is_in_this_bag_stripe || block_meta_data.get_next().does_not_equal(after_block)
+			{
+				cto_pool_traversal.record_finding(CtoPoolCheckFinding::BlockAllocatorBagLinkBroken(block_allocator_address, block_index));
+				break
+			}
+			
+			found += 1;
+			
+			let its_chain_length = chain_length_and_bag_stripe_index.chain_length();
+			let length = its_chain_length.as_length();
+			let exclusive_end_block_index = min(block_index + length, number_of_blocks);
+			if its_chain_length != chain_length || length > number_of_blocks - block_index || free_blocks[block_index .. exclusive_end_block_index].iter().any(|&is_free| is_free)
+			{
+				cto_pool_traversal.record_finding(CtoPoolCheckFinding::BlockAllocatorChainLengthInconsistent(block_allocator_address, block_index, length))
+			}
+			
+			// The first block is always marked, so that a cycle is detected.
+			free_blocks[block_index] = true;
+			for is_free in free_blocks[block_index .. exclusive_end_block_index].iter_mut()
+			{
+				*is_free = true
+			}
+			
+			after_block = block;
+			block = block_meta_data.get_previous();
+		}
+		
+		found
+	}
+	
 	#[inline(always)]
 	fn get_head_relaxed(&self) -> BlockPointer
 	{
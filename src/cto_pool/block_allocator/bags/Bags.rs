@@ -39,28 +39,59 @@ impl CtoSafe for Bags
 			bag.cto_pool_opened(cto_pool_arc)
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		for bag in self.bags.iter_mut()
+		{
+			bag.cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	// Walked by `BlockAllocator::cto_pool_traverse()` with `Bags::check()`, which needs to know the block allocator.
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::block_allocator::bags::Bags")
+	}
 }
 
 impl Bags
 {
 	#[inline(always)]
-	pub(crate) fn add(&self, block_meta_data_items: &BlockMetaDataItems, chain_length: ChainLength, add_block: BlockPointer)
+	pub(crate) fn add<P: Persistence>(&self, block_meta_data_items: &BlockMetaDataItems, chain_length: ChainLength, add_block: BlockPointer)
+	{
+		debug_assert!(add_block.is_not_null(), "add_block should not be null");
+		
+		let bag = chain_length.get_bag(&self.bags);
+		bag.add::<P>(chain_length, add_block, block_meta_data_items)
+	}
+	
+	/// As `add()`, but `add_block` may still be marked as being in a bag stripe and have stale links; used when rebuilding the bags after a crash.
+	#[inline(always)]
+	pub(crate) fn add_recovered<P: Persistence>(&self, block_meta_data_items: &BlockMetaDataItems, chain_length: ChainLength, add_block: BlockPointer)
 	{
 		debug_assert!(add_block.is_not_null(), "add_block should not be null");
 		
 		let bag = chain_length.get_bag(&self.bags);
-		bag.add(chain_length, add_block, block_meta_data_items)
+		bag.add_recovered::<P>(chain_length, add_block, block_meta_data_items)
 	}
 	
 	#[inline(always)]
-	pub(crate) fn remove(&self, block_meta_data_items: &BlockMetaDataItems, chain_length: ChainLength) -> BlockPointer
+	pub(crate) fn remove<P: Persistence>(&self, block_meta_data_items: &BlockMetaDataItems, chain_length: ChainLength) -> BlockPointer
 	{
 		let bag = chain_length.get_bag(&self.bags);
-		bag.remove(chain_length, block_meta_data_items)
+		bag.remove::<P>(chain_length, block_meta_data_items)
 	}
 	
 	#[inline(always)]
-	pub(crate) fn try_to_cut(&self, block_meta_data_items: &BlockMetaDataItems, might_not_be_in_bag_block: BlockPointer) -> bool
+	pub(crate) fn try_to_cut<P: Persistence>(&self, block_meta_data_items: &BlockMetaDataItems, might_not_be_in_bag_block: BlockPointer) -> bool
 	{
 		debug_assert!(might_not_be_in_bag_block.is_not_null(), "might_not_be_in_bag_block should not be null");
 		
@@ -72,7 +103,7 @@ impl Bags
 			let chain_length = chain_length_and_bag_stripe_index.chain_length();
 			let bag = chain_length.get_bag(&self.bags);
 			
-			if bag.try_to_cut(chain_length, might_not_be_in_bag_block, might_not_be_in_bag_block_meta_data, block_meta_data_items, bag_stripe_index)
+			if bag.try_to_cut::<P>(chain_length, might_not_be_in_bag_block, might_not_be_in_bag_block_meta_data, block_meta_data_items, bag_stripe_index)
 			{
 				return true
 			}
@@ -83,4 +114,21 @@ impl Bags
 		
 		false
 	}
+	
+	/// Checks the free chains in every bag, marking the blocks in them in `free_blocks`; see `BagStripe::check()`.
+	#[inline(always)]
+	pub(crate) fn check(&self, block_allocator_address: usize, block_meta_data_items: &BlockMetaDataItems, free_blocks: &mut [bool], cto_pool_traversal: &CtoPoolTraversal)
+	{
+		for (index, bag) in self.bags.iter().enumerate()
+		{
+			bag.check(ChainLength::from_index(index), block_allocator_address, block_meta_data_items, free_blocks, cto_pool_traversal)
+		}
+	}
+	
+	/// Number of chains in each bag; index 0 is for chains one block long.
+	#[inline(always)]
+	pub(crate) fn number_of_chains(&self) -> Vec<usize>
+	{
+		self.bags.iter().map(|bag| bag.number_of_chains()).collect()
+	}
 }
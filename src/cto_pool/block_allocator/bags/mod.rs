@@ -2,9 +2,12 @@
 // Copyright © 2017 The developers of nvml. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/nvml/master/COPYRIGHT.
 
 
-use super::flush_struct;
 use super::super::CtoPoolArc;
+use super::super::CtoPoolCheckFinding;
+use super::super::CtoPoolRelocation;
+use super::super::CtoPoolTraversal;
 use super::super::CtoSafe;
+use super::super::Persistence;
 use super::AtomicBlockPointer;
 use super::BlockMetaData;
 use super::BlockMetaDataItems;
@@ -13,6 +16,7 @@ use super::ChainLength;
 use ::spin_locks::BestSpinLockForCompilationTarget;
 use ::spin_locks::SpinLock;
 use ::std::cmp::max;
+use ::std::cmp::min;
 use ::std::mem::uninitialized;
 use ::std::ptr::write;
 use ::std::sync::atomic::*;
@@ -10,6 +10,16 @@ use self::bags::*;
 use ::libc::c_void;
 use ::std::cell::Cell;
 use ::std::cmp::min;
+use ::std::fmt;
+use ::std::fmt::Debug;
+use ::std::fmt::Formatter;
+use ::std::heap::Alloc;
+use ::std::heap::AllocErr;
+use ::std::heap::CannotReallocInPlace;
+use ::std::heap::Excess;
+use ::std::heap::Layout;
+use ::std::intrinsics::type_name;
+use ::std::marker::PhantomData;
 use ::std::mem::size_of;
 use ::std::ptr::copy_nonoverlapping;
 use ::std::ptr::drop_in_place;
@@ -24,6 +34,8 @@ mod bags;
 
 include!("AtomicBlockPointer.rs");
 include!("BlockAllocator.rs");
+include!("BlockAllocatorAlloc.rs");
+include!("BlockAllocatorStatistics.rs");
 include!("BlockMetaData.rs");
 include!("BlockMetaDataItems.rs");
 include!("BlockPointer.rs");
@@ -34,6 +46,3 @@ include!("Chains.rs");
 include!("NonNullExt.rs");
 include!("RestartCopyFromAt.rs");
 include!("RestartCopyIntoAt.rs");
-
-
-include!("FLUSH_HACKS.rs");
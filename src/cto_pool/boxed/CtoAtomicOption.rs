@@ -22,6 +22,36 @@ impl<Value: CtoSafe> CtoSafe for CtoAtomicOption<Value>
 			forget(cto_box);
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let value = cto_pool_relocation.relocate_atomic_pointer(&self.inner_cto_box);
+		if value.is_not_null()
+		{
+			let mut cto_box = unsafe { CtoBox::from_raw(value) };
+			cto_box.persistent_memory_mut().cto_pool_relocate(cto_pool_relocation);
+			forget(cto_box);
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		let value = self.inner_cto_box.load(SeqCst);
+		if value.is_not_null()
+		{
+			let cto_box = unsafe { CtoBox::from_raw(value) };
+			cto_box.cto_pool_traverse(cto_pool_traversal);
+			forget(cto_box);
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::boxed::CtoAtomicOption"), &[Value::schema_hash()])
+	}
 }
 
 unsafe impl<Value: CtoSafe + Send> Send for CtoAtomicOption<Value>
@@ -76,12 +106,33 @@ impl<Value: CtoSafe> CtoAtomicOption<Value>
 	}
 	
 	/// Swaps with the replacement value, returning the previous value.
+	/// In debug builds (or with the `pointer-checks` feature), panics if `replacement` is not in the same pool as this `CtoAtomicOption`; see `checked_swap()`.
 	#[inline(always)]
 	pub fn swap(&self, replacement: CtoBox<Value>, ordering: atomic::Ordering) -> Option<CtoBox<Value>>
 	{
+		#[cfg(any(debug_assertions, feature = "pointer-checks"))]
+		{
+			if let Err(error) = CtoPoolRegistry::check_pointer(self, CtoBox::as_ptr(&replacement))
+			{
+				panic!("{}", error)
+			}
+		}
+		
 		self.swap_inner(CtoBox::into_raw(replacement), ordering)
 	}
 	
+	/// As `swap()`, but first checks that `replacement` is in the same pool as this `CtoAtomicOption` (see `CtoPoolRegistry::check_pointer()`); if not, nothing is swapped and `replacement` is handed back with the error.
+	#[inline(always)]
+	pub fn checked_swap(&self, replacement: CtoBox<Value>, ordering: atomic::Ordering) -> Result<Option<CtoBox<Value>>, (CtoPointerError, CtoBox<Value>)>
+	{
+		if let Err(error) = CtoPoolRegistry::check_pointer(self, CtoBox::as_ptr(&replacement))
+		{
+			return Err((error, replacement))
+		}
+		
+		Ok(self.swap_inner(CtoBox::into_raw(replacement), ordering))
+	}
+	
 	/// Takes the CtoBox, replacing with None (null) behind.
 	#[inline(always)]
 	pub fn take(&self, ordering: atomic::Ordering) -> Option<CtoBox<Value>>
@@ -3,6 +3,8 @@
 
 
 /// Identical in concept to a regular Rust Box but exists in a persistent object pool.
+/// It does not keep its pool open, as it finds its pool with the `CtoPoolRegistry`; it must not be used after its pool has been closed.
+/// If it is dropped after its pool has been closed, nothing is done and what it refers to is leaked (see `CtoPool::collect_leaks()`), as the pool's memory is no longer mapped.
 pub struct CtoBox<Value: CtoSafe>
 {
 	persistent_memory_pointer: NonNull<CtoBoxInner<Value>>,
@@ -40,6 +42,30 @@ impl<Value: CtoSafe> CtoSafe for CtoBox<Value>
 	{
 		self.persistent_memory_mut().cto_pool_opened(cto_pool_arc)
 	}
+	
+	// A `CtoBox` uniquely owns its persistent memory, so it can not have been visited already.
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		cto_pool_relocation.relocate_non_null(&mut self.persistent_memory_pointer);
+		self.persistent_memory_mut().cto_pool_relocate(cto_pool_relocation)
+	}
+	
+	// Unless the pool is corrupt, a `CtoBox` uniquely owns its persistent memory, and so it is not necessary to check if it has been visited already.
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		if cto_pool_traversal.is_valid_pointer(self.persistent_memory_pointer.as_ptr())
+		{
+			self.persistent_memory().cto_pool_traverse(cto_pool_traversal)
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::boxed::CtoBox"), &[Value::schema_hash()])
+	}
 }
 
 impl<Value: CtoSafe> Drop for CtoBox<Value>
@@ -47,10 +73,14 @@ impl<Value: CtoSafe> Drop for CtoBox<Value>
 	#[inline(always)]
 	fn drop(&mut self)
 	{
-		let pool_pointer = self.persistent_memory().cto_pool_arc.pool_pointer();
-		
 		let persistent_memory_pointer = self.persistent_memory_pointer.as_ptr();
 		
+		let pool_pointer = match CtoPoolRegistry::pool_pointer_owning(persistent_memory_pointer)
+		{
+			None => return,
+			Some(pool_pointer) => pool_pointer,
+		};
+		
 		unsafe { drop_in_place(persistent_memory_pointer) }
 		
 		pool_pointer.free(persistent_memory_pointer);
@@ -164,13 +194,13 @@ impl<Value: CtoSafe + Hasher> Hasher for CtoBox<Value>
 	{
 		(**self).write_u64(i)
 	}
-	
+
 //	#[inline(always)]
 //	fn write_u128(&mut self, i: u128)
 //	{
 //		(**self).write_u128(i)
 //	}
-	
+
 	#[inline(always)]
 	fn write_usize(&mut self, i: usize)
 	{
@@ -200,13 +230,13 @@ impl<Value: CtoSafe + Hasher> Hasher for CtoBox<Value>
 	{
 		(**self).write_i64(i)
 	}
-	
+
 //	#[inline(always)]
 //	fn write_i128(&mut self, i: i128)
 //	{
 //		(**self).write_i128(i)
 //	}
-	
+
 	#[inline(always)]
 	fn write_isize(&mut self, i: isize)
 	{
@@ -332,6 +362,15 @@ impl<Value: CtoSafe> CtoBox<Value>
 		}
 	}
 	
+	/// As `from_raw()`, but first checks that `raw_value_pointer` points into the pool of `cto_pool_arc` (see `CtoPoolRegistry::check_pointer_is_in_pool()`).
+	#[inline(always)]
+	pub unsafe fn checked_from_raw(raw_value_pointer: *mut Value, cto_pool_arc: &CtoPoolArc) -> Result<Self, CtoPointerError>
+	{
+		CtoPoolRegistry::check_pointer_is_in_pool(cto_pool_arc, raw_value_pointer)?;
+		
+		Ok(Self::from_raw(raw_value_pointer))
+	}
+	
 	#[inline(always)]
 	fn persistent_memory(&self) -> &CtoBoxInner<Value>
 	{
@@ -8,7 +8,6 @@ pub(crate) struct CtoBoxInner<Value: CtoSafe>
 {
 	// Field order matters. `value: Value` must be first otherwise `from_raw_value_pointer()` will be very broken indeed.
 	value: Value,
-	cto_pool_arc: CtoPoolArc,
 }
 
 impl<Value: CtoSafe> Deref for CtoBoxInner<Value>
@@ -31,28 +30,30 @@ impl<Value: CtoSafe> DerefMut for CtoBoxInner<Value>
 
 impl<Value: CtoSafe> CtoBoxInner<Value>
 {
-	#[inline(always)]
-	fn common_initialization(&mut self, cto_pool_arc: &CtoPoolArc)
-	{
-		cto_pool_arc.write(&mut self.cto_pool_arc);
-	}
-	
 	#[inline(always)]
 	fn allocated<InitializationError, Initializer: FnOnce(*mut Value, &CtoPoolArc) -> Result<(), InitializationError>>(&mut self, cto_pool_arc: &CtoPoolArc, initializer: Initializer) -> Result<(), InitializationError>
 	{
-		self.common_initialization(cto_pool_arc);
-		
 		initializer(&mut self.value, cto_pool_arc)
 	}
 	
 	#[inline(always)]
 	fn cto_pool_opened(&mut self, cto_pool_arc: &CtoPoolArc)
 	{
-		self.common_initialization(cto_pool_arc);
-		
 		self.value.cto_pool_opened(cto_pool_arc)
 	}
 	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.value.cto_pool_relocate(cto_pool_relocation)
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		self.value.cto_pool_traverse(cto_pool_traversal)
+	}
+	
 	#[inline(always)]
 	fn into_raw_value_pointer(&mut self) -> *mut Value
 	{
@@ -37,6 +37,63 @@ impl<T: CtoSafe> CtoSafe for CtoVec<T>
 			index += 1;
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		// An empty or zero-sized buffer uses a dangling pointer that is not in the pool.
+		let capacity = self.buf.cap();
+		if capacity != 0 && size_of::<T>() != 0
+		{
+			let pointer = cto_pool_relocation.relocated(self.buf.ptr());
+			unsafe { write(&mut self.buf, RawVec::from_raw_parts_in(pointer, capacity, read(self.buf.alloc()))) };
+			cto_pool_relocation.persist(&self.buf);
+		}
+		
+		let mut index = 0;
+		while index < self.len
+		{
+			(unsafe { self.get_unchecked_mut(index) }).cto_pool_relocate(cto_pool_relocation);
+			index += 1;
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		// An unallocated or zero-sized buffer uses a dangling pointer that is not in the pool.
+		// An allocated buffer is checked even if empty, so that it is reached.
+		if self.buf.cap() != 0 && size_of::<T>() != 0
+		{
+			let first = self.buf.ptr() as *const T;
+			if !cto_pool_traversal.is_valid_pointer(first)
+			{
+				return
+			}
+			
+			if self.len != 0
+			{
+				let last = (first as usize).wrapping_add((self.len - 1).wrapping_mul(size_of::<T>())) as *const T;
+				if !cto_pool_traversal.is_valid_pointer(last)
+				{
+					return
+				}
+			}
+		}
+		
+		let mut index = 0;
+		while index < self.len
+		{
+			(unsafe { self.get_unchecked(index) }).cto_pool_traverse(cto_pool_traversal);
+			index += 1;
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::collections::CtoVec"), &[T::schema_hash()])
+	}
 }
 
 impl<T: CtoSafe + Clone> CtoVec<T>
@@ -106,26 +163,26 @@ impl<T: CtoSafe + Ord> Ord for CtoVec<T>
 macro_rules! __impl_slice_eq1
 {
     ($Lhs: ty, $Rhs: ty) =>
-   
+
 	{
         __impl_slice_eq1! { $Lhs, $Rhs, Sized }
     };
     ($Lhs: ty, $Rhs: ty, $Bound: ident) =>
-   
+
 	{
         impl<'a, 'b, A: $Bound, B: CtoSafe> PartialEq<$Rhs> for $Lhs where A: CtoSafe + PartialEq<B>
-       
+
 	{
             #[inline]
             fn eq(&self, other: &$Rhs) -> bool
-           
+
 	{
             	self[..] == other[..]
             }
-            
+
             #[inline]
             fn ne(&self, other: &$Rhs) -> bool
-           
+
 	{
             	self[..] != other[..]
             }
@@ -980,9 +1037,9 @@ impl<T: CtoSafe> CtoVec<T>
 impl<T: CtoSafe> IntoIterator for CtoVec<T>
 {
 	type Item = T;
-
+	
 	type IntoIter = CtoVecIntoIter<T>;
-
+	
 	/// Creates a consuming iterator, that is, one that moves each value out of the vector (from start to end).
 	/// The vector cannot be used after calling this.
 	///
@@ -1004,7 +1061,7 @@ impl<T: CtoSafe> IntoIterator for CtoVec<T>
 		{
 			let begin = self.as_mut_ptr();
 			assume(begin.is_not_null());
-
+			
 			let end = if size_of::<T>() == 0
 			{
 				arith_offset(begin as *const i8, self.len() as isize) as *const T
@@ -1013,10 +1070,10 @@ impl<T: CtoSafe> IntoIterator for CtoVec<T>
 			{
 				begin.offset(self.len() as isize) as *const T
 			};
-
+			
 			let cap = self.buf.cap();
 			forget(self);
-
+			
 			CtoVecIntoIter
 			{
 				buf: NonNull::new_unchecked(begin),
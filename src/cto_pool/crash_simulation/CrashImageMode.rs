@@ -0,0 +1,20 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// What a crash image contains for a cache line that was written but not written back and fenced when the simulated crash occurred.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum CrashImageMode
+{
+	/// The cache line is dropped, ie it contains what was last persisted.
+	/// This is the worst case if the CPU never evicts a cache line on its own.
+	DropUnpersisted,
+	
+	/// Each cache line is, at random, either dropped or kept as it was written (or as it was when written back but not fenced), as the CPU may have evicted it at any time before the crash.
+	/// The same `seed` produces the same crash images for the same sequence of stores.
+	RandomlyKeep
+	{
+		/// Seed for the random choices.
+		seed: u64,
+	},
+}
@@ -0,0 +1,195 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// What a `CrashSimulator` has recorded about the memory of a pool.
+///
+/// `durable` is what the memory would contain after a crash if no cache line were ever evicted; it is updated when a thread fences the cache lines it has written back (which are held in `written_back` until then), and when memory is persisted synchronously.
+/// Any cache line whose contents differ from `durable` has been written but not persisted.
+#[derive(Debug)]
+pub(crate) struct CrashRecording
+{
+	inclusive_start_address: usize,
+	length: usize,
+	durable: Vec<u8>,
+	written_back: HashMap<ThreadId, BTreeMap<usize, Vec<u8>>>,
+	number_of_fences: usize,
+	crash_every_nth_fence: usize,
+	crash_image_mode: CrashImageMode,
+	random_state: u64,
+	crash_images_directory_path: PathBuf,
+	crash_images: Vec<PathBuf>,
+}
+
+impl PersistenceRecording for CrashRecording
+{
+	#[inline(always)]
+	fn write_back(&mut self, address: usize)
+	{
+		if let Some((cache_line_address, cache_line_length)) = self.cache_line(address)
+		{
+			let contents = Self::live(cache_line_address, cache_line_length).to_vec();
+			self.written_back.entry(current().id()).or_insert_with(BTreeMap::new).insert(cache_line_address, contents);
+		}
+	}
+	
+	/// A crash may occur just before the fence completes, so any crash image is produced before what was written back is made durable.
+	#[inline(always)]
+	fn fence(&mut self)
+	{
+		self.number_of_fences += 1;
+		if self.number_of_fences % self.crash_every_nth_fence == 0
+		{
+			self.write_crash_image()
+		}
+		
+		if let Some(written_back) = self.written_back.remove(&current().id())
+		{
+			for (cache_line_address, contents) in written_back
+			{
+				let offset = cache_line_address - self.inclusive_start_address;
+				self.durable[offset .. offset + contents.len()].copy_from_slice(&contents[..]);
+			}
+		}
+	}
+	
+	#[inline(always)]
+	fn sync(&mut self, address: usize, length: usize)
+	{
+		let inclusive_start_address = address.max(self.inclusive_start_address);
+		let exclusive_end_address = (address + length).min(self.exclusive_end_address());
+		if inclusive_start_address < exclusive_end_address
+		{
+			let offset = inclusive_start_address - self.inclusive_start_address;
+			let length = exclusive_end_address - inclusive_start_address;
+			let contents = Self::live(inclusive_start_address, length).to_vec();
+			self.durable[offset .. offset + length].copy_from_slice(&contents[..]);
+		}
+	}
+}
+
+impl CrashRecording
+{
+	#[inline(always)]
+	fn new(inclusive_start_address: usize, length: usize, crash_images_directory_path: &Path, crash_image_mode: CrashImageMode, crash_every_nth_fence: usize) -> Self
+	{
+		assert_ne!(crash_every_nth_fence, 0, "crash_every_nth_fence can not be zero");
+		
+		let random_state = match crash_image_mode
+		{
+			CrashImageMode::DropUnpersisted => 0,
+			CrashImageMode::RandomlyKeep { seed } => seed,
+		};
+		
+		Self
+		{
+			inclusive_start_address,
+			length,
+			durable: Self::live(inclusive_start_address, length).to_vec(),
+			written_back: HashMap::new(),
+			number_of_fences: 0,
+			crash_every_nth_fence,
+			crash_image_mode,
+			// xorshift can not start from zero.
+			random_state: if random_state == 0 { 0x9E37_79B9_7F4A_7C15 } else { random_state },
+			crash_images_directory_path: crash_images_directory_path.to_path_buf(),
+			crash_images: Vec::new(),
+		}
+	}
+	
+	/// Where the `CrashRecording` that `record_write_back()`, `record_fence()`, `record_persistent_sync()` and `record_sync()` record to is installed.
+	#[inline(always)]
+	pub(crate) fn installed() -> &'static RecorderHook<CrashRecording>
+	{
+		static Installed: RecorderHook<CrashRecording> = RecorderHook::new();
+		
+		&Installed
+	}
+	
+	/// Writes a crash image to a file in the crash images directory, named after the number of fences so far; panics if it can not be written.
+	fn write_crash_image(&mut self)
+	{
+		let mut crash_image = self.durable.clone();
+		
+		if let CrashImageMode::RandomlyKeep { .. } = self.crash_image_mode
+		{
+			let cache_line_size = cache_line_size();
+			let mut offset = 0;
+			while offset < self.length
+			{
+				let cache_line_length = cache_line_size.min(self.length - offset);
+				let cache_line_address = self.inclusive_start_address + offset;
+				
+				// Each version of the cache line that might have reached persistent memory instead of the durable one.
+				let mut candidates: Vec<Vec<u8>> = Vec::new();
+				let live = Self::live(cache_line_address, cache_line_length);
+				if live != &self.durable[offset .. offset + cache_line_length]
+				{
+					candidates.push(live.to_vec());
+				}
+				for written_back in self.written_back.values()
+				{
+					if let Some(contents) = written_back.get(&cache_line_address)
+					{
+						candidates.push(contents.clone());
+					}
+				}
+				
+				if !candidates.is_empty()
+				{
+					let choice = (self.next_random() as usize) % (candidates.len() * 2);
+					if choice < candidates.len()
+					{
+						crash_image[offset .. offset + cache_line_length].copy_from_slice(&candidates[choice][..]);
+					}
+				}
+				
+				offset += cache_line_length;
+			}
+		}
+		
+		let crash_image_path = self.crash_images_directory_path.join(format!("crash-image-{}", self.number_of_fences));
+		if let Err(error) = write(&crash_image_path, &crash_image[..])
+		{
+			panic!("Could not write crash image '{:?}': {}", crash_image_path, error)
+		}
+		self.crash_images.push(crash_image_path);
+	}
+	
+	/// Aligned to cache lines, as memory is written back in whole cache lines; the first cache line may start before `inclusive_start_address`, in which case it is cut short.
+	#[inline(always)]
+	fn cache_line(&self, address: usize) -> Option<(usize, usize)>
+	{
+		if address < self.inclusive_start_address || address >= self.exclusive_end_address()
+		{
+			return None
+		}
+		
+		let cache_line_address = (round_address_down_to_start_of_cache_line(address as *mut u8) as usize).max(self.inclusive_start_address);
+		let cache_line_exclusive_end_address = (round_address_down_to_start_of_cache_line(address as *mut u8) as usize + cache_line_size()).min(self.exclusive_end_address());
+		Some((cache_line_address, cache_line_exclusive_end_address - cache_line_address))
+	}
+	
+	// What the memory contains now; it may be changing underneath.
+	#[inline(always)]
+	fn live<'memory>(address: usize, length: usize) -> &'memory [u8]
+	{
+		unsafe { from_raw_parts(address as *const u8, length) }
+	}
+	
+	#[inline(always)]
+	fn exclusive_end_address(&self) -> usize
+	{
+		self.inclusive_start_address + self.length
+	}
+	
+	// xorshift64*, so that crash images can be reproduced from a seed.
+	#[inline(always)]
+	fn next_random(&mut self) -> u64
+	{
+		self.random_state ^= self.random_state >> 12;
+		self.random_state ^= self.random_state << 25;
+		self.random_state ^= self.random_state >> 27;
+		self.random_state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+	}
+}
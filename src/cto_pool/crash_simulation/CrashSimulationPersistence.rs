@@ -0,0 +1,23 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Persists using `persistent_write_back()` and `persistent_fence()`, as `BestAvailablePersistence` does, while also recording every cache line written back and every fence for a `CrashSimulator`.
+/// Use it for a `BlockAllocator` (or anything else generic over `Persistence`) being tested with `simulate_crashes()`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct CrashSimulationPersistence;
+
+impl Persistence for CrashSimulationPersistence
+{
+	#[inline(always)]
+	fn flush_memory(address: *mut c_void, length: usize)
+	{
+		Self::for_each_cache_line(address, length, ::persistent_memory_operations::persistent_write_back)
+	}
+	
+	#[inline(always)]
+	fn drain_memory()
+	{
+		::persistent_memory_operations::persistent_fence()
+	}
+}
@@ -0,0 +1,57 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Records the persistence of a range of memory (usually that of a pool) and, every so many fences, writes a crash image of it: what it might contain had power failed just then.
+/// Cache lines are recorded when written back by `persistent_write_back()` (and so by `BestAvailablePersistence` and `CrashSimulationPersistence`) or by `ClwbPersistence`, `ClflushoptPersistence` and `ClflushPersistence`, and are persisted by a subsequent fence in the same thread (`persistent_fence()`, `persistent_sync()` or `Persistence::drain_memory()`); memory `msync`'d by `MsyncPersistence` is persisted immediately.
+/// Cache lines written back with `clwb`, `clflushopt` or `clflush` directly are not recorded, and so are never persisted.
+///
+/// A crash image starts with what has been persisted; cache lines that were written but not persisted are dropped or randomly kept (see `CrashImageMode`).
+/// Crash images are written as files, named `crash-image-N` after the number of fences recorded so far, so that they can be opened as pools.
+///
+/// Only one `CrashSimulator` can record at a time, as persistence operations are not specific to a pool.
+/// Recording stops when it is dropped; nothing else may be using the memory at that point.
+#[derive(Debug)]
+pub struct CrashSimulator(*mut Mutex<CrashRecording>);
+
+impl Drop for CrashSimulator
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		self.uninstall();
+	}
+}
+
+impl CrashSimulator
+{
+	/// Starts recording the `length` bytes at `inclusive_start_address`, which are assumed to have been persisted already.
+	/// Crash images are written to `crash_images_directory_path` at every `crash_every_nth_fence`th fence.
+	/// Panics if a `CrashSimulator` is already recording.
+	#[inline(always)]
+	pub fn start(inclusive_start_address: *mut u8, length: usize, crash_images_directory_path: &Path, crash_image_mode: CrashImageMode, crash_every_nth_fence: usize) -> Self
+	{
+		match CrashRecording::installed().install(CrashRecording::new(inclusive_start_address as usize, length, crash_images_directory_path, crash_image_mode, crash_every_nth_fence))
+		{
+			None => panic!("A CrashSimulator is already recording"),
+			Some(crash_recording) => CrashSimulator(crash_recording),
+		}
+	}
+	
+	/// Stops recording; returns the paths of the crash images written, in the order they were written.
+	#[inline(always)]
+	pub fn stop(self) -> Vec<PathBuf>
+	{
+		match self.uninstall()
+		{
+			None => Vec::new(),
+			Some(crash_recording) => crash_recording.crash_images,
+		}
+	}
+	
+	#[inline(always)]
+	fn uninstall(&self) -> Option<CrashRecording>
+	{
+		CrashRecording::installed().uninstall(self.0)
+	}
+}
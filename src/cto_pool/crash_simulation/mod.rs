@@ -0,0 +1,33 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+use intrinsics::cache_line_size;
+use intrinsics::round_address_down_to_start_of_cache_line;
+use super::CtoPool;
+use super::CtoPoolArc;
+use super::CtoPoolOpenError;
+use super::CtoSafe;
+use super::Persistence;
+use super::PMEMctopoolExt;
+use super::PersistenceRecording;
+use super::RecorderHook;
+use ::libc::c_void;
+use ::libc::mode_t;
+use ::parking_lot::Mutex;
+use ::std::collections::BTreeMap;
+use ::std::collections::HashMap;
+use ::std::error;
+use ::std::fs::write;
+use ::std::path::Path;
+use ::std::path::PathBuf;
+use ::std::slice::from_raw_parts;
+use ::std::thread::current;
+use ::std::thread::ThreadId;
+
+
+include!("CrashImageMode.rs");
+include!("CrashRecording.rs");
+include!("CrashSimulationPersistence.rs");
+include!("CrashSimulator.rs");
+include!("simulate_crashes.rs");
@@ -0,0 +1,43 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Tests that a pool recovers from power failure.
+///
+/// Creates a new pool, `pool`, in `directory_path` (which must not already contain one), and runs `workload` against it while a `CrashSimulator` writes crash images of it, also in `directory_path`.
+/// Each crash image is then opened with `CtoPool::open()` (which, as after a real crash, recovers what it can), and `invariants` run against it.
+/// Returns the crash images that could not be opened, or for which `invariants` failed, with the reason.
+///
+/// `root_value_initializer` is used to create the root when the pool is created, and in any crash image that lacks one.
+/// Persistent data structures used by `workload` that are generic over `Persistence` (such as `BlockAllocator`) should use `CrashSimulationPersistence`.
+/// The pool's memory is recorded from just after it has been created, so creating it is not tested.
+pub fn simulate_crashes<RootValue: CtoSafe, InitializationError: error::Error, RootValueInitializer: Fn(&mut RootValue, &CtoPoolArc) -> Result<(), InitializationError>, Workload: FnOnce(&CtoPool<RootValue>), Invariants: FnMut(&CtoPool<RootValue>) -> Result<(), String>>(directory_path: &Path, layout_name: &str, pool_size: usize, crash_image_mode: CrashImageMode, crash_every_nth_fence: usize, root_value_initializer: RootValueInitializer, workload: Workload, mut invariants: Invariants) -> Result<Vec<(PathBuf, String)>, CtoPoolOpenError<InitializationError>>
+{
+	const Mode: mode_t = 0o600;
+	
+	let crash_images =
+	{
+		let cto_pool = CtoPool::open(&directory_path.join("pool"), layout_name, pool_size, Mode, |root_value, cto_pool_arc| root_value_initializer(root_value, cto_pool_arc))?;
+		
+		let pool = cto_pool.pool_pointer().pool();
+		let crash_simulator = CrashSimulator::start(pool.mapped_memory.as_ptr(), pool.mapped_memory.length(), directory_path, crash_image_mode, crash_every_nth_fence);
+		workload(&cto_pool);
+		crash_simulator.stop()
+	};
+	
+	let mut failures = Vec::new();
+	for crash_image in crash_images
+	{
+		let reason = match CtoPool::open(&crash_image, layout_name, pool_size, Mode, |root_value, cto_pool_arc| root_value_initializer(root_value, cto_pool_arc))
+		{
+			Err(error) => Some(error.to_string()),
+			Ok(cto_pool) => invariants(&cto_pool).err(),
+		};
+		
+		if let Some(reason) = reason
+		{
+			failures.push((crash_image, reason))
+		}
+	}
+	Ok(failures)
+}
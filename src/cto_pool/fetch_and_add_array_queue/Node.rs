@@ -48,6 +48,61 @@ impl<Value: CtoSafe> CtoSafe for Node<Value>
 			OwnedFreeListElement::from_non_null_pointer(next).cto_pool_opened(cto_pool_arc)
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let enqueue_index_in_items = self.enqueue_index_in_items();
+		let exclusive_maximum_index = min(enqueue_index_in_items, u32::ExclusiveMaximumIndex);
+		
+		let mut dequeue_index_in_items = self.dequeue_index_in_items();
+		while dequeue_index_in_items < exclusive_maximum_index
+		{
+			let item = self.item(dequeue_index_in_items);
+			let value = item.load(Relaxed);
+			if value.is_not_null() && value != Self::TakenSentinel
+			{
+				let value = cto_pool_relocation.relocate_atomic_pointer(item);
+				if cto_pool_relocation.is_first_visit(value)
+				{
+					value.to_non_null().mutable_reference().cto_pool_relocate(cto_pool_relocation)
+				}
+			}
+			dequeue_index_in_items += 1
+		}
+		
+		let next = cto_pool_relocation.relocate_atomic_pointer(&self.next);
+		if next.is_not_null() && cto_pool_relocation.is_first_visit(next)
+		{
+			unsafe { &mut * next }.cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		let enqueue_index_in_items = self.enqueue_index_in_items();
+		let exclusive_maximum_index = min(enqueue_index_in_items, u32::ExclusiveMaximumIndex);
+		
+		let mut dequeue_index_in_items = self.dequeue_index_in_items();
+		while dequeue_index_in_items < exclusive_maximum_index
+		{
+			let value = self.item(dequeue_index_in_items).load(Relaxed);
+			if value != Self::TakenSentinel
+			{
+				cto_pool_traversal.traverse(value)
+			}
+			dequeue_index_in_items += 1
+		}
+		
+		cto_pool_traversal.traverse(self.next())
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::fetch_and_add_array_queue::Node"), &[Value::schema_hash()])
+	}
 }
 
 impl<Value: CtoSafe> Node<Value>
@@ -35,6 +35,40 @@ impl<Value: CtoSafe> CtoSafe for PersistentFetchAndAddArrayQueue<Value>
 		
 		persistent_sync()
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.free_list.cto_pool_relocate(cto_pool_relocation);
+		
+		// head is never null.
+		let head = cto_pool_relocation.relocate_atomic_pointer(&*self.head);
+		if cto_pool_relocation.is_first_visit(head)
+		{
+			unsafe { &mut * head }.cto_pool_relocate(cto_pool_relocation)
+		}
+		
+		// As tail should be reachable from head via .next on Node instances, only the pointer itself needs relocating.
+		cto_pool_relocation.relocate_atomic_pointer(&*self.tail);
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		self.free_list.cto_pool_traverse(cto_pool_traversal);
+		
+		// head is never null (unless the pool is corrupt).
+		cto_pool_traversal.traverse(self.head.load(SeqCst));
+		
+		// As tail should be reachable from head via .next on Node instances, only the pointer itself needs checking.
+		cto_pool_traversal.is_valid_pointer(self.tail.load(SeqCst));
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::fetch_and_add_array_queue::PersistentFetchAndAddArrayQueue"), &[Value::schema_hash()])
+	}
 }
 
 impl<Value: CtoSafe> Drop for PersistentFetchAndAddArrayQueue<Value>
@@ -20,6 +20,24 @@ impl<T> CtoSafe for AtomicPointerAndCounter<T>
 	default fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
 	{
 	}
+	
+	#[inline(always)]
+	default fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.relocate_pointer(cto_pool_relocation);
+	}
+	
+	#[inline(always)]
+	default fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		cto_pool_traversal.is_valid_pointer(self.get_pointer());
+	}
+	
+	#[inline(always)]
+	default fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::free_list::AtomicPointerAndCounter")
+	}
 }
 
 impl<T: CtoSafe> CtoSafe for AtomicPointerAndCounter<T>
@@ -33,6 +51,28 @@ impl<T: CtoSafe> CtoSafe for AtomicPointerAndCounter<T>
 			unsafe { &mut * pointer }.cto_pool_opened(cto_pool_arc)
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let pointer = self.relocate_pointer(cto_pool_relocation);
+		if pointer.is_not_null() && cto_pool_relocation.is_first_visit(pointer)
+		{
+			unsafe { &mut * pointer }.cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		cto_pool_traversal.traverse(self.get_pointer())
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::free_list::AtomicPointerAndCounter"), &[T::schema_hash()])
+	}
 }
 
 impl<T> AtomicPointerAndCounter<T>
@@ -72,6 +112,21 @@ impl<T> AtomicPointerAndCounter<T>
 		}
 	}
 	
+	// Only used when a pool is opened, so there is no contention; the counter is left as-is.
+	#[inline(always)]
+	fn relocate_pointer(&self, cto_pool_relocation: &CtoPoolRelocation) -> *mut T
+	{
+		let mut was = self.get_pointer_and_counter();
+		let mut relocated = was;
+		relocated.set_pointer(cto_pool_relocation.relocated(was.get_pointer()));
+		
+		let swapped = self.compare_and_swap_weak(&mut was, relocated);
+		debug_assert!(swapped, "pointer was changed whilst relocating");
+		cto_pool_relocation.persist(self);
+		
+		relocated.get_pointer()
+	}
+	
 	#[inline(always)]
 	fn get(&self) -> (u64, u64)
 	{
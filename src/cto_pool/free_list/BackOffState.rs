@@ -22,6 +22,22 @@ impl CtoSafe for BackOffState
 		self.reset_metric();
 		self.reset_total_operations();
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::free_list::BackOffState")
+	}
 }
 
 impl Default for BackOffState
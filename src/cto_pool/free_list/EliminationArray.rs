@@ -22,6 +22,36 @@ impl<T> CtoSafe for EliminationArray<T>
 			cache_line_index += 1;
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let mut cache_line_index = 0;
+		while cache_line_index < self.length.as_usize()
+		{
+			self.elimination_array_cache_line_unchecked_mut(cache_line_index).cto_pool_relocate(cto_pool_relocation);
+			
+			cache_line_index += 1;
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		let mut cache_line_index = 0;
+		while cache_line_index < self.length.as_usize()
+		{
+			self.elimination_array_cache_line_unchecked(cache_line_index).cto_pool_traverse(cto_pool_traversal);
+			
+			cache_line_index += 1;
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::free_list::EliminationArray")
+	}
 }
 
 impl<T> EliminationArray<T>
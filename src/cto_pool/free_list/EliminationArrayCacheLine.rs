@@ -21,6 +21,36 @@ impl<T> CtoSafe for EliminationArrayCacheLine<T>
 			entry_index += 1;
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let mut entry_index = 0;
+		while entry_index < MaximumNumberOfFreeListElementPointersThatFitInACacheLine
+		{
+			self.entry_mut(entry_index).cto_pool_relocate(cto_pool_relocation);
+			
+			entry_index += 1;
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		let mut entry_index = 0;
+		while entry_index < MaximumNumberOfFreeListElementPointersThatFitInACacheLine
+		{
+			self.entry(entry_index).cto_pool_traverse(cto_pool_traversal);
+			
+			entry_index += 1;
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::free_list::EliminationArrayCacheLine")
+	}
 }
 
 impl<T> EliminationArrayCacheLine<T>
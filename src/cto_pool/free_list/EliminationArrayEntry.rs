@@ -16,6 +16,28 @@ impl<T> CtoSafe for EliminationArrayEntry<T>
 			unsafe { &mut * value }.cto_pool_opened(cto_pool_arc)
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let value = cto_pool_relocation.relocate_atomic_pointer(&self.0);
+		if value.is_not_null() && cto_pool_relocation.is_first_visit(value)
+		{
+			unsafe { &mut * value }.cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		cto_pool_traversal.traverse(self.value())
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::free_list::EliminationArrayEntry")
+	}
 }
 
 impl<T> EliminationArrayEntry<T>
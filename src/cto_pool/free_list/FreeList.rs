@@ -34,6 +34,26 @@ impl<T> CtoSafe for FreeList<T>
 		self.top.cto_pool_opened(cto_pool_arc);
 		self.elimination_array.cto_pool_opened(cto_pool_arc);
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.top.cto_pool_relocate(cto_pool_relocation);
+		self.elimination_array.cto_pool_relocate(cto_pool_relocation);
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		self.top.cto_pool_traverse(cto_pool_traversal);
+		self.elimination_array.cto_pool_traverse(cto_pool_traversal);
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::free_list::FreeList"), &[FreeListElement::<T>::schema_hash()])
+	}
 }
 
 impl<T> Drop for FreeList<T>
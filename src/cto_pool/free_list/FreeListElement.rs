@@ -41,6 +41,24 @@ impl<T> CtoSafe for FreeListElement<T>
 	{
 		self.cto_pool_opened_always(cto_pool_arc)
 	}
+	
+	#[inline(always)]
+	default fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.cto_pool_relocate_always(cto_pool_relocation)
+	}
+	
+	#[inline(always)]
+	default fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		self.cto_pool_traverse_always(cto_pool_traversal)
+	}
+	
+	#[inline(always)]
+	default fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::free_list::FreeListElement")
+	}
 }
 
 impl<T: CtoSafe> CtoSafe for FreeListElement<T>
@@ -51,6 +69,26 @@ impl<T: CtoSafe> CtoSafe for FreeListElement<T>
 		self.cto_pool_opened_always(cto_pool_arc);
 		self.value.cto_pool_opened(cto_pool_arc)
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.cto_pool_relocate_always(cto_pool_relocation);
+		self.value.cto_pool_relocate(cto_pool_relocation)
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		self.cto_pool_traverse_always(cto_pool_traversal);
+		self.value.cto_pool_traverse(cto_pool_traversal)
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::free_list::FreeListElement"), &[T::schema_hash()])
+	}
 }
 
 impl<T: Copy> FreeListElement<T>
@@ -167,4 +205,22 @@ impl<T> FreeListElement<T>
 			unsafe { &mut * next }.cto_pool_opened(cto_pool_arc)
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate_always(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		cto_pool_relocation.relocate_pointer(&mut self.next);
+		
+		let next = self.next;
+		if next.is_not_null() && cto_pool_relocation.is_first_visit(next)
+		{
+			unsafe { &mut * next }.cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse_always(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		cto_pool_traversal.traverse(self.next)
+	}
 }
@@ -6,6 +6,8 @@ use IsNotNull;
 use ToNonNull;
 use hyper_thread::generate_hyper_thread_safe_random_usize;
 use super::CtoPoolArc;
+use super::CtoPoolRelocation;
+use super::CtoPoolTraversal;
 use super::CtoSafe;
 use super::arc::CtoStrongArc;
 use super::arc::CtoStrongArcInner;
@@ -0,0 +1,133 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Stored in Persistent Memory.
+/// Every chunk of the heap starts with one of these; the heap is a contiguous sequence of chunks, so it can always be walked from its start using `size`.
+/// Only `size` and `state` are persisted; which chunks are free is re-discovered by walking the heap when the pool is opened.
+/// Each field is updated with a single 8-byte store so that a crash can never leave a torn value.
+#[derive(Debug)]
+#[repr(C)]
+pub(crate) struct ChunkHeader
+{
+	size: AtomicUsize,
+	state: AtomicUsize,
+}
+
+impl ChunkHeader
+{
+	/// Size of this header in bytes; payloads immediately follow it.
+	pub(crate) const Size: usize = 16;
+	
+	/// Every chunk (and hence every payload) is aligned to this.
+	pub(crate) const MinimumAlignment: usize = 16;
+	
+	/// A chunk must be able to hold a header and a minimally-sized payload.
+	pub(crate) const MinimumChunkSize: usize = Self::Size + Self::MinimumAlignment;
+	
+	/// `FREECHNK` in ASCII.
+	const Free: usize = 0x4652_4545_4348_4E4B;
+	
+	/// `ALLOCHNK` in ASCII.
+	const Allocated: usize = 0x414C_4C4F_4348_4E4B;
+	
+	#[inline(always)]
+	pub(crate) fn at<'a>(chunk_address: usize) -> &'a Self
+	{
+		debug_assert_eq!(size_of::<Self>(), Self::Size, "size_of::<ChunkHeader>() is not ChunkHeader::Size");
+		debug_assert_eq!(chunk_address % Self::MinimumAlignment, 0, "chunk_address is not aligned");
+		
+		unsafe { & * (chunk_address as *const Self) }
+	}
+	
+	#[inline(always)]
+	pub(crate) fn chunk_address_from_payload(payload_address: usize) -> usize
+	{
+		payload_address - Self::Size
+	}
+	
+	#[inline(always)]
+	pub(crate) fn payload_address(chunk_address: usize) -> usize
+	{
+		chunk_address + Self::Size
+	}
+	
+	#[inline(always)]
+	pub(crate) fn initialize_free(&self, size: usize, persistence: MappingPersistence)
+	{
+		self.initialize(size, Self::Free, persistence)
+	}
+	
+	#[inline(always)]
+	pub(crate) fn size(&self) -> usize
+	{
+		self.size.load(Relaxed)
+	}
+	
+	#[inline(always)]
+	pub(crate) fn set_size(&self, size: usize, persistence: MappingPersistence)
+	{
+		debug_assert!(size >= Self::MinimumChunkSize, "size is too small");
+		debug_assert_eq!(size % Self::MinimumAlignment, 0, "size is not a multiple of MinimumAlignment");
+		
+		self.size.store(size, Relaxed);
+		self.persist(persistence)
+	}
+	
+	#[inline(always)]
+	pub(crate) fn usable_size(&self) -> usize
+	{
+		self.size() - Self::Size
+	}
+	
+	#[inline(always)]
+	pub(crate) fn is_free(&self) -> bool
+	{
+		self.state.load(Relaxed) == Self::Free
+	}
+	
+	#[inline(always)]
+	pub(crate) fn is_allocated(&self) -> bool
+	{
+		self.state.load(Relaxed) == Self::Allocated
+	}
+	
+	#[inline(always)]
+	pub(crate) fn has_valid_state(&self) -> bool
+	{
+		self.is_free() || self.is_allocated()
+	}
+	
+	#[inline(always)]
+	pub(crate) fn mark_free(&self, persistence: MappingPersistence)
+	{
+		self.set_state(Self::Free, persistence)
+	}
+	
+	#[inline(always)]
+	pub(crate) fn mark_allocated(&self, persistence: MappingPersistence)
+	{
+		self.set_state(Self::Allocated, persistence)
+	}
+	
+	#[inline(always)]
+	fn initialize(&self, size: usize, state: usize, persistence: MappingPersistence)
+	{
+		self.size.store(size, Relaxed);
+		self.state.store(state, Relaxed);
+		self.persist(persistence)
+	}
+	
+	#[inline(always)]
+	fn set_state(&self, state: usize, persistence: MappingPersistence)
+	{
+		self.state.store(state, Relaxed);
+		self.persist(persistence)
+	}
+	
+	#[inline(always)]
+	fn persist(&self, persistence: MappingPersistence)
+	{
+		persistence.persist_memory(self as *const Self as usize, Self::Size)
+	}
+}
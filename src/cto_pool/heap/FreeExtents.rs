@@ -0,0 +1,110 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Stored in Volatile Memory.
+/// An index of free chunks, rebuilt every time a pool is opened by walking the heap.
+/// Indexed by address (to coalesce neighbours) and by size (for best-fit allocation).
+#[derive(Debug, Default)]
+pub(crate) struct FreeExtents
+{
+	by_address: BTreeMap<usize, usize>,
+	by_size: BTreeSet<(usize, usize)>,
+	free_size: usize,
+}
+
+impl FreeExtents
+{
+	#[inline(always)]
+	pub(crate) fn insert(&mut self, chunk_address: usize, chunk_size: usize)
+	{
+		let previous = self.by_address.insert(chunk_address, chunk_size);
+		debug_assert!(previous.is_none(), "chunk_address was already free");
+		self.by_size.insert((chunk_size, chunk_address));
+		self.free_size += chunk_size;
+	}
+	
+	#[inline(always)]
+	pub(crate) fn remove(&mut self, chunk_address: usize, chunk_size: usize)
+	{
+		let removed = self.by_address.remove(&chunk_address);
+		debug_assert_eq!(removed, Some(chunk_size), "chunk_address was not free or had a different size");
+		self.by_size.remove(&(chunk_size, chunk_address));
+		self.free_size -= chunk_size;
+	}
+	
+	/// Size of the free chunk starting exactly at `chunk_address`, if any.
+	#[inline(always)]
+	pub(crate) fn size_at(&self, chunk_address: usize) -> Option<usize>
+	{
+		self.by_address.get(&chunk_address).map(|chunk_size| *chunk_size)
+	}
+	
+	/// The free chunk that ends exactly at `chunk_address`, if any.
+	#[inline(always)]
+	pub(crate) fn ending_at(&self, chunk_address: usize) -> Option<(usize, usize)>
+	{
+		match self.by_address.range(.. chunk_address).next_back()
+		{
+			Some((&preceding_chunk_address, &preceding_chunk_size)) if preceding_chunk_address + preceding_chunk_size == chunk_address => Some((preceding_chunk_address, preceding_chunk_size)),
+			_ => None,
+		}
+	}
+	
+	/// Best-fit search.
+	/// Returns `(chunk_address, chunk_size, leading_padding)`; `leading_padding` is the number of bytes at the start of the free chunk that must be left behind (as a free chunk) to satisfy `alignment`.
+	#[inline(always)]
+	pub(crate) fn find(&self, alignment: usize, payload_size: usize) -> Option<(usize, usize, usize)>
+	{
+		let minimum_chunk_size = ChunkHeader::Size + payload_size;
+		
+		for &(chunk_size, chunk_address) in self.by_size.range((minimum_chunk_size, 0) ..)
+		{
+			let leading_padding = Self::leading_padding(chunk_address, alignment);
+			if leading_padding + minimum_chunk_size <= chunk_size
+			{
+				return Some((chunk_address, chunk_size, leading_padding))
+			}
+		}
+		
+		None
+	}
+	
+	/// Total size of all free chunks.
+	#[inline(always)]
+	pub(crate) fn free_size(&self) -> usize
+	{
+		self.free_size
+	}
+	
+	/// Size of the largest free chunk, or zero if there are none.
+	#[inline(always)]
+	pub(crate) fn largest(&self) -> usize
+	{
+		match self.by_size.iter().next_back()
+		{
+			None => 0,
+			Some(&(chunk_size, _chunk_address)) => chunk_size,
+		}
+	}
+	
+	/// Free chunks in address order, as `chunk_address => chunk_size`.
+	#[inline(always)]
+	pub(crate) fn by_address(&self) -> &BTreeMap<usize, usize>
+	{
+		&self.by_address
+	}
+	
+	/// Leading padding is either zero or large enough to form a free chunk in its own right.
+	#[inline(always)]
+	fn leading_padding(chunk_address: usize, alignment: usize) -> usize
+	{
+		let payload_address = ChunkHeader::payload_address(chunk_address).round_up_to_alignment(alignment);
+		let mut leading_padding = ChunkHeader::chunk_address_from_payload(payload_address) - chunk_address;
+		while leading_padding != 0 && leading_padding < ChunkHeader::MinimumChunkSize
+		{
+			leading_padding += alignment;
+		}
+		leading_padding
+	}
+}
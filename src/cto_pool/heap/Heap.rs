@@ -0,0 +1,499 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Stored in Volatile Memory.
+/// A general purpose, `malloc`-like heap over a range of persistent memory.
+///
+/// The persistent state is just a sequence of `ChunkHeader`s; every update to it is ordered so that a crash leaves a walkable heap (at worst, an allocation is leaked).
+/// The index of free chunks is volatile and is rebuilt by walking the heap on open.
+/// Chunk headers are persisted with `persistence`, which suits how the heap's memory was mapped.
+#[derive(Debug)]
+pub(crate) struct Heap
+{
+	inclusive_start_address: usize,
+	exclusive_end_address: AtomicUsize,
+	free_extents: Mutex<FreeExtents>,
+	number_of_allocations: AtomicUsize,
+	persistence: MappingPersistence,
+}
+
+impl Heap
+{
+	/// Formats the memory `inclusive_start_address .. exclusive_end_address` as an empty heap.
+	#[inline(always)]
+	pub(crate) fn create(inclusive_start_address: usize, exclusive_end_address: usize, persistence: MappingPersistence) -> Result<Self, PmdkError>
+	{
+		let (inclusive_start_address, exclusive_end_address) = Self::align_bounds(inclusive_start_address, exclusive_end_address);
+		
+		let heap_size = exclusive_end_address.saturating_sub(inclusive_start_address);
+		if heap_size < ChunkHeader::MinimumChunkSize
+		{
+			return Err(PmdkError::OutOfSpace(ChunkHeader::MinimumChunkSize))
+		}
+		
+		ChunkHeader::at(inclusive_start_address).initialize_free(heap_size, persistence);
+		
+		let mut free_extents = FreeExtents::default();
+		free_extents.insert(inclusive_start_address, heap_size);
+		
+		Ok(Self::new(inclusive_start_address, exclusive_end_address, free_extents, 0, persistence))
+	}
+	
+	/// Opens an existing heap, rebuilding the index of free chunks and coalescing any adjacent free chunks left behind by a crash.
+	#[inline(always)]
+	pub(crate) fn open(inclusive_start_address: usize, exclusive_end_address: usize, persistence: MappingPersistence) -> Result<Self, PmdkError>
+	{
+		let (inclusive_start_address, exclusive_end_address) = Self::align_bounds(inclusive_start_address, exclusive_end_address);
+		
+		let mut free_extents = FreeExtents::default();
+		let mut free_run: Option<(usize, usize)> = None;
+		let mut number_of_allocations = 0;
+		
+		Self::walk(inclusive_start_address, exclusive_end_address, |chunk_address, chunk_header|
+		{
+			if chunk_header.is_free()
+			{
+				free_run = match free_run
+				{
+					None => Some((chunk_address, chunk_header.size())),
+					Some((free_run_address, free_run_size)) => Some((free_run_address, free_run_size + chunk_header.size())),
+				};
+			}
+			else
+			{
+				number_of_allocations += 1;
+				if let Some((free_run_address, free_run_size)) = free_run.take()
+				{
+					Self::record_free_run(&mut free_extents, free_run_address, free_run_size, persistence);
+				}
+			}
+		})?;
+		
+		if let Some((free_run_address, free_run_size)) = free_run
+		{
+			Self::record_free_run(&mut free_extents, free_run_address, free_run_size, persistence);
+		}
+		
+		Ok(Self::new(inclusive_start_address, exclusive_end_address, free_extents, number_of_allocations, persistence))
+	}
+	
+	/// Checks the heap can be walked but does not modify it (eg by coalescing free chunks); the resultant heap can not allocate.
+	/// Free chunks are indexed as found, without coalescing, so that statistics are still available.
+	#[inline(always)]
+	pub(crate) fn open_read_only(inclusive_start_address: usize, exclusive_end_address: usize, persistence: MappingPersistence) -> Result<Self, PmdkError>
+	{
+		let (inclusive_start_address, exclusive_end_address) = Self::align_bounds(inclusive_start_address, exclusive_end_address);
+		
+		let mut free_extents = FreeExtents::default();
+		let mut number_of_allocations = 0;
+		
+		Self::walk(inclusive_start_address, exclusive_end_address, |chunk_address, chunk_header|
+		{
+			if chunk_header.is_free()
+			{
+				free_extents.insert(chunk_address, chunk_header.size());
+			}
+			else
+			{
+				number_of_allocations += 1;
+			}
+		})?;
+		
+		Ok(Self::new(inclusive_start_address, exclusive_end_address, free_extents, number_of_allocations, persistence))
+	}
+	
+	/// Checks that a heap can be walked from start to end without finding a corrupt chunk.
+	/// Does not modify the heap.
+	#[inline(always)]
+	pub(crate) fn is_consistent(inclusive_start_address: usize, exclusive_end_address: usize) -> bool
+	{
+		let (inclusive_start_address, exclusive_end_address) = Self::align_bounds(inclusive_start_address, exclusive_end_address);
+		
+		Self::walk(inclusive_start_address, exclusive_end_address, |_chunk_address, _chunk_header| {}).is_ok()
+	}
+	
+	/// Extends the heap up to `new_exclusive_end_address`, which must be memory that has just been made available immediately after the end of the heap.
+	///
+	/// A free chunk covering the new memory is written first; `record_new_exclusive_end_address` is then called, and must persistently record the new end of the heap; only then is the free chunk coalesced with any free chunk preceding it.
+	/// A crash before the new end is recorded leaves the heap as it was; a crash after it leaves two adjacent free chunks, which are coalesced when the heap is next opened.
+	///
+	/// Does nothing if the new memory is too small to form a chunk.
+	#[inline(always)]
+	pub(crate) fn grow<RecordNewExclusiveEndAddress: FnOnce()>(&self, new_exclusive_end_address: usize, record_new_exclusive_end_address: RecordNewExclusiveEndAddress)
+	{
+		let new_exclusive_end_address = new_exclusive_end_address & !(ChunkHeader::MinimumAlignment - 1);
+		
+		let mut free_extents = self.free_extents.lock();
+		
+		let old_exclusive_end_address = self.exclusive_end_address();
+		debug_assert!(new_exclusive_end_address >= old_exclusive_end_address, "new_exclusive_end_address '0x{:X}' can not be before the end of the heap '0x{:X}'", new_exclusive_end_address, old_exclusive_end_address);
+		
+		let added_size = new_exclusive_end_address - old_exclusive_end_address;
+		if added_size < ChunkHeader::MinimumChunkSize
+		{
+			return
+		}
+		
+		ChunkHeader::at(old_exclusive_end_address).initialize_free(added_size, self.persistence);
+		record_new_exclusive_end_address();
+		self.exclusive_end_address.store(new_exclusive_end_address, Release);
+		
+		let (chunk_address, chunk_size) = match free_extents.ending_at(old_exclusive_end_address)
+		{
+			None => (old_exclusive_end_address, added_size),
+			
+			Some((preceding_chunk_address, preceding_chunk_size)) =>
+			{
+				free_extents.remove(preceding_chunk_address, preceding_chunk_size);
+				let chunk_size = preceding_chunk_size + added_size;
+				ChunkHeader::at(preceding_chunk_address).set_size(chunk_size, self.persistence);
+				(preceding_chunk_address, chunk_size)
+			}
+		};
+		free_extents.insert(chunk_address, chunk_size)
+	}
+	
+	/// How the heap's memory is persisted.
+	#[inline(always)]
+	pub(crate) fn persistence(&self) -> MappingPersistence
+	{
+		self.persistence
+	}
+	
+	/// Is this pointer within the memory managed by this heap?
+	#[inline(always)]
+	pub(crate) fn contains(&self, pointer: *const u8) -> bool
+	{
+		let address = pointer as usize;
+		address >= self.inclusive_start_address && address < self.exclusive_end_address()
+	}
+	
+	/// Allocates `size` bytes aligned to `alignment`, which must be a power of two.
+	#[inline(always)]
+	pub(crate) fn allocate(&self, alignment: usize, size: usize) -> Result<*mut u8, PmdkError>
+	{
+		if !alignment.is_power_of_two()
+		{
+			return Err(PmdkError::BadAlignment(alignment))
+		}
+		
+		if self.can_never_fit(size)
+		{
+			return Err(PmdkError::OutOfSpace(size))
+		}
+		
+		let alignment = max(alignment, ChunkHeader::MinimumAlignment);
+		let payload_size = Self::payload_size(size);
+		
+		let mut free_extents = self.free_extents.lock();
+		
+		let (chunk_address, chunk_size, leading_padding) = match free_extents.find(alignment, payload_size)
+		{
+			None => return Err(PmdkError::OutOfSpace(size)),
+			Some(found) => found,
+		};
+		free_extents.remove(chunk_address, chunk_size);
+		
+		let allocation_address = chunk_address + leading_padding;
+		let mut allocation_size = ChunkHeader::Size + payload_size;
+		let trailing_size = chunk_size - leading_padding - allocation_size;
+		
+		// Order of writes matters: new headers are written inside the free chunk (where a walk can not see them) before the size that makes them visible is changed.
+		if trailing_size < ChunkHeader::MinimumChunkSize
+		{
+			allocation_size += trailing_size;
+		}
+		else
+		{
+			let trailing_address = allocation_address + allocation_size;
+			ChunkHeader::at(trailing_address).initialize_free(trailing_size, self.persistence);
+			free_extents.insert(trailing_address, trailing_size);
+		}
+		
+		if leading_padding == 0
+		{
+			ChunkHeader::at(allocation_address).set_size(allocation_size, self.persistence);
+		}
+		else
+		{
+			ChunkHeader::at(allocation_address).initialize_free(allocation_size, self.persistence);
+			ChunkHeader::at(chunk_address).set_size(leading_padding, self.persistence);
+			free_extents.insert(chunk_address, leading_padding);
+		}
+		
+		ChunkHeader::at(allocation_address).mark_allocated(self.persistence);
+		self.number_of_allocations.fetch_add(1, Relaxed);
+		
+		Ok(ChunkHeader::payload_address(allocation_address) as *mut u8)
+	}
+	
+	/// Frees a pointer previously returned by `allocate()` or `reallocate()`.
+	#[inline(always)]
+	pub(crate) fn free(&self, pointer: *mut u8)
+	{
+		let mut chunk_address = self.allocated_chunk_address(pointer);
+		let chunk_header = ChunkHeader::at(chunk_address);
+		
+		CtoPoolRegistry::unregister_block_allocators_in(pointer as usize, pointer as usize + chunk_header.usable_size());
+		
+		let mut free_extents = self.free_extents.lock();
+		
+		chunk_header.mark_free(self.persistence);
+		self.number_of_allocations.fetch_sub(1, Relaxed);
+		let mut chunk_size = chunk_header.size();
+		
+		let following_chunk_address = chunk_address + chunk_size;
+		if let Some(following_chunk_size) = free_extents.size_at(following_chunk_address)
+		{
+			free_extents.remove(following_chunk_address, following_chunk_size);
+			chunk_size += following_chunk_size;
+			chunk_header.set_size(chunk_size, self.persistence);
+		}
+		
+		if let Some((preceding_chunk_address, preceding_chunk_size)) = free_extents.ending_at(chunk_address)
+		{
+			free_extents.remove(preceding_chunk_address, preceding_chunk_size);
+			chunk_size += preceding_chunk_size;
+			chunk_address = preceding_chunk_address;
+			ChunkHeader::at(chunk_address).set_size(chunk_size, self.persistence);
+		}
+		
+		free_extents.insert(chunk_address, chunk_size)
+	}
+	
+	/// Resizes an allocation, in place if possible.
+	/// If the allocation has to move, the new allocation has at least the alignment of the old pointer (capped at the page size), so the alignment the original allocation was made with is preserved.
+	#[inline(always)]
+	pub(crate) fn reallocate(&self, pointer: *mut u8, new_size: usize) -> Result<*mut u8, PmdkError>
+	{
+		if self.can_never_fit(new_size)
+		{
+			return Err(PmdkError::OutOfSpace(new_size))
+		}
+		
+		if self.reallocate_in_place(pointer, new_size)
+		{
+			return Ok(pointer)
+		}
+		
+		let chunk_header = ChunkHeader::at(self.allocated_chunk_address(pointer));
+		
+		let alignment = min(1 << (pointer as usize).trailing_zeros(), memory_map_page_size());
+		let new_pointer = self.allocate(alignment, new_size)?;
+		
+		let copy_size = min(chunk_header.usable_size(), new_size);
+		unsafe { copy_nonoverlapping(pointer as *const u8, new_pointer, copy_size) };
+		self.persistence.persist_memory(new_pointer as usize, copy_size);
+		
+		self.free(pointer);
+		
+		Ok(new_pointer)
+	}
+	
+	/// Resizes an allocation without moving it; shrinking always succeeds, but growing only succeeds if the chunk following the allocation is free and large enough.
+	/// Returns `false` if the allocation could not be resized.
+	#[inline(always)]
+	pub(crate) fn reallocate_in_place(&self, pointer: *mut u8, new_size: usize) -> bool
+	{
+		if self.can_never_fit(new_size)
+		{
+			return false
+		}
+		
+		let chunk_address = self.allocated_chunk_address(pointer);
+		let chunk_header = ChunkHeader::at(chunk_address);
+		let required_chunk_size = ChunkHeader::Size + Self::payload_size(new_size);
+		
+		let mut free_extents = self.free_extents.lock();
+		
+		let chunk_size = chunk_header.size();
+		let following_chunk_address = chunk_address + chunk_size;
+		
+		if required_chunk_size <= chunk_size
+		{
+			let mut remainder_size = chunk_size - required_chunk_size;
+			if remainder_size >= ChunkHeader::MinimumChunkSize
+			{
+				if let Some(following_chunk_size) = free_extents.size_at(following_chunk_address)
+				{
+					free_extents.remove(following_chunk_address, following_chunk_size);
+					remainder_size += following_chunk_size;
+				}
+				Self::split_off_free_remainder(&mut free_extents, chunk_header, chunk_address, required_chunk_size, remainder_size, self.persistence);
+			}
+			return true
+		}
+		
+		if let Some(following_chunk_size) = free_extents.size_at(following_chunk_address)
+		{
+			let combined_chunk_size = chunk_size + following_chunk_size;
+			if required_chunk_size <= combined_chunk_size
+			{
+				free_extents.remove(following_chunk_address, following_chunk_size);
+				
+				let remainder_size = combined_chunk_size - required_chunk_size;
+				if remainder_size >= ChunkHeader::MinimumChunkSize
+				{
+					Self::split_off_free_remainder(&mut free_extents, chunk_header, chunk_address, required_chunk_size, remainder_size, self.persistence);
+				}
+				else
+				{
+					chunk_header.set_size(combined_chunk_size, self.persistence);
+				}
+				return true
+			}
+		}
+		
+		false
+	}
+	
+	/// Usable size of an allocation; at least the size requested.
+	#[inline(always)]
+	pub(crate) fn usable_size(&self, pointer: *mut u8) -> usize
+	{
+		ChunkHeader::at(self.allocated_chunk_address(pointer)).usable_size()
+	}
+	
+	/// The allocations in the heap, as a map of the address of each to the address just after its end.
+	#[inline(always)]
+	pub(crate) fn allocations(&self) -> Result<BTreeMap<usize, usize>, PmdkError>
+	{
+		let _free_extents = self.free_extents.lock();
+		
+		let mut allocations = BTreeMap::new();
+		Self::walk(self.inclusive_start_address, self.exclusive_end_address(), |chunk_address, chunk_header|
+		{
+			if chunk_header.is_allocated()
+			{
+				let payload_address = ChunkHeader::payload_address(chunk_address);
+				allocations.insert(payload_address, payload_address + chunk_header.usable_size());
+			}
+		})?;
+		Ok(allocations)
+	}
+	
+	/// Every chunk in the heap, in address order, as its address, its size (including its chunk header) and whether it is allocated.
+	#[inline(always)]
+	pub(crate) fn chunks(&self) -> Result<Vec<(usize, usize, bool)>, PmdkError>
+	{
+		let _free_extents = self.free_extents.lock();
+		
+		let mut chunks = Vec::new();
+		Self::walk(self.inclusive_start_address, self.exclusive_end_address(), |chunk_address, chunk_header| chunks.push((chunk_address, chunk_header.size(), chunk_header.is_allocated())))?;
+		Ok(chunks)
+	}
+	
+	/// A snapshot of how the heap is used; `total_size` is the size of the memory the heap is in, including anything before it (such as a pool header).
+	#[inline(always)]
+	pub(crate) fn statistics(&self, total_size: usize) -> CtoPoolStatistics
+	{
+		let free_extents = self.free_extents.lock();
+		let heap_size = self.exclusive_end_address() - self.inclusive_start_address;
+		let free_size = free_extents.free_size();
+		
+		CtoPoolStatistics
+		{
+			total_size,
+			allocated_size: heap_size - free_size,
+			free_size,
+			number_of_allocations: self.number_of_allocations.load(Relaxed),
+			largest_free_extent: free_extents.largest(),
+		}
+	}
+	
+	#[inline(always)]
+	fn new(inclusive_start_address: usize, exclusive_end_address: usize, free_extents: FreeExtents, number_of_allocations: usize, persistence: MappingPersistence) -> Self
+	{
+		Self
+		{
+			inclusive_start_address,
+			exclusive_end_address: AtomicUsize::new(exclusive_end_address),
+			free_extents: Mutex::new(free_extents),
+			number_of_allocations: AtomicUsize::new(number_of_allocations),
+			persistence,
+		}
+	}
+	
+	#[inline(always)]
+	fn exclusive_end_address(&self) -> usize
+	{
+		self.exclusive_end_address.load(Acquire)
+	}
+	
+	// Also stops the rounding up of huge sizes from overflowing.
+	#[inline(always)]
+	fn can_never_fit(&self, size: usize) -> bool
+	{
+		size > self.exclusive_end_address() - self.inclusive_start_address
+	}
+	
+	#[inline(always)]
+	fn align_bounds(inclusive_start_address: usize, exclusive_end_address: usize) -> (usize, usize)
+	{
+		let inclusive_start_address = inclusive_start_address.round_up_to_alignment(ChunkHeader::MinimumAlignment);
+		let exclusive_end_address = exclusive_end_address & !(ChunkHeader::MinimumAlignment - 1);
+		(inclusive_start_address, exclusive_end_address)
+	}
+	
+	#[inline(always)]
+	fn payload_size(size: usize) -> usize
+	{
+		max(size, 1).round_up_to_alignment(ChunkHeader::MinimumAlignment)
+	}
+	
+	#[inline(always)]
+	fn allocated_chunk_address(&self, pointer: *mut u8) -> usize
+	{
+		assert!(self.contains(pointer), "pointer '{:?}' is not in this heap", pointer);
+		
+		let chunk_address = ChunkHeader::chunk_address_from_payload(pointer as usize);
+		assert!(ChunkHeader::at(chunk_address).is_allocated(), "pointer '{:?}' is not allocated (double free or corrupt chunk)", pointer);
+		chunk_address
+	}
+	
+	// The remainder header is written inside the existing chunk, and only becomes visible when the chunk is shrunk.
+	#[inline(always)]
+	fn split_off_free_remainder(free_extents: &mut FreeExtents, chunk_header: &ChunkHeader, chunk_address: usize, required_chunk_size: usize, remainder_size: usize, persistence: MappingPersistence)
+	{
+		let remainder_address = chunk_address + required_chunk_size;
+		ChunkHeader::at(remainder_address).initialize_free(remainder_size, persistence);
+		chunk_header.set_size(required_chunk_size, persistence);
+		free_extents.insert(remainder_address, remainder_size);
+	}
+	
+	#[inline(always)]
+	fn record_free_run(free_extents: &mut FreeExtents, free_run_address: usize, free_run_size: usize, persistence: MappingPersistence)
+	{
+		let chunk_header = ChunkHeader::at(free_run_address);
+		if chunk_header.size() != free_run_size
+		{
+			chunk_header.set_size(free_run_size, persistence);
+		}
+		free_extents.insert(free_run_address, free_run_size);
+	}
+	
+	fn walk<ChunkUser: FnMut(usize, &ChunkHeader)>(inclusive_start_address: usize, exclusive_end_address: usize, mut chunk_user: ChunkUser) -> Result<(), PmdkError>
+	{
+		let mut chunk_address = inclusive_start_address;
+		while chunk_address != exclusive_end_address
+		{
+			if exclusive_end_address - chunk_address < ChunkHeader::MinimumChunkSize
+			{
+				return Err(PmdkError::CorruptMetadata("chunk runs past the end of the heap", chunk_address))
+			}
+			
+			let chunk_header = ChunkHeader::at(chunk_address);
+			let chunk_size = chunk_header.size();
+			if !chunk_header.has_valid_state() || chunk_size < ChunkHeader::MinimumChunkSize || chunk_size % ChunkHeader::MinimumAlignment != 0 || chunk_size > exclusive_end_address - chunk_address
+			{
+				return Err(PmdkError::CorruptMetadata("invalid chunk header", chunk_address))
+			}
+			
+			chunk_user(chunk_address, chunk_header);
+			
+			chunk_address += chunk_size;
+		}
+		Ok(())
+	}
+}
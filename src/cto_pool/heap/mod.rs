@@ -0,0 +1,26 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+use Alignment;
+use memory_map::memory_map_page_size;
+use super::CtoPoolRegistry;
+use super::CtoPoolStatistics;
+use super::MappingPersistence;
+use super::PmdkError;
+use ::parking_lot::Mutex;
+use ::std::cmp::max;
+use ::std::cmp::min;
+use ::std::collections::BTreeMap;
+use ::std::collections::BTreeSet;
+use ::std::mem::size_of;
+use ::std::ptr::copy_nonoverlapping;
+use ::std::sync::atomic::AtomicUsize;
+use ::std::sync::atomic::Ordering::Acquire;
+use ::std::sync::atomic::Ordering::Relaxed;
+use ::std::sync::atomic::Ordering::Release;
+
+
+include!("ChunkHeader.rs");
+include!("FreeExtents.rs");
+include!("Heap.rs");
@@ -2,7 +2,32 @@
 // Copyright © 2017 The developers of nvml. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/nvml/master/COPYRIGHT.
 
 
+use Alignment;
 use IsNotNull;
+use hyper_thread::generate_hyper_thread_safe_random_usize;
+use dax::DaxDevicePathExt;
+use intrinsics::CacheLineWriteBack;
+use intrinsics::cache_line_size;
+use intrinsics::clflush;
+use intrinsics::clflushopt;
+use intrinsics::clwb;
+use intrinsics::round_address_down_to_start_of_cache_line;
+use intrinsics::sfence;
+use memory_map::ContiguousPart;
+use memory_map::CouldNotMemoryMapError;
+use memory_map::MappedMemory;
+use memory_map::MemoryMapAddress;
+use memory_map::PersistentMemoryFilePathExt;
+use memory_map::find_lowest_unoccupied_address_in_process_map;
+use memory_map::memory_map_contiguously;
+use memory_map::memory_map_page_size;
+use persistent_memory_operations::persistent_fence;
+use persistent_memory_operations::persistent_sync;
+use persistent_memory_operations::persistent_write_back;
+use pool_set::PoolSetParseError;
+use pool_set::PoolSetPathExt;
+use self::block_allocator::BlockAllocator;
+use self::heap::Heap;
 use self::arc::CtoArc;
 use self::collections::CtoVec;
 use self::synchronisation::CtoParkingLotMutexLock;
@@ -11,21 +36,36 @@ use self::synchronisation::CtoParkingLotReentrantMutexLock;
 use self::string::CtoString;
 use self::boxed::CtoBox;
 use self::rc::CtoRc;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::c_uint;
 use ::libc::c_void;
 use ::libc::mode_t;
+use ::libc::msync;
+use ::libc::MS_SYNC;
 use ::libc::size_t;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::syscall;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::SYS_memfd_create;
+use ::parking_lot::Mutex;
+use ::parking_lot::RwLock;
 use ::std::borrow::Borrow;
 use ::std::borrow::BorrowMut;
+use ::std::cell::RefCell;
 use ::std::cmp::min;
 use ::std::cmp::Ordering;
+use ::std::collections::BTreeMap;
+use ::std::collections::HashMap;
+use ::std::collections::HashSet;
 use ::std::error;
 use ::std::ffi::CStr;
 use ::std::ffi::CString;
+use ::std::ffi::NulError;
 use ::std::fmt;
 use ::std::fmt::Debug;
 use ::std::fmt::Display;
 use ::std::fmt::Formatter;
 use ::std::fmt::Pointer;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::fs::File;
+use ::std::fs::OpenOptions;
+use ::std::fs::remove_file;
 use ::std::hash::Hash;
 use ::std::hash::Hasher;
 use ::std::heap::Alloc;
@@ -33,17 +73,31 @@ use ::std::heap::AllocErr;
 use ::std::heap::CannotReallocInPlace;
 use ::std::heap::Excess;
 use ::std::heap::Layout;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::io;
+use ::std::io::ErrorKind;
 use ::std::marker::PhantomData;
 use ::std::mem::align_of;
+use ::std::mem::forget;
 use ::std::mem::size_of;
 use ::std::ops::Deref;
 use ::std::ops::DerefMut;
+use ::std::os::unix::fs::OpenOptionsExt;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::os::unix::io::FromRawFd;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::os::unix::io::RawFd;
 use ::std::ptr::copy_nonoverlapping;
 use ::std::ptr::drop_in_place;
 use ::std::ptr::NonNull;
+use ::std::ptr::null;
+use ::std::ptr::null_mut;
 use ::std::ptr::write;
+use ::std::ptr::write_bytes;
+use ::std::slice::from_raw_parts;
 use ::std::path::Path;
+use ::std::path::PathBuf;
+use ::std::sync::atomic::AtomicPtr;
 use ::std::sync::atomic::AtomicUsize;
+use ::std::sync::atomic::Ordering::Acquire;
+use ::std::sync::atomic::Ordering::Release;
 use ::std::sync::atomic::Ordering::SeqCst;
 
 /// An Arc like that in regular Rust's stdlib.
@@ -58,6 +112,18 @@ pub mod boxed;
 /// Collections
 pub mod collections;
 
+/// Simulates power failure, by recording what has been persisted and producing the images of pools that might be left after a crash, so that recovery can be tested without an NVDIMM.
+/// Only available with the `crash-simulation` feature, as it records every persistence operation in the process.
+#[cfg(feature = "crash-simulation")]
+pub mod crash_simulation;
+
+/// Finds persistency bugs, such as stores that are never written back, write backs that are never fenced and redundant write backs, by recording every persistence operation made on a range of memory.
+/// Only available with the `persistency-check` feature, which is intended for debug builds.
+#[cfg(feature = "persistency-check")]
+pub mod persistency_check;
+
+mod heap;
+
 /// A fetch-and-add array queue (`FAAArrayQueue`) by Pedro Ramalhete & Andreia Correia of Concurrency Freaks
 /// See https://github.com/pramalhe/ConcurrencyFreaks/blob/master/CPP/queues/array/FAAArrayQueue.hpp and the Concurrency Freaks blog.
 pub mod fetch_and_add_array_queue;
@@ -80,16 +146,57 @@ const PMEMCTO_MAX_LAYOUT: size_t = 1024;
 
 
 include!("Allocator.rs");
+include!("BestAvailablePersistence.rs");
+include!("ClflushoptPersistence.rs");
+include!("ClflushPersistence.rs");
+include!("ClwbPersistence.rs");
 include!("CtoPool.rs");
 include!("CtoPoolAlloc.rs");
 include!("CtoPoolAllocationError.rs");
 include!("CtoPoolArcInner.rs");
 include!("CtoPoolArc.rs");
+include!("CtoPoolBlockAllocatorMarks.rs");
+include!("CtoPoolCheckFinding.rs");
+include!("CtoPoolDump.rs");
+include!("CtoPoolHeader.rs");
+include!("CtoPoolHeaderBase.rs");
+include!("CtoPoolHeaderValidationError.rs");
+include!("CtoPoolInformation.rs");
+include!("CtoPoolLeaks.rs");
+include!("CtoPoolMigration.rs");
+include!("CtoPoolMigrations.rs");
+include!("CtoPoolNamedRoots.rs");
 include!("CtoPoolOpenError.rs");
 include!("CtoPoolPathExt.rs");
+include!("CtoPoolReferenceCounts.rs");
+include!("CtoPointerError.rs");
+include!("CtoPoolRegistration.rs");
+include!("CtoPoolRegistry.rs");
+include!("CtoPoolRelocation.rs");
+include!("CtoPoolStatistics.rs");
+include!("CtoPoolTraversal.rs");
 include!("CtoSafe.rs");
+include!("EadrPersistence.rs");
+include!("LastShutdown.rs");
+include!("MappingPersistence.rs");
+include!("MaximumNamedRootNameLength.rs");
+include!("MaximumNumberOfNamedRoots.rs");
+include!("MsyncPersistence.rs");
 include!("Persistence.rs");
+include!("PersistenceRecording.rs");
 include!("PersistentMemoryWrapper.rs");
 include!("PmdkError.rs");
 include!("PMEMctopool.rs");
 include!("PMEMctopoolExt.rs");
+include!("ReadOnlyCtoPool.rs");
+include!("RecorderHook.rs");
+include!("RootDirectory.rs");
+include!("RootDirectoryEntry.rs");
+include!("RootDirectoryError.rs");
+include!("TypeFingerprint.rs");
+include!("VolatileCtoPoolFile.rs");
+include!("record_fence.rs");
+include!("record_persistent_sync.rs");
+include!("record_sync.rs");
+include!("record_write_back.rs");
+include!("with_each_persistence_recording.rs");
@@ -0,0 +1,39 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A persistency bug found by a `PersistencyChecker`.
+/// Each is reported once, for the address of the (start of the) cache line it was found in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PersistencyBug
+{
+	/// A cache line (the address) was stored to but never written back, so the store would be lost in a crash; found when the `PersistencyChecker` was stopped.
+	UnflushedStore(usize),
+	
+	/// A cache line (the address) was written back but never fenced, so the store might be lost in a crash; found when the `PersistencyChecker` was stopped.
+	UnfencedFlush(usize),
+	
+	/// A cache line (the address) was written back when it had not changed since it was last persisted, or when it had already been written back, unchanged, since the last fence.
+	/// These are not unsafe, but are slow.
+	RedundantFlush(usize),
+	
+	/// A `persistent_sync()` was reached when a cache line (the address) had been stored to but not written back.
+	SyncWithUnflushedData(usize),
+}
+
+impl Display for PersistencyBug
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::PersistencyBug::*;
+		
+		match *self
+		{
+			UnflushedStore(address) => write!(f, "cache line '0x{:x}' was stored to but never written back", address),
+			UnfencedFlush(address) => write!(f, "cache line '0x{:x}' was written back but never fenced", address),
+			RedundantFlush(address) => write!(f, "cache line '0x{:x}' was written back redundantly", address),
+			SyncWithUnflushedData(address) => write!(f, "cache line '0x{:x}' had not been written back at a persistent sync", address),
+		}
+	}
+}
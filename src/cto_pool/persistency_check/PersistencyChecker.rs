@@ -0,0 +1,66 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Checks the persistence of a range of memory (usually that of a pool) for missing and redundant write backs and fences, in the spirit of `pmemcheck`.
+/// Intended for debug builds and tests, as every `persistent_sync()` compares the whole range with what has been persisted.
+///
+/// Cache lines are tracked as being dirty (stored to but not written back), written back but not fenced, or durable.
+/// Cache lines are written back by `persistent_write_back()` (and so by `BestAvailablePersistence`) or by `ClwbPersistence`, `ClflushoptPersistence` and `ClflushPersistence`, and are persisted by a subsequent fence in the same thread (`persistent_fence()`, `persistent_sync()` or `Persistence::drain_memory()`); memory `msync`'d by `MsyncPersistence` is persisted immediately.
+/// Stores themselves can not be intercepted, so a cache line is dirty if its contents differ from what was last persisted; bytes annotated with `annotate_volatile()` are ignored.
+/// Consequently, stores made by other threads that have yet to write them back are reported at a `persistent_sync()`, too.
+/// Memory persisted with `EadrPersistence` is never written back, so should not be checked.
+///
+/// Only one `PersistencyChecker` can run at a time, as persistence operations are not specific to a pool.
+/// Checking stops when it is dropped; nothing else may be using the memory at that point.
+#[derive(Debug)]
+pub struct PersistencyChecker(*mut Mutex<PersistencyRecording>);
+
+impl Drop for PersistencyChecker
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		self.uninstall();
+	}
+}
+
+impl PersistencyChecker
+{
+	/// Starts checking the `length` bytes at `inclusive_start_address`, which are assumed to have been persisted already.
+	/// Panics if a `PersistencyChecker` is already running.
+	#[inline(always)]
+	pub fn start(inclusive_start_address: *mut u8, length: usize) -> Self
+	{
+		match PersistencyRecording::installed().install(PersistencyRecording::new(inclusive_start_address as usize, length))
+		{
+			None => panic!("A PersistencyChecker is already running"),
+			Some(persistency_recording) => PersistencyChecker(persistency_recording),
+		}
+	}
+	
+	/// The persistency bugs found so far, ordered by kind then address.
+	/// Stores that have never been written back and write backs that have never been fenced are only found when stopped.
+	#[inline(always)]
+	pub fn bugs(&self) -> Vec<PersistencyBug>
+	{
+		unsafe { & * self.0 }.lock().bugs.iter().cloned().collect()
+	}
+	
+	/// Stops checking; returns all the persistency bugs found, ordered by kind then address.
+	#[inline(always)]
+	pub fn stop(self) -> Vec<PersistencyBug>
+	{
+		match self.uninstall()
+		{
+			None => Vec::new(),
+			Some(persistency_recording) => persistency_recording.stopped(),
+		}
+	}
+	
+	#[inline(always)]
+	fn uninstall(&self) -> Option<PersistencyRecording>
+	{
+		PersistencyRecording::installed().uninstall(self.0)
+	}
+}
@@ -0,0 +1,218 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// What a `PersistencyChecker` has recorded about the memory it checks.
+///
+/// `durable` is what has been persisted; it is updated when a thread fences the cache lines it has written back (which are held in `written_back` until then), and when memory is persisted synchronously.
+/// `volatile` holds the ranges annotated as volatile, by inclusive start address, with their exclusive end addresses.
+#[derive(Debug)]
+pub(crate) struct PersistencyRecording
+{
+	inclusive_start_address: usize,
+	length: usize,
+	durable: Vec<u8>,
+	written_back: HashMap<ThreadId, BTreeMap<usize, Vec<u8>>>,
+	volatile: BTreeMap<usize, usize>,
+	bugs: BTreeSet<PersistencyBug>,
+}
+
+impl PersistenceRecording for PersistencyRecording
+{
+	#[inline(always)]
+	fn write_back(&mut self, address: usize)
+	{
+		if let Some((cache_line_address, cache_line_length)) = self.cache_line(address)
+		{
+			let contents = Self::live(cache_line_address, cache_line_length).to_vec();
+			
+			let redundant = if !self.differs_from_durable(cache_line_address, &contents[..])
+			{
+				true
+			}
+			else
+			{
+				match self.written_back.get(&current().id()).and_then(|written_back| written_back.get(&cache_line_address))
+				{
+					None => false,
+					Some(already_written_back) => already_written_back == &contents,
+				}
+			};
+			if redundant
+			{
+				self.bugs.insert(PersistencyBug::RedundantFlush(cache_line_address));
+			}
+			
+			self.written_back.entry(current().id()).or_insert_with(BTreeMap::new).insert(cache_line_address, contents);
+		}
+	}
+	
+	#[inline(always)]
+	fn fence(&mut self)
+	{
+		if let Some(written_back) = self.written_back.remove(&current().id())
+		{
+			for (cache_line_address, contents) in written_back
+			{
+				let offset = cache_line_address - self.inclusive_start_address;
+				self.durable[offset .. offset + contents.len()].copy_from_slice(&contents[..]);
+			}
+		}
+	}
+	
+	/// Any cache line that still differs from what has been persisted, and that has not been written back by another thread, was stored to without being written back.
+	#[inline(always)]
+	fn persistent_sync(&mut self)
+	{
+		self.fence();
+		
+		for cache_line_address in self.dirty_cache_lines()
+		{
+			if !self.is_written_back(cache_line_address)
+			{
+				self.bugs.insert(PersistencyBug::SyncWithUnflushedData(cache_line_address));
+			}
+		}
+	}
+	
+	#[inline(always)]
+	fn sync(&mut self, address: usize, length: usize)
+	{
+		let inclusive_start_address = address.max(self.inclusive_start_address);
+		let exclusive_end_address = (address + length).min(self.exclusive_end_address());
+		if inclusive_start_address < exclusive_end_address
+		{
+			let offset = inclusive_start_address - self.inclusive_start_address;
+			let length = exclusive_end_address - inclusive_start_address;
+			let contents = Self::live(inclusive_start_address, length).to_vec();
+			self.durable[offset .. offset + length].copy_from_slice(&contents[..]);
+		}
+	}
+}
+
+impl PersistencyRecording
+{
+	#[inline(always)]
+	fn new(inclusive_start_address: usize, length: usize) -> Self
+	{
+		Self
+		{
+			inclusive_start_address,
+			length,
+			durable: Self::live(inclusive_start_address, length).to_vec(),
+			written_back: HashMap::new(),
+			volatile: BTreeMap::new(),
+			bugs: BTreeSet::new(),
+		}
+	}
+	
+	/// Where the `PersistencyRecording` that `record_write_back()`, `record_fence()`, `record_persistent_sync()`, `record_sync()` and `annotate_volatile()` record to is installed.
+	#[inline(always)]
+	pub(crate) fn installed() -> &'static RecorderHook<PersistencyRecording>
+	{
+		static Installed: RecorderHook<PersistencyRecording> = RecorderHook::new();
+		
+		&Installed
+	}
+	
+	#[inline(always)]
+	fn annotate_volatile(&mut self, address: usize, length: usize)
+	{
+		let inclusive_start_address = address.max(self.inclusive_start_address);
+		let exclusive_end_address = (address + length).min(self.exclusive_end_address());
+		if inclusive_start_address < exclusive_end_address
+		{
+			let exclusive_end_address = match self.volatile.get(&inclusive_start_address)
+			{
+				None => exclusive_end_address,
+				Some(&existing_exclusive_end_address) => existing_exclusive_end_address.max(exclusive_end_address),
+			};
+			self.volatile.insert(inclusive_start_address, exclusive_end_address);
+		}
+	}
+	
+	fn stopped(mut self) -> Vec<PersistencyBug>
+	{
+		for cache_line_address in self.dirty_cache_lines()
+		{
+			let bug = if self.is_written_back(cache_line_address)
+			{
+				PersistencyBug::UnfencedFlush(cache_line_address)
+			}
+			else
+			{
+				PersistencyBug::UnflushedStore(cache_line_address)
+			};
+			self.bugs.insert(bug);
+		}
+		
+		self.bugs.into_iter().collect()
+	}
+	
+	fn dirty_cache_lines(&self) -> Vec<usize>
+	{
+		let mut dirty_cache_lines = Vec::new();
+		
+		let mut address = self.inclusive_start_address;
+		while address < self.exclusive_end_address()
+		{
+			let (cache_line_address, cache_line_length) = self.cache_line(address).unwrap();
+			if self.differs_from_durable(cache_line_address, Self::live(cache_line_address, cache_line_length))
+			{
+				dirty_cache_lines.push(cache_line_address);
+			}
+			address = cache_line_address + cache_line_length;
+		}
+		
+		dirty_cache_lines
+	}
+	
+	#[inline(always)]
+	fn is_written_back(&self, cache_line_address: usize) -> bool
+	{
+		self.written_back.values().any(|written_back| written_back.contains_key(&cache_line_address))
+	}
+	
+	/// Volatile bytes are ignored.
+	#[inline(always)]
+	fn differs_from_durable(&self, address: usize, contents: &[u8]) -> bool
+	{
+		let offset = address - self.inclusive_start_address;
+		let durable = &self.durable[offset .. offset + contents.len()];
+		
+		contents.iter().zip(durable.iter()).enumerate().any(|(index, (byte, durable_byte))| byte != durable_byte && !self.is_volatile(address + index))
+	}
+	
+	#[inline(always)]
+	fn is_volatile(&self, address: usize) -> bool
+	{
+		self.volatile.range(.. address + 1).rev().any(|(_, &exclusive_end_address)| address < exclusive_end_address)
+	}
+	
+	/// Aligned to cache lines, as memory is written back in whole cache lines; the first cache line may start before `inclusive_start_address`, in which case it is cut short.
+	#[inline(always)]
+	fn cache_line(&self, address: usize) -> Option<(usize, usize)>
+	{
+		if address < self.inclusive_start_address || address >= self.exclusive_end_address()
+		{
+			return None
+		}
+		
+		let cache_line_address = (round_address_down_to_start_of_cache_line(address as *mut u8) as usize).max(self.inclusive_start_address);
+		let cache_line_exclusive_end_address = (round_address_down_to_start_of_cache_line(address as *mut u8) as usize + cache_line_size()).min(self.exclusive_end_address());
+		Some((cache_line_address, cache_line_exclusive_end_address - cache_line_address))
+	}
+	
+	// What the memory contains now; it may be changing underneath.
+	#[inline(always)]
+	fn live<'memory>(address: usize, length: usize) -> &'memory [u8]
+	{
+		unsafe { from_raw_parts(address as *const u8, length) }
+	}
+	
+	#[inline(always)]
+	fn exclusive_end_address(&self) -> usize
+	{
+		self.inclusive_start_address + self.length
+	}
+}
@@ -0,0 +1,13 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Annotates `length` bytes at `address` as volatile, ie never needing to be persisted, if a `PersistencyChecker` is running; stores to them are not reported.
+///
+/// Use this for memory inside a pool that is reinitialized whenever the pool is opened, such as the lock word of a `parking_lot` lock (the `CtoParkingLot*Lock` wrappers annotate their own).
+/// Annotations last until the `PersistencyChecker` is stopped, even if the memory is freed.
+#[inline(always)]
+pub fn annotate_volatile(address: *const u8, length: usize)
+{
+	PersistencyRecording::installed().with(|persistency_recording| persistency_recording.annotate_volatile(address as usize, length))
+}
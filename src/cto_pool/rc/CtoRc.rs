@@ -3,6 +3,8 @@
 
 
 /// Similar to a Rust Rc but allocated in a persistent memory CTO Pool.
+/// It does not keep its pool open, as it finds its pool with the `CtoPoolRegistry`; it must not be used after its pool has been closed.
+/// If it is dropped after its pool has been closed, nothing is done and what it refers to is leaked (see `CtoPool::collect_leaks()`), as the pool's memory is no longer mapped.
 pub struct CtoRc<Value: CtoSafe>
 {
 	persistent_memory_pointer: NonNull<CtoRcInner<Value>>
@@ -40,6 +42,28 @@ impl<Value: CtoSafe> CtoSafe for CtoRc<Value>
 	{
 		self.persistent_memory_mut().cto_pool_opened(cto_pool_arc)
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		cto_pool_relocation.relocate_non_null(&mut self.persistent_memory_pointer);
+		self.cto_pool_relocate_persistent_memory(cto_pool_relocation)
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		if cto_pool_traversal.is_valid_pointer(self.persistent_memory_pointer())
+		{
+			self.cto_pool_traverse_persistent_memory(cto_pool_traversal)
+		}
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::rc::CtoRc"), &[Value::schema_hash()])
+	}
 }
 
 impl<Value: CtoSafe> Drop for CtoRc<Value>
@@ -47,6 +71,12 @@ impl<Value: CtoSafe> Drop for CtoRc<Value>
 	#[inline(always)]
 	fn drop(&mut self)
 	{
+		let pool_pointer = match CtoPoolRegistry::pool_pointer_owning(self.persistent_memory_pointer())
+		{
+			None => return,
+			Some(pool_pointer) => pool_pointer,
+		};
+		
 		let has_no_more_strong_references =
 		{
 			let cto_rc_inner = self.persistent_memory();
@@ -69,8 +99,6 @@ impl<Value: CtoSafe> Drop for CtoRc<Value>
 			
 			if cto_rc_inner.weak_count() == 0
 			{
-				let pool_pointer = cto_rc_inner.cto_pool_arc.pool_pointer();
-				
 				pool_pointer.free(ptr);
 			}
 		}
@@ -307,6 +335,30 @@ impl<Value: CtoSafe> CtoRc<Value>
 		}
 	}
 	
+	// Other references to the same persistent memory may have already relocated it.
+	#[inline(always)]
+	fn cto_pool_relocate_persistent_memory(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		if cto_pool_relocation.is_first_visit(self.persistent_memory_pointer())
+		{
+			self.persistent_memory_mut().cto_pool_relocate(cto_pool_relocation)
+		}
+	}
+	
+	// Every reference to the same persistent memory is counted, but only the first traverses it.
+	// Strong references do not hold a weak reference, and weak references can not be persisted, so the weak count should be zero.
+	#[inline(always)]
+	fn cto_pool_traverse_persistent_memory(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		let persistent_memory_pointer = self.persistent_memory_pointer();
+		let persistent_memory = self.persistent_memory();
+		cto_pool_traversal.found_strong_reference(persistent_memory_pointer, persistent_memory.strong_count(), Some((persistent_memory.weak_count(), 0)));
+		if cto_pool_traversal.is_first_visit(persistent_memory_pointer)
+		{
+			persistent_memory.cto_pool_traverse(cto_pool_traversal)
+		}
+	}
+	
 	#[inline(always)]
 	fn persistent_memory(&self) -> &CtoRcInner<Value>
 	{
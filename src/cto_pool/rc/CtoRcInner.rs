@@ -10,7 +10,6 @@ pub(crate) struct CtoRcInner<Value: CtoSafe>
 	value: Value,
 	strong_counter: CtoRcCounter,
 	weak_counter: CtoRcCounter,
-	cto_pool_arc: CtoPoolArc,
 }
 
 impl<Value: CtoSafe> Deref for CtoRcInner<Value>
@@ -35,12 +34,6 @@ impl<Value: CtoSafe> DerefMut for CtoRcInner<Value>
 
 impl<Value: CtoSafe> CtoRcInner<Value>
 {
-	#[inline(always)]
-	fn common_initialization(&mut self, cto_pool_arc: &CtoPoolArc)
-	{
-		cto_pool_arc.write(&mut self.cto_pool_arc);
-	}
-	
 	#[inline(always)]
 	fn allocated<InitializationError, Initializer: FnOnce(*mut Value, &CtoPoolArc) -> Result<(), InitializationError>>(&mut self, cto_pool_arc: &CtoPoolArc, initializer: Initializer) -> Result<(), InitializationError>
 	{
@@ -48,19 +41,27 @@ impl<Value: CtoSafe> CtoRcInner<Value>
 		
 		unsafe { write(&mut self.weak_counter, CtoRcCounter::default()) };
 		
-		self.common_initialization(cto_pool_arc);
-		
 		initializer(&mut self.value, cto_pool_arc)
 	}
 	
 	#[inline(always)]
 	fn cto_pool_opened(&mut self, cto_pool_arc: &CtoPoolArc)
 	{
-		self.common_initialization(cto_pool_arc);
-		
 		self.value.cto_pool_opened(cto_pool_arc)
 	}
 	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.value.cto_pool_relocate(cto_pool_relocation)
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		self.value.cto_pool_traverse(cto_pool_traversal)
+	}
+	
 	#[inline(always)]
 	fn into_raw_value_pointer(&mut self) -> *mut Value
 	{
@@ -3,6 +3,7 @@
 
 
 /// Very similar to Rust's Rc Weak.
+/// As for a `CtoRc`, if it is dropped after its pool has been closed, nothing is done.
 pub struct WeakCtoRc<Value: CtoSafe>
 {
 	persistent_memory_pointer: Option<NonNull<CtoRcInner<Value>>>,
@@ -15,14 +16,18 @@ impl<Value: CtoSafe> Drop for WeakCtoRc<Value>
 	{
 		if let Some(persistent_memory_pointer) = self.persistent_memory_pointer
 		{
+			let pool_pointer = match CtoPoolRegistry::pool_pointer_owning(persistent_memory_pointer.as_ptr())
+			{
+				None => return,
+				Some(pool_pointer) => pool_pointer,
+			};
+			
 			let cto_rc_inner = unsafe { persistent_memory_pointer.as_ref() };
 			
 			cto_rc_inner.weak_count_decrement();
 			
 			if cto_rc_inner.strong_count() == 0 && cto_rc_inner.weak_count() == 0
 			{
-				let pool_pointer = cto_rc_inner.cto_pool_arc.pool_pointer();
-				
 				pool_pointer.free(persistent_memory_pointer.as_ptr());
 			}
 		}
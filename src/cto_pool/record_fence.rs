@@ -0,0 +1,9 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+/// Records a fence by this thread, which persists the cache lines it has written back, if a `CrashSimulator` or `PersistencyChecker` is recording.
+#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))]
+#[inline(always)]
+pub(crate) fn record_fence()
+{
+	with_each_persistence_recording(|persistence_recording| persistence_recording.fence())
+}
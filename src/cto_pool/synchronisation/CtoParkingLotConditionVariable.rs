@@ -23,6 +23,22 @@ impl CtoSafe for CtoParkingLotConditionVariable
 	{
 		unsafe { write(&mut self.0, Condvar::new()) };
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::synchronisation::CtoParkingLotConditionVariable")
+	}
 }
 
 impl CtoParkingLotConditionVariable
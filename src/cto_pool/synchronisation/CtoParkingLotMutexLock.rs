@@ -13,6 +13,8 @@ impl<Value: CtoSafe> Deref for CtoParkingLotMutexLock<Value>
 	#[inline(always)]
 	fn deref(&self) -> &Self::Target
 	{
+		#[cfg(feature = "persistency-check")] super::persistency_check::annotate_volatile(&self.hack_to_access_private_fields().raw as *const RawMutex_HorribleHackToAccessPrivateFields as *const u8, size_of::<RawMutex_HorribleHackToAccessPrivateFields>());
+		
 		&self.0
 	}
 }
@@ -30,6 +32,29 @@ impl<Value: CtoSafe> CtoSafe for CtoParkingLotMutexLock<Value>
 			unsafe { &mut *mutate_private_fields.data.get() }.cto_pool_opened(cto_pool_arc);
 		}
 	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		let mutate_private_fields = self.hack_to_mutate_private_fields();
+		
+		unsafe { &mut *mutate_private_fields.data.get() }.cto_pool_relocate(cto_pool_relocation)
+	}
+	
+	// The lock is not taken, as it may have been left locked by a crash.
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		let private_fields = self.hack_to_access_private_fields();
+		
+		unsafe { & *private_fields.data.get() }.cto_pool_traverse(cto_pool_traversal)
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("persistent_memory::cto_pool::synchronisation::CtoParkingLotMutexLock"), &[Value::schema_hash()])
+	}
 }
 
 impl<Value: CtoSafe> CtoParkingLotMutexLock<Value>
@@ -73,6 +98,12 @@ impl<Value: CtoSafe> CtoParkingLotMutexLock<Value>
 		unsafe { &mut * (&mut self.0 as *mut Mutex<Value> as *mut Mutex_HorribleHackToAccessPrivateFields<Value>) }
 	}
 	
+	#[inline(always)]
+	fn hack_to_access_private_fields(&self) -> &Mutex_HorribleHackToAccessPrivateFields<Value>
+	{
+		unsafe { & * (&self.0 as *const Mutex<Value> as *const Mutex_HorribleHackToAccessPrivateFields<Value>) }
+	}
+	
 	#[inline(always)]
 	fn initialize_raw(mutate_private_fields: &mut Mutex_HorribleHackToAccessPrivateFields<Value>)
 	{
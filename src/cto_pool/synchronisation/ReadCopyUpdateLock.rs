@@ -36,8 +36,9 @@ impl<Value: CtoSafe + Clone> ReadCopyUpdateLock<Value>
 	/// A version of `write()` that provides customization of the deep clone of the contents of value.
 	/// The pointer (first argument) supplied to the callback is effectively the uninitialized clone.
 	/// The third third argument is the original value.
+	/// Returns an error if the clone can not be allocated (including, as `PmdkError::PoolClosed`, if the pool is being closed) or if the callback fails.
 	#[inline(always)]
-	pub fn write_customized_deep_clone<CallbackError, DeepCloneCallback: FnOnce(*mut Value, &CtoPoolArc, &Value) -> Result<(), CallbackError>>(&self, deep_clone_callback: DeepCloneCallback) -> Result<ReadCopyUpdateLockWriteGuard<Value>, CallbackError>
+	pub fn write_customized_deep_clone<CallbackError, DeepCloneCallback: FnOnce(*mut Value, &CtoPoolArc, &Value) -> Result<(), CallbackError>>(&self, deep_clone_callback: DeepCloneCallback) -> Result<ReadCopyUpdateLockWriteGuard<Value>, CtoPoolAllocationError<CallbackError>>
 	{
 		let guard = self.write_lock.lock();
 		let cto_arc = self.cto_arc_cell.get();
@@ -60,17 +61,22 @@ impl<Value: CtoSafe + Clone> ReadCopyUpdateLock<Value>
 	/// Acquire an exclusive write handle to the `ReadCopyUpdateLock`, protected by an `ReadCopyUpdateLockGuard`.
 	/// This operation blocks if another `ReadCopyUpdateLockGuard` is currently alive, ie the `ReadCopyUpdateLock` has already handed one out to another writer.
 	/// Clones the data protected by the `ReadCopyUpdateLock`, which can be expensive.
+	/// Returns an error if the clone can not be allocated (including, as `PmdkError::PoolClosed`, if the pool is being closed).
 	#[inline(always)]
-	pub fn write(&self) -> ReadCopyUpdateLockWriteGuard<Value>
+	pub fn write(&self) -> Result<ReadCopyUpdateLockWriteGuard<Value>, PmdkError>
 	{
 		let guard = self.write_lock.lock();
 		let cto_arc = self.cto_arc_cell.get();
+		let deep_clone_of_value = cto_arc.deep_clone()?;
 		
-		ReadCopyUpdateLockWriteGuard
-		{
-			lock: self,
-			deep_clone_of_value: cto_arc.deep_clone(),
-			_guard: guard,
-		}
+		Ok
+		(
+			ReadCopyUpdateLockWriteGuard
+			{
+				lock: self,
+				deep_clone_of_value,
+				_guard: guard,
+			}
+		)
 	}
 }
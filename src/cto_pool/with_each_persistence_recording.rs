@@ -0,0 +1,10 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+/// Calls `user` with each installed `PersistenceRecording`.
+#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))]
+#[inline(always)]
+fn with_each_persistence_recording<User: Fn(&mut PersistenceRecording)>(user: User)
+{
+	#[cfg(feature = "crash-simulation")] self::crash_simulation::CrashRecording::installed().with(|crash_recording| user(crash_recording));
+	#[cfg(feature = "persistency-check")] self::persistency_check::PersistencyRecording::installed().with(|persistency_recording| user(persistency_recording));
+}
@@ -33,6 +33,13 @@ quick_error!
             from()
 		}
 		
+		/// Is not on an NVDIMM.
+		IsNotOnAnNvdimm
+		{
+			description("Is not on an NVDIMM")
+			display("Is not on an NVDIMM (no NVDIMM region could be found)")
+		}
+		
 		/// Isn't a file.
 		IsNotAFile
 		{
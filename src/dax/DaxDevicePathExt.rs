@@ -35,6 +35,14 @@ pub trait DaxDevicePathExt
 	#[inline(always)]
 	fn find_dax_device_region_id(&self) -> Result<usize, CouldNotObtainDaxDeviceStatisticError>;
 	
+	/// NVDIMM unsafe shutdown count; the sum of the `dirty_shutdown` counts of every NVDIMM in the region backing this DAX device, or, for a file, its file system.
+	/// Increases whenever an NVDIMM loses power without successfully writing back its buffers; if it has changed, data written but not yet written back may have been lost.
+	///
+	/// Requires Linux 4.19 or later; fails if the path is not on an NVDIMM.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	fn find_nvdimm_unsafe_shutdown_count(&self) -> Result<u64, CouldNotObtainDaxDeviceStatisticError>;
+	
 	#[doc(hidden)]
 	#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
 	#[inline(always)]
@@ -125,28 +133,62 @@ impl DaxDevicePathExt for Path
 		)
 	}
 	
-	#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+	#[cfg(any(target_os = "android", target_os = "linux"))]
 	#[inline(always)]
-	fn find_dax_device_file_statistic_string<Statistic, FileTemplate: FnOnce(u32, u32) -> String, Parser: FnOnce(&str) -> Result<Statistic, CouldNotObtainDaxDeviceStatisticError>>(&self, file_template: FileTemplate, parser: Parser) -> Result<Statistic, CouldNotObtainDaxDeviceStatisticError>
+	fn find_nvdimm_unsafe_shutdown_count(&self) -> Result<u64, CouldNotObtainDaxDeviceStatisticError>
 	{
-		let device_dax_align_file_path =
+		use ::libc::major;
+		use ::libc::minor;
+		
+		let sys_device_path = if self.is_this_a_dax_device()
 		{
 			let (device_major, device_minor) = self.character_device_major_and_device_minor()?;
-			file_template(device_major, device_minor)
+			PathBuf::from(format!("/sys/dev/char/{}:{}/device", device_major, device_minor))
+		}
+		else
+		{
+			let block_device = self.metadata()?.dev();
+			PathBuf::from(format!("/sys/dev/block/{}:{}", unsafe { major(block_device) }, unsafe { minor(block_device) }))
 		};
 		
-		let mut alignment_file = File::open(device_dax_align_file_path)?;
+		// eg `/sys/devices/platform/ACPI0012:00/ndbus0/region0/namespace0.0/block/pmem0` or `/sys/devices/platform/ACPI0012:00/ndbus0/region0/dax0.1/dax0.0`.
+		let sys_device_path = sys_device_path.canonicalize()?;
+		let mut region_path = sys_device_path.as_path();
+		while !region_path.file_name().map(|file_name| file_name.to_string_lossy().starts_with("region")).unwrap_or(false)
+		{
+			region_path = match region_path.parent()
+			{
+				None => return Err(CouldNotObtainDaxDeviceStatisticError::IsNotOnAnNvdimm),
+				Some(parent) => parent,
+			}
+		}
+		let bus_path = region_path.parent().ok_or(CouldNotObtainDaxDeviceStatisticError::IsNotOnAnNvdimm)?;
 		
-		let mut statistic_string = String::with_capacity(64);
-		let _bytes_read = alignment_file.read_to_string(&mut statistic_string)?;
+		let number_of_mappings = read_statistic_string(&region_path.join("mappings"))?.parse::<usize>()?;
 		
-		if !statistic_string.ends_with('\n')
+		let mut unsafe_shutdown_count = 0;
+		for mapping_index in 0 .. number_of_mappings
 		{
-			return Err(CouldNotObtainDaxDeviceStatisticError::StringDidNotEndWithLineFeed)
+			// Formatted as `nmem0,offset,length,position`.
+			let mapping = read_statistic_string(&region_path.join(format!("mapping{}", mapping_index)))?;
+			let nvdimm = mapping.split(',').next().unwrap();
+			
+			unsafe_shutdown_count += read_statistic_string(&bus_path.join(nvdimm).join("nfit").join("dirty_shutdown"))?.parse::<u64>()?;
 		}
-		let statistic_string = &statistic_string[.. statistic_string.len() - 1];
+		Ok(unsafe_shutdown_count)
+	}
+	
+	#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+	#[inline(always)]
+	fn find_dax_device_file_statistic_string<Statistic, FileTemplate: FnOnce(u32, u32) -> String, Parser: FnOnce(&str) -> Result<Statistic, CouldNotObtainDaxDeviceStatisticError>>(&self, file_template: FileTemplate, parser: Parser) -> Result<Statistic, CouldNotObtainDaxDeviceStatisticError>
+	{
+		let device_dax_statistic_file_path =
+		{
+			let (device_major, device_minor) = self.character_device_major_and_device_minor()?;
+			file_template(device_major, device_minor)
+		};
 		
-		parser(statistic_string)
+		parser(&read_statistic_string(Path::new(&device_dax_statistic_file_path))?)
 	}
 	
 	#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
@@ -0,0 +1,22 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Reads a sysfs statistic file, removing the trailing line feed.
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+#[inline(always)]
+fn read_statistic_string(statistic_file_path: &Path) -> Result<String, CouldNotObtainDaxDeviceStatisticError>
+{
+	let mut statistic_file = File::open(statistic_file_path)?;
+	
+	let mut statistic_string = String::with_capacity(64);
+	let _bytes_read = statistic_file.read_to_string(&mut statistic_string)?;
+	
+	if !statistic_string.ends_with('\n')
+	{
+		return Err(CouldNotObtainDaxDeviceStatisticError::StringDidNotEndWithLineFeed)
+	}
+	statistic_string.pop();
+	
+	Ok(statistic_string)
+}
@@ -3,16 +3,21 @@
 
 
 /// Cache line size for compiled architecture.
+/// Use `cache_line_size()` to find the cache line size of the CPU this process is running on.
 #[cfg(target_arch = "x86_64")] pub const CacheLineSize: usize = 64;
 
 /// Cache line size for compiled architecture.
+/// Use `cache_line_size()` to find the cache line size of the CPU this process is running on.
 #[cfg(target_arch = "x86")] pub const CacheLineSize: usize = 32;
 
 /// Cache line size for compiled architecture.
+/// Use `cache_line_size()` to find the cache line size of the CPU this process is running on.
 #[cfg(target_arch = "aarch64")] pub const CacheLineSize: usize = 64;
 
 /// Cache line size for compiled architecture.
+/// Use `cache_line_size()` to find the cache line size of the CPU this process is running on.
 #[cfg(all(target_pointer_width = "32", not(any(target_arch = "x86_64", target_arch = "x86", target_arch = "aarch64"))))] pub const CacheLineSize: usize = 32;
 
 /// Cache line size for compiled architecture.
+/// Use `cache_line_size()` to find the cache line size of the CPU this process is running on.
 #[cfg(all(target_pointer_width = "64", not(any(target_arch = "x86_64", target_arch = "x86", target_arch = "aarch64"))))] pub const CacheLineSize: usize = 64;
@@ -0,0 +1,179 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// An instruction that writes back a modified cache line to memory.
+///
+/// Use `CacheLineWriteBack::best_available()` to find the best instruction supported by the CPU this process is running on, so that one binary can run on, say, Broadwell (`clflush`), Skylake (`clflushopt`) and Ice Lake (`clwb`).
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum CacheLineWriteBack
+{
+	/// `clwb`; writes back without necessarily evicting the cache line.
+	#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+	Clwb,
+	
+	/// `clflushopt`; writes back and evicts the cache line.
+	#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+	Clflushopt,
+	
+	/// `clflush`; writes back and evicts the cache line, and is serialized.
+	#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+	Clflush,
+	
+	/// `dc cvac`; cleans the cache line to the point of coherency.
+	#[cfg(target_arch = "aarch64")]
+	DcCvac,
+	
+	/// No instruction is available.
+	Unavailable,
+}
+
+impl CacheLineWriteBack
+{
+	/// Returns the (cached) best instruction supported by the CPU this process is running on.
+	#[inline(always)]
+	pub fn best_available() -> Self
+	{
+		static mut BestAvailable: Option<CacheLineWriteBack> = None;
+		
+		// Technically, we ought to use an Atomic compare-and-swap, but the cost isn't worthwhile.
+		match unsafe { BestAvailable }
+		{
+			Some(best_available) => best_available,
+			
+			None =>
+			{
+				let best_available = Self::detect();
+				unsafe { BestAvailable = Some(best_available) };
+				best_available
+			}
+		}
+	}
+	
+	/// Is this instruction supported by the CPU this process is running on?
+	///
+	/// Uses `cpuid`, which is slow.
+	#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+	#[inline(always)]
+	pub fn is_available(self) -> bool
+	{
+		use self::CacheLineWriteBack::*;
+		
+		const StructuredExtendedFeatureFlagsLeaf: u32 = 7;
+		const ClflushIsSupported: u32 = 1 << 19;
+		const ClflushoptIsSupported: u32 = 1 << 23;
+		const ClwbIsSupported: u32 = 1 << 24;
+		
+		#[inline(always)]
+		fn structured_extended_feature_flags() -> u32
+		{
+			let (maximum_leaf, _ebx, _ecx, _edx) = cpuid(0, 0);
+			if maximum_leaf < StructuredExtendedFeatureFlagsLeaf
+			{
+				0
+			}
+			else
+			{
+				let (_eax, ebx, _ecx, _edx) = cpuid(StructuredExtendedFeatureFlagsLeaf, 0);
+				ebx
+			}
+		}
+		
+		match self
+		{
+			Clwb => structured_extended_feature_flags() & ClwbIsSupported != 0,
+			
+			Clflushopt => structured_extended_feature_flags() & ClflushoptIsSupported != 0,
+			
+			Clflush =>
+			{
+				let (_eax, _ebx, _ecx, edx) = cpuid(1, 0);
+				edx & ClflushIsSupported != 0
+			}
+			
+			Unavailable => true,
+		}
+	}
+	
+	/// Is this instruction supported by the CPU this process is running on?
+	#[cfg(not(any(target_arch = "x86_64", target_arch = "x86")))]
+	#[inline(always)]
+	pub fn is_available(self) -> bool
+	{
+		true
+	}
+	
+	/// Writes back the cache line starting at `address_rounded_down_to_start_of_cache_line`.
+	///
+	/// Executing an instruction that is not available will cause an illegal instruction fault.
+	#[inline(always)]
+	pub fn write_back(self, address_rounded_down_to_start_of_cache_line: *mut u8)
+	{
+		use self::CacheLineWriteBack::*;
+		
+		match self
+		{
+			#[cfg(any(target_arch = "x86_64", target_arch = "x86"))] Clwb => clwb(address_rounded_down_to_start_of_cache_line),
+			#[cfg(any(target_arch = "x86_64", target_arch = "x86"))] Clflushopt => clflushopt(address_rounded_down_to_start_of_cache_line),
+			#[cfg(any(target_arch = "x86_64", target_arch = "x86"))] Clflush => clflush(address_rounded_down_to_start_of_cache_line),
+			#[cfg(target_arch = "aarch64")] DcCvac => dc_cvac(address_rounded_down_to_start_of_cache_line),
+			Unavailable => (),
+		}
+	}
+	
+	/// Are write backs with this instruction ordered by locked read-modify-write instructions (eg compare-and-swap, fetch-add, exchange)?
+	///
+	/// True for `clwb` and `clflushopt`.
+	#[inline(always)]
+	pub fn is_ordered_by_locked_read_modify_write_operations(self) -> bool
+	{
+		use self::CacheLineWriteBack::*;
+		
+		match self
+		{
+			#[cfg(any(target_arch = "x86_64", target_arch = "x86"))] Clwb => true,
+			#[cfg(any(target_arch = "x86_64", target_arch = "x86"))] Clflushopt => true,
+			#[cfg(any(target_arch = "x86_64", target_arch = "x86"))] Clflush => false,
+			#[cfg(target_arch = "aarch64")] DcCvac => false,
+			Unavailable => false,
+		}
+	}
+	
+	#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+	#[inline(always)]
+	fn detect() -> Self
+	{
+		use self::CacheLineWriteBack::*;
+		
+		if Clwb.is_available()
+		{
+			Clwb
+		}
+		else if Clflushopt.is_available()
+		{
+			Clflushopt
+		}
+		else if Clflush.is_available()
+		{
+			Clflush
+		}
+		else
+		{
+			Unavailable
+		}
+	}
+	
+	#[cfg(target_arch = "aarch64")]
+	#[inline(always)]
+	fn detect() -> Self
+	{
+		CacheLineWriteBack::DcCvac
+	}
+	
+	#[cfg(not(any(target_arch = "x86_64", target_arch = "x86", target_arch = "aarch64")))]
+	#[inline(always)]
+	fn detect() -> Self
+	{
+		CacheLineWriteBack::Unavailable
+	}
+}
@@ -0,0 +1,84 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Returns the (cached) size of a cache line of the CPU this process is running on.
+///
+/// On x86 and x86_64, this is the `clflush` line size reported by `cpuid`; on AArch64, it is the smallest data cache line size reported by the `CTR_EL0` register.
+/// Falls back to `CacheLineSize` if the CPU does not report a size.
+#[inline(always)]
+pub fn cache_line_size() -> usize
+{
+	const UnassignedCacheLineSize: usize = 0;
+	
+	static mut DetectedCacheLineSize: usize = UnassignedCacheLineSize;
+	
+	let cache_line_size = unsafe { DetectedCacheLineSize };
+	
+	// Technically, we ought to use an Atomic compare-and-swap, but the cost isn't worthwhile.
+	if cache_line_size == UnassignedCacheLineSize
+	{
+		let cache_line_size = detect_cache_line_size();
+		debug_assert!(cache_line_size.is_power_of_two(), "cache_line_size '{}' is not a power of two", cache_line_size);
+		unsafe { DetectedCacheLineSize = cache_line_size };
+		cache_line_size
+	}
+	else
+	{
+		cache_line_size
+	}
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+#[inline(always)]
+fn detect_cache_line_size() -> usize
+{
+	const ClflushIsSupported: u32 = 1 << 19;
+	
+	let (_eax, ebx, _ecx, edx) = cpuid(1, 0);
+	
+	// Bits 15:8 of ebx are the clflush line size in 8-byte quad words.
+	let clflush_line_size_in_quad_words = ((ebx >> 8) & 0xFF) as usize;
+	
+	if edx & ClflushIsSupported != 0 && clflush_line_size_in_quad_words != 0
+	{
+		clflush_line_size_in_quad_words * 8
+	}
+	else
+	{
+		CacheLineSize
+	}
+}
+
+#[cfg(target_arch = "aarch64")]
+#[inline(always)]
+fn detect_cache_line_size() -> usize
+{
+	let cache_type_register: u64;
+	
+	unsafe
+	{
+		asm!
+		(
+			"mrs %0, ctr_el0"
+			:
+				"=r" (cache_type_register)
+			:
+			:
+			:
+				"volatile"
+		)
+	}
+	
+	// Bits 19:16 (DminLine) are the log2 of the number of 4-byte words in the smallest data cache line.
+	let smallest_data_cache_line_size_log2_in_words = (cache_type_register >> 16) & 0xF;
+	
+	4 << smallest_data_cache_line_size_log2_in_words
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "x86", target_arch = "aarch64")))]
+#[inline(always)]
+fn detect_cache_line_size() -> usize
+{
+	CacheLineSize
+}
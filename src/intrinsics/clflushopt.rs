@@ -11,36 +11,28 @@
 /// Executions of the `clflushopt` instruction are ordered with respect to fence instructions and to locked read-modify-write instructions; they are also ordered with respect to the following accesses to the cache line being invalidated: writes, executions of `clflush`, and executions of `clflushopt`.
 /// They are not ordered with respect to writes, executions of `clflush`, or executions of `clflushopt` that access other cache lines; to enforce ordering with such an operation, software can insert an `sfence` instruction between `clflush` and that operation.
 ///
-/// To ensure this intrinsic is available, use `cargo rustc -- -C target-feature=+clflushopt`.
-///
-/// This intrinsic is not available for Intel architectures before Skylake and AMD architectures before Zen v1.
+/// This intrinsic is not available for Intel architectures before Skylake and AMD architectures before Zen v1; executing it on such a CPU causes an illegal instruction fault.
+/// Use `CacheLineWriteBack::Clflushopt.is_available()` or `CacheLineWriteBack::best_available()` to check at runtime.
 ///
 /// Executing `clflushopt` will nearly always a TSX abort.
 #[allow(unused_variables)]
 #[inline(always)]
 pub fn clflushopt(address: *mut u8)
 {
-	#[cfg(all(target_feature = "clflushopt", any(target_arch = "x86_64", target_arch = "x86")))]
+	// There is a LLVM intrinsic for `clflushopt` (`llvm.x86.clflushopt`) but it can only be used when compiling with `-C target-feature=+clflushopt`.
+	
+	#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+	unsafe
 	{
-		extern
-		{
-			#[link_name = "llvm.x86.clflushopt"]
-			pub fn llvm_x86_clflushopt(a: *mut i8) -> ();
-		}
-		
-		unsafe { llvm_x86_clflushopt(address as *mut i8) }
-		
-		/*
-			asm!
-			(
-				".byte 0x66; clflush %0"
-				:
-					"+m" (address)
-				:
-				:
-				:
-					"volatile"
-			);
-		*/
+		asm!
+		(
+			".byte 0x66; clflush %0"
+			:
+				"+m" (*address)
+			:
+			:
+			:
+				"volatile"
+		)
 	}
 }
@@ -15,9 +15,8 @@
 /// `clwb` is implicitly ordered with older stores executed by the logical processor to the same address.
 /// For usages that require only writing back modified data from cache lines to memory (do not require the line to be invalidated), and expect to subsequently access the data, software is recommended to use `clwb` (with appropriate fencing) instead of `clflushopt` or `clflush` for improved performance.
 ///
-/// To ensure this intrinsic is available, use `cargo rustc -- -C target-feature=+clwb`.
-///
-/// This intrinsic is not available for Intel architectures before Cannonlake or Icelake.
+/// This intrinsic is not available for Intel architectures before Cannonlake or Icelake; executing it on such a CPU causes an illegal instruction fault.
+/// Use `CacheLineWriteBack::Clwb.is_available()` or `CacheLineWriteBack::best_available()` to check at runtime.
 ///
 /// Executing `clwb` will nearly always cause a TSX abort.
 //noinspection SpellCheckingInspection
@@ -27,7 +26,7 @@ pub fn clwb(address: *mut u8)
 {
 	// There is a LLVM intrinsic for `clwb` (`llvm.x86.clwb`) but it may not be exposed by Rust.
 	
-	#[cfg(all(target_feature = "clwb", any(target_arch = "x86_64", target_arch = "x86")))]
+	#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 	unsafe
 	{
 		asm!
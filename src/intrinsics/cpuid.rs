@@ -0,0 +1,39 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Executes the `cpuid` instruction for `leaf` (`eax`) and `sub_leaf` (`ecx`), returning `(eax, ebx, ecx, edx)`.
+///
+/// Does not check that `leaf` is supported; leaf `0` returns the maximum supported leaf in `eax`.
+///
+/// `cpuid` is a serializing instruction and is slow; results should be cached.
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+#[inline(always)]
+pub fn cpuid(leaf: u32, sub_leaf: u32) -> (u32, u32, u32, u32)
+{
+	let eax: u32;
+	let ebx: u32;
+	let ecx: u32;
+	let edx: u32;
+	
+	unsafe
+	{
+		asm!
+		(
+			"cpuid"
+			:
+				"={eax}" (eax),
+				"={ebx}" (ebx),
+				"={ecx}" (ecx),
+				"={edx}" (edx)
+			:
+				"{eax}" (leaf),
+				"{ecx}" (sub_leaf)
+			:
+			:
+				"volatile"
+		)
+	}
+	
+	(eax, ebx, ecx, edx)
+}
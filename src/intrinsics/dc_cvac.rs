@@ -14,7 +14,7 @@ pub fn dc_cvac(address: *mut u8)
 			"dc cvac, %0"
 			:
 			:
-				"r" (p)
+				"r" (address)
 			:
 				"memory"
 			:
@@ -2,10 +2,13 @@
 // Copyright © 2017 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
 
 
+include!("cache_line_size.rs");
 include!("CacheLineSize.rs");
+include!("CacheLineWriteBack.rs");
 include!("clflush.rs");
 include!("clflushopt.rs");
 include!("clwb.rs");
+include!("cpuid.rs");
 include!("dc_cvac.rs");
 include!("dmb_ish.rs");
 include!("round_address_down_to_start_of_cache_line.rs");
@@ -6,9 +6,11 @@
 #[inline(always)]
 pub fn round_address_down_to_start_of_cache_line(address: *mut u8) -> *mut u8
 {
-	debug_assert_ne!(CacheLineSize, 0, "CacheLineSize can not be zero");
+	let cache_line_size = cache_line_size();
 	
-	const CacheLineFlags: usize = !(CacheLineSize - 1);
+	debug_assert_ne!(cache_line_size, 0, "cache_line_size can not be zero");
 	
-	((address as usize) & CacheLineFlags) as *mut u8
+	let cache_line_flags = !(cache_line_size - 1);
+	
+	((address as usize) & cache_line_flags) as *mut u8
 }
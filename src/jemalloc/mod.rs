@@ -62,6 +62,8 @@ impl<T> MemoryPointerRange<T>
 																	https://stackoverflow.com/questions/16933103/using-tcmalloc-jemalloc-with-custom-memory-pool-manager?noredirect=1&lq=1
 																	http://jemalloc.net/jemalloc.3.html#arenas.create
 																	Bug in compiling with custom prefix (needed to avoid clash with Rust)
+																	Extent hooks can serve an arena's extents from a pool's MappedMemory, but the arena and extent metadata is kept in volatile memory
+																	After a restart, allocations made before can be neither sized nor freed, so an arena can not back a pool heap on its own
 																	
 	jemalloc 4	Maybe if can access MIB functionality directly		Similar issues to jemalloc 5, but might compile without the custom prefix bug (untested)
 	
@@ -45,7 +45,9 @@
 //!
 //! This crate makes extensive use of instructions of modern CPUs, particularly Skylake.
 //!
-//! To ensure these instructions are used, build with `cargo rustc -- -C target-feature=+sse2,+rdrnd,+clflushopt,+clwb`.
+//! The best cache line write back instruction (`clwb`, `clflushopt` or `clflush`) and the cache line size are detected at runtime, so one binary can be used with a mix of CPUs.
+//!
+//! To ensure `rdrnd` is used, build with `cargo rustc -- -C target-feature=+sse2,+rdrnd`.
 //!
 
 
@@ -99,6 +101,9 @@ pub mod memory_map;
 /// 7. Pedro Ramalhete & Andreia Correia argue that (4) does not require a `pfence()` before and a `pfence()` after on x86_64 because read-modify-write instructions (CAS, fetch_add, exchange, etc) ensure order for `clflushopt` and `clwb`.
 pub mod persistent_memory_operations;
 
+/// PMDK-compatible pool set (`PMEMPOOLSET`) files, allowing a pool to span several files or DAX devices.
+pub mod pool_set;
+
 
 include!("Alignment.rs");
 include!("ExtendedNonNull.rs");
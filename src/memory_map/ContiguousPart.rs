@@ -0,0 +1,19 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// One file (or DAX device) to be memory mapped back-to-back with others by `memory_map_contiguously()`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct ContiguousPart<'a>
+{
+	/// Path to a file or DAX device.
+	pub path: &'a Path,
+	
+	/// Offset into the file or DAX device to start mapping from.
+	/// Must be a multiple of the page size (and, for a DAX device, of its alignment).
+	pub offset: u64,
+	
+	/// Number of bytes to map.
+	/// Must be a non-zero multiple of the alignment passed to `memory_map_contiguously()`.
+	pub length: usize,
+}
@@ -42,6 +42,20 @@ quick_error!
 			from()
 		}
 		
+		/// A part of a pool set is too small to memory map once rounded down to the alignment (and less any header).
+		PartIsTooSmall(path: PathBuf)
+		{
+			description("part is too small")
+			display("Could not memory map because part '{:?}' is too small", path)
+		}
+		
+		/// A `MemoryMapAddress::Mandatory` address range is already (perhaps partly) mapped.
+		AddressOccupied(address: usize, size: usize)
+		{
+			description("address range is occupied")
+			display("Could not memory map because the address range of size '{}' at '0x{:X}' is occupied", size, address)
+		}
+		
 		/// The `mmap` libc call failed.
 		MMapFailed
 		{
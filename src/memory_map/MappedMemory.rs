@@ -21,6 +21,14 @@ impl Drop for MappedMemory
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
 impl MappedMemory
 {
+	/// Reserves `size` bytes of inaccessible anonymous memory, eg to keep anything else from being mapped in that range until this is dropped.
+	/// As for `memory_map_contiguously()`, an existing mapping is never replaced.
+	#[inline(always)]
+	pub fn reserve(size: usize, memory_map_address: MemoryMapAddress) -> Result<Self, CouldNotMemoryMapError>
+	{
+		Ok(MappedMemory(reserve_contiguous_range(size, memory_map_page_size(), memory_map_address)?.to_non_null(), size, false))
+	}
+	
 	/// Memory-mapped address.
 	#[inline(always)]
 	pub fn to_non_null(&self) -> NonNull<u8>
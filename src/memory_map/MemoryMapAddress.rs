@@ -18,7 +18,7 @@ pub enum MemoryMapAddress
 	},
 	
 	/// Memory map at this address and no other; will round it up to satisfy alignment.
-	/// Memory mapping will fail if it can not be allocated at this address.
+	/// Memory mapping will fail with `CouldNotMemoryMapError::AddressOccupied` if any of the address range is already mapped; an existing mapping is never replaced.
 	Mandatory
 	{
 		/// Unaligned address to use.
@@ -54,7 +54,10 @@ impl MemoryMapAddress
 			
 			Mandatory { unaligned_address } =>
 			{
-				#[cfg(any(target_os = "android", target_os = "linux"))] let flags = mmap_flags | MAP_FIXED;
+				// Linux 4.17 introduces `MAP_FIXED_NOREPLACE`, which fails with `EEXIST` rather than replacing an existing mapping as `MAP_FIXED` does.
+				// Older kernels ignore it, and treat the address as a hint; `was_honoured()` detects the kernel using another address instead.
+				#[cfg(any(target_os = "android", target_os = "linux"))] const MAP_FIXED_NOREPLACE: MMapFlags = 0x100000;
+				#[cfg(any(target_os = "android", target_os = "linux"))] let flags = mmap_flags | MAP_FIXED_NOREPLACE;
 				#[cfg(target_os = "freebsd")] let flags = mmap_flags | MAP_FIXED | MAP_EXCL;
 				
 				let aligned_address = unaligned_address.round_up_to_alignment(alignment);
@@ -63,4 +66,17 @@ impl MemoryMapAddress
 			}
 		}
 	}
+	
+	/// Was memory mapped at the address `address()` gave, if that address was `Mandatory`?
+	/// If not, `mapped_address .. mapped_address + size` must be un-mapped, and the address range treated as occupied.
+	#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+	#[inline(always)]
+	pub fn was_honoured(&self, address: *mut c_void, mapped_address: *mut c_void) -> bool
+	{
+		match *self
+		{
+			MemoryMapAddress::Mandatory { .. } => mapped_address == address,
+			_ => true,
+		}
+	}
 }
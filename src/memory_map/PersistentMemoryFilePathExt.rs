@@ -17,13 +17,10 @@ pub trait PersistentMemoryFilePathExt
 {
 	/// Memory map (`mmap`) persistent memory file.
 	/// Returns pointer to mapped address and boolean indicating whether the Linux 4.15+ `MAP_SYNC` flag was used successfully.
-	/// If a minimum_address_hint_and_alignment is supplied, then `MAP_FIXED` is used to try to fix the mapping at a particular location.
+	/// If `memory_map_address` is `MemoryMapAddress::Mandatory`, the mapping is fixed at that address, and fails with `CouldNotMemoryMapError::AddressOccupied` rather than replace an existing mapping.
 	/// `offset` should normally be zero.
 	///
 	/// When the result is dropped the memory mapping will be un-mapped using `munmap`.
-	///
-	/// Memory mapping at fixed addresses is not thread-safe (`mmap` can silently replace existing mappings).
-	///
 	#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
 	#[inline(always)]
 	fn memory_map(&self, read_only: bool, memory_map_address: MemoryMapAddress, offset: u64, alignment_if_not_dax_device: usize) -> Result<MappedMemory, CouldNotMemoryMapError>;
@@ -71,50 +68,14 @@ impl PersistentMemoryFilePathExt for Path
 			PROT_READ | PROT_WRITE
 		};
 		let memory_map_file = OpenOptions::new().read(true).write(!read_only).open(self)?;
-		let memory_map_file_descriptor = memory_map_file.as_raw_fd();
-		
-		// Linux 4.15 (28th January 2018) introduces the `MAP_SYNC` and `MAP_SHARED_VALIDATE` mmap_flags to `mmap(2)`, a mechanism that implements synchronous page faults for DAX mappings to make flushing of DAX mappings possible from userspace so that they can be flushed on finer than page granularity and also avoid the overhead of a syscall.
-		// It arranges for any filesystem metadata updates that may be required to satisfy a write fault to also be flushed ("on disk") before the kernel returns to userspace from the fault handler.
-		// Effectively every write-fault that dirties metadata completes an `fsync()` before returning from the fault handler.
-		// The new `MAP_SHARED_VALIDATE` mapping type guarantees that the `MAP_SYNC` flag is validated as supported by the filesystem's `mmap()` implementation.
-		#[cfg(any(target_os = "android", target_os = "linux"))]
-		{
-			const MAP_SYNC: i32 = 0x80000;
-			const MAP_SHARED_VALIDATE: i32 = 0x03;
-			let new_linux_flags = mmap_flags | MAP_SHARED_VALIDATE | MAP_SYNC;
-			
-			let address = unsafe { mmap(address, size, protection, new_linux_flags, memory_map_file_descriptor, mmap_offset) };
-			if address == MAP_FAILED
-			{
-				// Try again without MAP_SHARED_VALIDATE | MAP_SYNC in case the filesystem does not support them; mmap *does not fail* for invalid or unrecognised flags.
-				let address = unsafe { mmap(address, size, protection, mmap_flags, memory_map_file_descriptor, mmap_offset) };
-				if address == MAP_FAILED
-				{
-					Err(CouldNotMemoryMapError::MMapFailed)
-				}
-				else
-				{
-					Ok(MappedMemory((address as *mut u8).to_non_null(), size, false))
-				}
-			}
-			else
-			{
-				Ok(MappedMemory((address as *mut u8).to_non_null(), size, true))
-			}
-		}
 		
-		#[cfg(not(any(target_os = "android", target_os = "linux")))]
+		let (mapped_address, was_mapped_with_linux_sync_flag) = memory_map_file_descriptor(address, size, protection, mmap_flags, memory_map_file.as_raw_fd(), mmap_offset)?;
+		if !memory_map_address.was_honoured(address, mapped_address)
 		{
-			let address = unsafe { mmap(address, size, protection, mmap_flags, memory_map_file_descriptor, mmap_offset) };
-			if address == MAP_FAILED
-			{
-				Err(CouldNotMemoryMapError::MMapFailed)
-			}
-			else
-			{
-				Ok(MappedMemory((address as *mut u8).to_non_null(), size, false))
-			}
+			unsafe { munmap(mapped_address, size) };
+			return Err(CouldNotMemoryMapError::AddressOccupied(address as usize, size))
 		}
+		Ok(MappedMemory((mapped_address as *mut u8).to_non_null(), size, was_mapped_with_linux_sync_flag))
 	}
 	
 	#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
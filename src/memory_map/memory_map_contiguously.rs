@@ -0,0 +1,120 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Memory maps several files (or DAX devices) back-to-back into one contiguous, aligned range of virtual memory.
+///
+/// The whole range is first reserved with an inaccessible anonymous mapping, and then each part is mapped over its slice of the reservation using `MAP_FIXED`; nothing else can be mapped in between the parts.
+///
+/// `alignment` must be a power of two, at least `memory_map_page_size()` and at least the alignment of any DAX device amongst the parts.
+///
+/// The result was mapped with the Linux 4.15+ `MAP_SYNC` flag only if every part was.
+///
+/// When the result is dropped the whole range will be un-mapped using `munmap`.
+///
+/// The reservation never replaces an existing mapping, even for a `MemoryMapAddress::Mandatory` address, so other threads can safely memory map at the same time.
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+#[inline(always)]
+pub fn memory_map_contiguously(parts: &[ContiguousPart], read_only: bool, memory_map_address: MemoryMapAddress, alignment: usize) -> Result<MappedMemory, CouldNotMemoryMapError>
+{
+	assert!(!parts.is_empty(), "parts can not be empty");
+	assert!(alignment.is_power_of_two(), "alignment '{}' is not a power of two", alignment);
+	assert!(alignment >= memory_map_page_size(), "alignment '{}' is less than memory_map_page_size '{}'", alignment, memory_map_page_size());
+	
+	let mut size = 0;
+	for part in parts.iter()
+	{
+		assert_ne!(part.length, 0, "part '{:?}' has a length of zero", part.path);
+		assert_eq!(part.length % alignment, 0, "part '{:?}' has a length '{}' which is not a multiple of alignment '{}'", part.path, part.length, alignment);
+		
+		size += part.length;
+	}
+	
+	// Until every part has been mapped, dropping this un-maps the reservation (and any parts mapped so far).
+	let mut mapped_memory = MappedMemory(reserve_contiguous_range(size, alignment, memory_map_address)?.to_non_null(), size, true);
+	
+	let protection = if read_only
+	{
+		PROT_READ
+	}
+	else
+	{
+		PROT_READ | PROT_WRITE
+	};
+	
+	let mut part_address = mapped_memory.as_ptr() as usize;
+	for part in parts.iter()
+	{
+		let memory_map_file = OpenOptions::new().read(true).write(!read_only).open(part.path)?;
+		
+		let (mapped_address, was_mapped_with_linux_sync_flag) = memory_map_file_descriptor(part_address as *mut c_void, part.length, protection, MAP_SHARED | MAP_FIXED, memory_map_file.as_raw_fd(), part.offset as i64)?;
+		if mapped_address as usize != part_address
+		{
+			return Err(CouldNotMemoryMapError::MMapFailed)
+		}
+		
+		mapped_memory.2 = mapped_memory.2 && was_mapped_with_linux_sync_flag;
+		part_address += part.length;
+	}
+	
+	Ok(mapped_memory)
+}
+
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+#[inline(always)]
+fn reserve_contiguous_range(size: usize, alignment: usize, memory_map_address: MemoryMapAddress) -> Result<*mut u8, CouldNotMemoryMapError>
+{
+	const NoFileDescriptor: c_int = -1;
+	const NoOffset: i64 = 0;
+	
+	#[cfg(any(target_os = "android", target_os = "linux"))] const ReservationFlags: MMapFlags = MAP_PRIVATE | MAP_ANONYMOUS | MAP_NORESERVE;
+	#[cfg(target_os = "freebsd")] const ReservationFlags: MMapFlags = MAP_PRIVATE | MAP_ANONYMOUS;
+	
+	let (address, mmap_flags) = memory_map_address.address(size, alignment, ReservationFlags)?;
+	
+	// Without a (suitably aligned) address to use, reserve enough extra to be able to align the start of the range.
+	let reservation_size = if address.is_null()
+	{
+		size + alignment
+	}
+	else
+	{
+		size
+	};
+	
+	let reservation_address = unsafe { mmap(address, reservation_size, PROT_NONE, mmap_flags, NoFileDescriptor, NoOffset) };
+	if reservation_address == MAP_FAILED
+	{
+		return Err(mmap_failed(address, reservation_size))
+	}
+	
+	if !memory_map_address.was_honoured(address, reservation_address)
+	{
+		unsafe { munmap(reservation_address, reservation_size) };
+		return Err(CouldNotMemoryMapError::AddressOccupied(address as usize, size))
+	}
+	
+	let reservation_inclusive_start_address = reservation_address as usize;
+	let reservation_exclusive_end_address = reservation_inclusive_start_address + reservation_size;
+	let aligned_inclusive_start_address = reservation_inclusive_start_address.round_up_to_alignment(alignment);
+	let aligned_exclusive_end_address = aligned_inclusive_start_address + size;
+	
+	if aligned_exclusive_end_address > reservation_exclusive_end_address
+	{
+		// A hint was not honoured, and the address given instead is not aligned.
+		unsafe { munmap(reservation_address, reservation_size) };
+		return Err(CouldNotMemoryMapError::CouldNotFindAContiguousRegionToMemoryMapInto(size as u64, alignment))
+	}
+	
+	if aligned_inclusive_start_address != reservation_inclusive_start_address
+	{
+		unsafe { munmap(reservation_address, aligned_inclusive_start_address - reservation_inclusive_start_address) };
+	}
+	
+	if aligned_exclusive_end_address != reservation_exclusive_end_address
+	{
+		unsafe { munmap(aligned_exclusive_end_address as *mut c_void, reservation_exclusive_end_address - aligned_exclusive_end_address) };
+	}
+	
+	Ok(aligned_inclusive_start_address as *mut u8)
+}
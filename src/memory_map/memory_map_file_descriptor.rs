@@ -0,0 +1,39 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// `mmap`s a file descriptor, trying the Linux 4.15+ `MAP_SYNC` flag first.
+/// Returns the mapped address and whether the `MAP_SYNC` flag was used successfully.
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+#[inline(always)]
+fn memory_map_file_descriptor(address: *mut c_void, size: usize, protection: c_int, mmap_flags: MMapFlags, file_descriptor: c_int, offset: i64) -> Result<(*mut c_void, bool), CouldNotMemoryMapError>
+{
+	// Linux 4.15 (28th January 2018) introduces the `MAP_SYNC` and `MAP_SHARED_VALIDATE` mmap_flags to `mmap(2)`, a mechanism that implements synchronous page faults for DAX mappings to make flushing of DAX mappings possible from userspace so that they can be flushed on finer than page granularity and also avoid the overhead of a syscall.
+	// It arranges for any filesystem metadata updates that may be required to satisfy a write fault to also be flushed ("on disk") before the kernel returns to userspace from the fault handler.
+	// Effectively every write-fault that dirties metadata completes an `fsync()` before returning from the fault handler.
+	// The new `MAP_SHARED_VALIDATE` mapping type guarantees that the `MAP_SYNC` flag is validated as supported by the filesystem's `mmap()` implementation.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	{
+		const MAP_SYNC: i32 = 0x80000;
+		const MAP_SHARED_VALIDATE: i32 = 0x03;
+		let new_linux_flags = mmap_flags | MAP_SHARED_VALIDATE | MAP_SYNC;
+		
+		let mapped_address = unsafe { mmap(address, size, protection, new_linux_flags, file_descriptor, offset) };
+		if mapped_address != MAP_FAILED
+		{
+			return Ok((mapped_address, true))
+		}
+		
+		// Try again without MAP_SHARED_VALIDATE | MAP_SYNC in case the filesystem does not support them; mmap *does not fail* for invalid or unrecognised flags.
+	}
+	
+	let mapped_address = unsafe { mmap(address, size, protection, mmap_flags, file_descriptor, offset) };
+	if mapped_address == MAP_FAILED
+	{
+		Err(mmap_failed(address, size))
+	}
+	else
+	{
+		Ok((mapped_address, false))
+	}
+}
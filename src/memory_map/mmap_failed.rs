@@ -0,0 +1,18 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// The error for an `mmap` of `size` bytes at `address` that has just failed; `EEXIST` means `MAP_FIXED_NOREPLACE` found the address range occupied.
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+#[inline(always)]
+fn mmap_failed(address: *mut c_void, size: usize) -> CouldNotMemoryMapError
+{
+	if io::Error::last_os_error().raw_os_error() == Some(EEXIST)
+	{
+		CouldNotMemoryMapError::AddressOccupied(address as usize, size)
+	}
+	else
+	{
+		CouldNotMemoryMapError::MMapFailed
+	}
+}
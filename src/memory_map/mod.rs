@@ -9,34 +9,43 @@ use super::dax::DaxDevicePathExt;
 #[cfg(unix)] use ::libc::_SC_PAGESIZE;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::c_int;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::c_void;
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::EEXIST;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::MAP_ANONYMOUS;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::MAP_FAILED;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::MAP_FIXED;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::MAP_NORESERVE;
 #[cfg(target_os = "freebsd")] use ::libc::MAP_EXCL;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::MAP_PRIVATE;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::MAP_SHARED;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::mmap;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::munmap;
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::PROT_NONE;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::PROT_READ;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::libc::PROT_WRITE;
 #[cfg(unix)] use ::libc::sysconf;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::collections::BTreeMap;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::fs::File;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::fs::OpenOptions;
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::io;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::io::BufRead;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::io::BufReader;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::os::unix::io::AsRawFd;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::path::Path;
+use ::std::path::PathBuf;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::ptr::NonNull;
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))] use ::std::ptr::null_mut;
 
 
+include!("ContiguousPart.rs");
 include!("CouldNotMemoryMapError.rs");
 include!("find_lowest_unoccupied_address_in_process_map.rs");
 include!("find_random_memory_map_unoccupied_address.rs");
 include!("MappedMemory.rs");
+include!("memory_map_contiguously.rs");
+include!("memory_map_file_descriptor.rs");
 include!("memory_map_page_size.rs");
 include!("MemoryMapAddress.rs");
+include!("mmap_failed.rs");
 include!("MMapFlags.rs");
 include!("OccupiedMemoryMapParseError.rs");
 include!("PersistentMemoryFilePathExt.rs");
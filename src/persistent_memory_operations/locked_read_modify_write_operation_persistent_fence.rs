@@ -7,21 +7,8 @@
 #[inline(always)]
 pub fn locked_read_modify_write_operation_persistent_fence()
 {
-	#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+	if !CacheLineWriteBack::best_available().is_ordered_by_locked_read_modify_write_operations()
 	{
-		if cfg!(target_feature = "clwb")
-		{
-			// Do nothing
-		}
-		else if cfg!(target_feature = "clflushopt")
-		{
-			// Do nothing
-		}
-		else if cfg!(target_feature = "sse2")
-		{
-			persistent_fence();
-		}
+		persistent_fence()
 	}
-
-	#[cfg(target_arch = "aarch64")] persistent_fence();
 }
@@ -15,7 +15,7 @@
 /// 	* A *Release* `store`
 ///     * A locked read-modify-write instructions, such as Compare-and-Swap, Fetch-Add, Exchange, which has *Acquire-Release* (or presumably stronger) memory ordering\*
 ///
-/// Does not seem to be needed if `persistent_write_back()` is implemented using the `clflush()` intrinsic, which is the least efficient, oldest and worst choice for implementing `persistent_write_back()`.
+/// Does not seem to be needed if `persistent_write_back()` is implemented using the `clflush()` intrinsic, which is the least efficient, oldest and worst choice for implementing `persistent_write_back()`; it is nonetheless always issued, as it also orders *Release* stores and non-temporal stores.
 ///
 /// \* Pedro Ramalhete & Andreia Correia argue that the preceding and following `persistent_fence()` are not needed on x86_64 because locked read-modify-write instructions ensure order for `clflushopt()` and `clwb()`.
 #[inline(always)]
@@ -26,4 +26,6 @@ pub fn persistent_fence()
 	#[cfg(all(target_feature = "sse2", any(target_arch = "x86_64", target_arch = "x86")))] sfence();
 	
 	#[cfg(target_arch = "aarch64")] dmb_ish();
+	
+	#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))] ::cto_pool::record_fence();
 }
@@ -20,4 +20,6 @@ pub fn persistent_sync()
 	#[cfg(all(target_feature = "sse2", any(target_arch = "x86_64", target_arch = "x86")))] sfence();
 	
 	#[cfg(target_arch = "aarch64")] dmb_ish();
+	
+	#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))] ::cto_pool::record_persistent_sync();
 }
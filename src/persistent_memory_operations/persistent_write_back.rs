@@ -6,6 +6,8 @@
 ///
 /// Initiates the write-back of the cache-line associated with address to persistent memory; on a 64-bit x86_64 chip, this is 64 bytes.
 ///
+/// Uses the best instruction supported by the CPU this process is running on (see `CacheLineWriteBack::best_available()`); on x86_64, this is `clwb`, `clflushopt` or `clflush`.
+///
 /// Non-blocking.
 ///
 /// * Use this immediately after a *Relaxed* `store`.
@@ -14,29 +16,13 @@
 /// * Use this immediately after all locked read-modify-write instructions, such as Compare-and-Swap, Fetch-Add, Exchange, etc (followed by a `persistent_fence()`\*) which have *Acquire-Release* (or presumably stronger) memory ordering; precede the locked read-modify-write instructions with a `persistent_fence()`\*.
 ///
 /// \* Pedro Ramalhete & Andreia Correia argue that the preceding and following `persistent_fence()` are not needed on x86_64 because locked read-modify-write instructions ensure order for `clflushopt()` and `clwb()`.
-#[allow(unused_variables)]
 #[inline(always)]
 pub fn persistent_write_back(address: *mut u8)
 {
 	let address_rounded_down_to_start_of_cache_line = round_address_down_to_start_of_cache_line(address);
 	
-	#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
-	{
-		if cfg!(target_feature = "clwb")
-		{
-			#[cfg(target_feature = "clwb")] clwb(address_rounded_down_to_start_of_cache_line)
-		}
-		else if cfg!(target_feature = "clflushopt")
-		{
-			#[cfg(target_feature = "clflushopt")] clflushopt(address_rounded_down_to_start_of_cache_line)
-		}
-		else if cfg!(target_feature = "sse2")
-		{
-			// Be aware that this ALSO does persistent_fence(), and so isn't equivalent to `clwb` or `clflushopt`.
-			// However, we do not optimize for this case. All chips from Skylake onwards support `clflushopt`, and persistent memory NVDIMMs do not effectively exist for pre-Skylake.
-			#[cfg(target_feature = "sse2")] clflush(address_rounded_down_to_start_of_cache_line)
-		}
-	}
+	#[cfg(any(feature = "crash-simulation", feature = "persistency-check"))] ::cto_pool::record_write_back(address_rounded_down_to_start_of_cache_line as usize);
 	
-	#[cfg(target_arch = "aarch64")] dc_cvac(address_rounded_down_to_start_of_cache_line);
+	// Be aware that `clflush` ALSO does persistent_fence(), and so isn't equivalent to `clwb` or `clflushopt`.
+	CacheLineWriteBack::best_available().write_back(address_rounded_down_to_start_of_cache_line)
 }
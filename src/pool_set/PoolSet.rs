@@ -0,0 +1,178 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A parsed PMDK-compatible pool set file.
+///
+/// A pool set file looks like:-
+///
+/// ```text
+/// PMEMPOOLSET
+/// OPTION SINGLEHDR
+/// 100G /mnt/pmem0/pool.part0
+/// 100G /mnt/pmem1/pool.part1
+/// AUTO /dev/dax0.0
+///
+/// REPLICA
+/// 300G /mnt/pmem2/pool.replica
+/// ```
+///
+/// * The first line (ignoring comments, which start with `#`, and blank lines) must be `PMEMPOOLSET`;
+/// * `OPTION` lines must come before any parts;
+/// * Each part line is a size followed by an absolute path; sizes can have a suffix (`K`, `KiB`, `KB`, `M`, etc) or be `AUTO` (for DAX devices);
+/// * Each `REPLICA` line starts a new replica; the parts before the first `REPLICA` line are the master replica.
+///
+/// Remote replicas (`REPLICA <node> <pool set file>`) are not supported.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct PoolSet
+{
+	headers: PoolSetHeaders,
+	replicas: Vec<PoolSetReplica>,
+}
+
+impl PoolSet
+{
+	/// Signature that must be the first line of a pool set file.
+	pub const Signature: &'static str = "PMEMPOOLSET";
+	
+	/// Parses a pool set file.
+	#[inline(always)]
+	pub fn parse<R: BufRead>(reader: R) -> Result<Self, PoolSetParseError>
+	{
+		use self::PoolSetParseError::*;
+		
+		let mut seen_signature = false;
+		let mut headers = PoolSetHeaders::PerPart;
+		let mut replicas = Vec::new();
+		let mut parts = Vec::new();
+		let mut last_line_number = 0;
+		
+		for (index, line) in reader.lines().enumerate()
+		{
+			let line_number = index + 1;
+			last_line_number = line_number;
+			
+			let line = line?;
+			let line = match line.find('#')
+			{
+				None => &line[..],
+				Some(comment_starts_at) => &line[.. comment_starts_at],
+			}.trim();
+			
+			if line.is_empty()
+			{
+				continue
+			}
+			
+			if !seen_signature
+			{
+				if line == Self::Signature
+				{
+					seen_signature = true;
+					continue
+				}
+				return Err(MissingSignature)
+			}
+			
+			let mut fields = line.split_whitespace();
+			match fields.next().unwrap()
+			{
+				"OPTION" =>
+				{
+					if !replicas.is_empty() || !parts.is_empty()
+					{
+						return Err(OptionAfterParts(line_number))
+					}
+					
+					headers = match (fields.next(), fields.next())
+					{
+						(Some("SINGLEHDR"), None) => PoolSetHeaders::SingleHeader,
+						(Some("NOHDRS"), None) => PoolSetHeaders::NoHeaders,
+						_ => return Err(InvalidOption(line_number, line.to_owned())),
+					};
+				}
+				
+				"REPLICA" =>
+				{
+					if fields.next().is_some()
+					{
+						return Err(RemoteReplicasAreNotSupported(line_number))
+					}
+					
+					if parts.is_empty()
+					{
+						return Err(EmptyReplica(line_number))
+					}
+					
+					replicas.push(PoolSetReplica::new(replace(&mut parts, Vec::new())));
+				}
+				
+				size => match (PoolSetPartSize::parse(size), fields.next(), fields.next())
+				{
+					(None, _, _) => return Err(InvalidSize(line_number, size.to_owned())),
+					
+					(Some(size), Some(path), None) =>
+					{
+						let path = PathBuf::from(path);
+						if !path.is_absolute()
+						{
+							return Err(RelativePath(line_number, path))
+						}
+						parts.push(PoolSetPart::new(size, path));
+					}
+					
+					_ => return Err(InvalidPart(line_number, line.to_owned())),
+				},
+			}
+		}
+		
+		if !seen_signature
+		{
+			return Err(MissingSignature)
+		}
+		
+		if parts.is_empty()
+		{
+			return Err(EmptyReplica(last_line_number))
+		}
+		replicas.push(PoolSetReplica::new(parts));
+		
+		Ok
+		(
+			Self
+			{
+				headers,
+				replicas,
+			}
+		)
+	}
+	
+	/// How headers are laid out in parts.
+	#[inline(always)]
+	pub fn headers(&self) -> PoolSetHeaders
+	{
+		self.headers
+	}
+	
+	/// The master replica; the one to memory map.
+	#[inline(always)]
+	pub fn master_replica(&self) -> &PoolSetReplica
+	{
+		&self.replicas[0]
+	}
+	
+	/// All replicas, including the master replica (which is first).
+	#[inline(always)]
+	pub fn replicas(&self) -> &[PoolSetReplica]
+	{
+		&self.replicas[..]
+	}
+	
+	/// Memory maps the master replica into one contiguous range of memory.
+	/// See `PoolSetReplica::memory_map()`.
+	#[inline(always)]
+	pub fn memory_map_master_replica(&self, read_only: bool, memory_map_address: MemoryMapAddress, alignment_if_not_dax_device: usize) -> Result<MappedMemory, CouldNotMemoryMapError>
+	{
+		self.master_replica().memory_map(self.headers, read_only, memory_map_address, alignment_if_not_dax_device)
+	}
+}
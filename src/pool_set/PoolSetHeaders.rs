@@ -0,0 +1,35 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// How part headers are laid out in a pool set, as specified by `OPTION` lines.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum PoolSetHeaders
+{
+	/// The default; every part starts with a header.
+	/// The header of every part after the first is skipped when memory mapping, so that the remainder of each part is contiguous with the previous part.
+	PerPart,
+	
+	/// `OPTION SINGLEHDR`; only the first part has a header, and all of every part is contiguous.
+	SingleHeader,
+	
+	/// `OPTION NOHDRS`; no part has a header.
+	NoHeaders,
+}
+
+impl PoolSetHeaders
+{
+	/// The offset into a part at which memory mapping starts, so that the part is contiguous with the previous part.
+	#[inline(always)]
+	pub fn offset_of_part(self, index: usize, alignment: usize) -> u64
+	{
+		if index != 0 && self == PoolSetHeaders::PerPart
+		{
+			alignment as u64
+		}
+		else
+		{
+			0
+		}
+	}
+}
@@ -0,0 +1,76 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+quick_error!
+{
+	/// Could not parse a pool set file.
+	#[derive(Debug)]
+	pub enum PoolSetParseError
+	{
+		/// Input-Output error.
+		InputOutput(cause: io::Error)
+		{
+			cause(cause)
+			description(cause.description())
+			display("Could not parse pool set file because of Input/Output error: {}", cause)
+			from()
+		}
+		
+		/// The first line was not `PMEMPOOLSET`.
+		MissingSignature
+		{
+			description("missing PMEMPOOLSET signature")
+			display("Could not parse pool set file because the first line was not 'PMEMPOOLSET'")
+		}
+		
+		/// An `OPTION` line was not `OPTION SINGLEHDR` or `OPTION NOHDRS`.
+		InvalidOption(line_number: usize, line: String)
+		{
+			description("invalid OPTION")
+			display("Could not parse pool set file because line '{}' is an invalid OPTION '{}'", line_number, line)
+		}
+		
+		/// An `OPTION` line came after a part or a `REPLICA` line.
+		OptionAfterParts(line_number: usize)
+		{
+			description("OPTION after parts")
+			display("Could not parse pool set file because line '{}' is an OPTION after parts", line_number)
+		}
+		
+		/// A part's size could not be parsed.
+		InvalidSize(line_number: usize, size: String)
+		{
+			description("invalid part size")
+			display("Could not parse pool set file because line '{}' has an invalid part size '{}'", line_number, size)
+		}
+		
+		/// A part line was not a size followed by a path.
+		InvalidPart(line_number: usize, line: String)
+		{
+			description("invalid part")
+			display("Could not parse pool set file because line '{}' is not a size followed by a path '{}'", line_number, line)
+		}
+		
+		/// A part's path was not absolute.
+		RelativePath(line_number: usize, path: PathBuf)
+		{
+			description("part path is not absolute")
+			display("Could not parse pool set file because line '{}' has a path '{:?}' which is not absolute", line_number, path)
+		}
+		
+		/// A replica (including the master replica) had no parts.
+		EmptyReplica(line_number: usize)
+		{
+			description("replica has no parts")
+			display("Could not parse pool set file because the replica ending at line '{}' has no parts", line_number)
+		}
+		
+		/// Remote replicas (`REPLICA <node> <pool set file>`) are not supported.
+		RemoteReplicasAreNotSupported(line_number: usize)
+		{
+			description("remote replicas are not supported")
+			display("Could not parse pool set file because line '{}' is a remote REPLICA, which is not supported", line_number)
+		}
+	}
+}
@@ -0,0 +1,38 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A part (file or DAX device) of a replica in a pool set.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct PoolSetPart
+{
+	size: PoolSetPartSize,
+	path: PathBuf,
+}
+
+impl PoolSetPart
+{
+	#[inline(always)]
+	fn new(size: PoolSetPartSize, path: PathBuf) -> Self
+	{
+		Self
+		{
+			size,
+			path,
+		}
+	}
+	
+	/// Size.
+	#[inline(always)]
+	pub fn size(&self) -> PoolSetPartSize
+	{
+		self.size
+	}
+	
+	/// Absolute path to a file or DAX device.
+	#[inline(always)]
+	pub fn path(&self) -> &Path
+	{
+		&self.path
+	}
+}
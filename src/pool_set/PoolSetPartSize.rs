@@ -0,0 +1,56 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// Size of a part in a pool set.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum PoolSetPartSize
+{
+	/// `AUTO`; use all of a DAX device.
+	Automatic,
+	
+	/// A size in bytes.
+	Bytes(u64),
+}
+
+impl PoolSetPartSize
+{
+	/// Parses a size such as `AUTO`, `4096`, `4K`, `4KiB`, `4KB` (which is 4,000 bytes) or `20G`.
+	#[inline(always)]
+	pub fn parse(size: &str) -> Option<Self>
+	{
+		use self::PoolSetPartSize::*;
+		
+		if size == "AUTO"
+		{
+			return Some(Automatic)
+		}
+		
+		let suffix_starts_at = size.find(|character: char| !character.is_digit(10)).unwrap_or(size.len());
+		let (digits, suffix) = size.split_at(suffix_starts_at);
+		
+		let number = match digits.parse::<u64>()
+		{
+			Err(_) => return None,
+			Ok(number) => number,
+		};
+		
+		let multiplier: u64 = match suffix
+		{
+			"" | "B" => 1,
+			"K" | "KiB" => 1 << 10,
+			"M" | "MiB" => 1 << 20,
+			"G" | "GiB" => 1 << 30,
+			"T" | "TiB" => 1 << 40,
+			"P" | "PiB" => 1 << 50,
+			"KB" => 1_000,
+			"MB" => 1_000_000,
+			"GB" => 1_000_000_000,
+			"TB" => 1_000_000_000_000,
+			"PB" => 1_000_000_000_000_000,
+			_ => return None,
+		};
+		
+		number.checked_mul(multiplier).map(Bytes)
+	}
+}
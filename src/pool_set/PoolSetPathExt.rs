@@ -0,0 +1,65 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// An extension trait to make it easier to use a Path to access a pool set file.
+pub trait PoolSetPathExt
+{
+	/// Is this a pool set file (a regular file starting with `PMEMPOOLSET`)?
+	#[inline(always)]
+	fn is_a_pool_set_file(&self) -> bool;
+	
+	/// Parse this pool set file.
+	#[inline(always)]
+	fn parse_pool_set_file(&self) -> Result<PoolSet, PoolSetParseError>;
+	
+	/// Appends a part to the end of this pool set file (ie to its last replica).
+	/// `part_path` must be absolute.
+	#[inline(always)]
+	fn append_part_to_pool_set_file(&self, size: u64, part_path: &Path) -> io::Result<()>;
+}
+
+impl PoolSetPathExt for Path
+{
+	#[inline(always)]
+	fn is_a_pool_set_file(&self) -> bool
+	{
+		match self.metadata()
+		{
+			Ok(metadata) => if !metadata.is_file()
+			{
+				return false
+			},
+			Err(_) => return false,
+		}
+		
+		let mut signature = [0u8; 11];
+		match File::open(self).and_then(|mut file| file.read_exact(&mut signature))
+		{
+			Err(_) => false,
+			Ok(()) => &signature[..] == PoolSet::Signature.as_bytes(),
+		}
+	}
+	
+	#[inline(always)]
+	fn parse_pool_set_file(&self) -> Result<PoolSet, PoolSetParseError>
+	{
+		PoolSet::parse(BufReader::new(File::open(self)?))
+	}
+	
+	#[inline(always)]
+	fn append_part_to_pool_set_file(&self, size: u64, part_path: &Path) -> io::Result<()>
+	{
+		debug_assert!(part_path.is_absolute(), "part_path '{:?}' must be absolute", part_path);
+		
+		let part_path = match part_path.to_str()
+		{
+			Some(part_path) if !part_path.contains(char::is_whitespace) && !part_path.contains('#') => part_path,
+			_ => return Err(io::Error::new(ErrorKind::InvalidInput, "part_path can not be written to a pool set file")),
+		};
+		
+		let mut file = OpenOptions::new().append(true).open(self)?;
+		writeln!(file, "{} {}", size, part_path)?;
+		file.sync_all()
+	}
+}
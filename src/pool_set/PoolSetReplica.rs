@@ -0,0 +1,166 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+/// A replica (either the master replica or a copy of it) in a pool set.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct PoolSetReplica
+{
+	parts: Vec<PoolSetPart>,
+}
+
+impl PoolSetReplica
+{
+	#[inline(always)]
+	fn new(parts: Vec<PoolSetPart>) -> Self
+	{
+		debug_assert!(!parts.is_empty(), "parts can not be empty");
+		
+		Self
+		{
+			parts,
+		}
+	}
+	
+	/// Parts, in the order they are memory mapped.
+	#[inline(always)]
+	pub fn parts(&self) -> &[PoolSetPart]
+	{
+		&self.parts[..]
+	}
+	
+	/// Creates any part files which are not DAX devices, each with its size.
+	///
+	/// Returns `Ok(None)` if any part file already exists (in which case no part files are created).
+	/// Otherwise returns the paths of the part files created, so they can be removed if subsequent initialization fails.
+	#[inline(always)]
+	pub fn create_part_files(&self, mode: mode_t) -> io::Result<Option<Vec<PathBuf>>>
+	{
+		#[inline(always)]
+		fn remove_part_files(created_part_files: Vec<PathBuf>)
+		{
+			for created_part_file in created_part_files.iter()
+			{
+				let _ = remove_file(created_part_file);
+			}
+		}
+		
+		let mut created_part_files = Vec::with_capacity(self.parts.len());
+		
+		for part in self.parts.iter()
+		{
+			if part.path.is_this_a_dax_device()
+			{
+				continue
+			}
+			
+			let size = match part.size
+			{
+				PoolSetPartSize::Automatic =>
+				{
+					remove_part_files(created_part_files);
+					return Err(io::Error::new(ErrorKind::InvalidInput, "AUTO can only be used for DAX devices"))
+				}
+				
+				PoolSetPartSize::Bytes(size) => size,
+			};
+			
+			match OpenOptions::new().write(true).create_new(true).mode(mode as u32).open(&part.path)
+			{
+				Err(error) =>
+				{
+					remove_part_files(created_part_files);
+					
+					return if error.kind() == ErrorKind::AlreadyExists
+					{
+						Ok(None)
+					}
+					else
+					{
+						Err(error)
+					}
+				}
+				
+				Ok(file) =>
+				{
+					created_part_files.push(part.path.clone());
+					
+					if let Err(error) = file.set_len(size)
+					{
+						remove_part_files(created_part_files);
+						return Err(error)
+					}
+				}
+			}
+		}
+		
+		Ok(Some(created_part_files))
+	}
+	
+	/// Memory maps all parts back-to-back into one contiguous range of memory using `memory_map_contiguously()`.
+	///
+	/// The alignment used is the largest of `alignment_if_not_dax_device` and the alignments of any DAX devices; each part is rounded down to a multiple of it.
+	/// A part's size is the size of its file (or, for a DAX device, the smaller of its size and the size of the device), less any header skipped.
+	#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+	#[inline(always)]
+	pub fn memory_map(&self, headers: PoolSetHeaders, read_only: bool, memory_map_address: MemoryMapAddress, alignment_if_not_dax_device: usize) -> Result<MappedMemory, CouldNotMemoryMapError>
+	{
+		let (contiguous_parts, alignment) = self.contiguous_parts(headers, alignment_if_not_dax_device)?;
+		
+		memory_map_contiguously(&contiguous_parts[..], read_only, memory_map_address, alignment)
+	}
+	
+	/// The parts as they would be memory mapped by `memory_map()`, and the alignment that would be used.
+	#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+	#[inline(always)]
+	pub fn contiguous_parts(&self, headers: PoolSetHeaders, alignment_if_not_dax_device: usize) -> Result<(Vec<ContiguousPart>, usize), CouldNotMemoryMapError>
+	{
+		let mut alignment = alignment_if_not_dax_device;
+		for part in self.parts.iter()
+		{
+			if part.path.is_this_a_dax_device()
+			{
+				alignment = max(alignment, part.path.find_dax_device_alignment()?);
+			}
+		}
+		
+		let mut contiguous_parts = Vec::with_capacity(self.parts.len());
+		for (index, part) in self.parts.iter().enumerate()
+		{
+			let offset = headers.offset_of_part(index, alignment);
+			
+			let length = self.part_length(part, offset, alignment)?;
+			if length == 0
+			{
+				return Err(CouldNotMemoryMapError::PartIsTooSmall(part.path.clone()))
+			}
+			
+			contiguous_parts.push
+			(
+				ContiguousPart
+				{
+					path: &part.path,
+					offset,
+					length,
+				}
+			);
+		}
+		
+		Ok((contiguous_parts, alignment))
+	}
+	
+	#[inline(always)]
+	fn part_length(&self, part: &PoolSetPart, offset: u64, alignment: usize) -> Result<usize, CouldNotObtainDaxDeviceStatisticError>
+	{
+		let available = part.path.memory_file_size_for_use_with_memory_map()?;
+		
+		let size = match part.size
+		{
+			PoolSetPartSize::Bytes(size) if part.path.is_this_a_dax_device() => min(size, available),
+			_ => available,
+		};
+		
+		let length = size.saturating_sub(offset) as usize;
+		Ok(length & !(alignment - 1))
+	}
+}
@@ -0,0 +1,37 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+use super::dax::CouldNotObtainDaxDeviceStatisticError;
+use super::dax::DaxDevicePathExt;
+use super::memory_map::ContiguousPart;
+use super::memory_map::CouldNotMemoryMapError;
+use super::memory_map::MappedMemory;
+use super::memory_map::MemoryMapAddress;
+use super::memory_map::PersistentMemoryFilePathExt;
+use super::memory_map::memory_map_contiguously;
+use ::libc::mode_t;
+use ::std::cmp::max;
+use ::std::cmp::min;
+use ::std::fs::File;
+use ::std::fs::OpenOptions;
+use ::std::fs::remove_file;
+use ::std::io;
+use ::std::io::BufRead;
+use ::std::io::BufReader;
+use ::std::io::ErrorKind;
+use ::std::io::Read;
+use ::std::io::Write;
+use ::std::mem::replace;
+use ::std::os::unix::fs::OpenOptionsExt;
+use ::std::path::Path;
+use ::std::path::PathBuf;
+
+
+include!("PoolSet.rs");
+include!("PoolSetHeaders.rs");
+include!("PoolSetParseError.rs");
+include!("PoolSetPart.rs");
+include!("PoolSetPartSize.rs");
+include!("PoolSetPathExt.rs");
+include!("PoolSetReplica.rs");
@@ -0,0 +1,310 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+#![cfg(feature = "crash-simulation")]
+#![feature(allocator_api)]
+
+
+extern crate persistent_memory;
+
+
+use ::persistent_memory::cto_pool::CtoPool;
+use ::persistent_memory::cto_pool::CtoPoolArc;
+use ::persistent_memory::cto_pool::CtoPoolRelocation;
+use ::persistent_memory::cto_pool::CtoPoolTraversal;
+use ::persistent_memory::cto_pool::CtoSafe;
+use ::persistent_memory::cto_pool::TypeFingerprint;
+use ::persistent_memory::cto_pool::arc::CtoStrongArc;
+use ::persistent_memory::cto_pool::block_allocator::BlockAllocator;
+use ::persistent_memory::cto_pool::block_allocator::BlockAllocatorAlloc;
+use ::persistent_memory::cto_pool::block_allocator::BlockSize;
+use ::persistent_memory::cto_pool::boxed::CtoBox;
+use ::persistent_memory::cto_pool::crash_simulation::CrashImageMode;
+use ::persistent_memory::cto_pool::crash_simulation::CrashSimulationPersistence;
+use ::persistent_memory::cto_pool::crash_simulation::simulate_crashes;
+use ::persistent_memory::cto_pool::fetch_and_add_array_queue::Node;
+use ::persistent_memory::cto_pool::fetch_and_add_array_queue::PersistentFetchAndAddArrayQueue;
+use ::persistent_memory::cto_pool::free_list::EliminationArrayLength;
+use ::persistent_memory::cto_pool::free_list::FreeList;
+use ::persistent_memory::cto_pool::free_list::InitializedFreeListElement;
+use ::std::collections::HashSet;
+use ::std::env::temp_dir;
+use ::std::fs::create_dir;
+use ::std::fs::remove_dir_all;
+use ::std::heap::Alloc;
+use ::std::heap::Layout;
+use ::std::io;
+use ::std::mem::forget;
+use ::std::mem::zeroed;
+use ::std::path::Path;
+use ::std::path::PathBuf;
+use ::std::ptr::NonNull;
+use ::std::ptr::write;
+use ::std::ptr::write_bytes;
+use ::std::time::SystemTime;
+use ::std::time::UNIX_EPOCH;
+
+
+const PoolSize: usize = 4 * 1024 * 1024;
+
+const NumberOfValues: u64 = 16;
+
+const CrashEveryNthFence: usize = 4;
+
+const CrashImageModes: [CrashImageMode; 2] = [CrashImageMode::DropUnpersisted, CrashImageMode::RandomlyKeep { seed: 0x5EED }];
+
+type Blocks = Option<BlockAllocatorAlloc<CrashSimulationPersistence>>;
+
+const NumberOfBlocks: usize = 16;
+
+struct Queue
+{
+	free_list: CtoStrongArc<FreeList<Node<u64>>>,
+	queue: CtoStrongArc<PersistentFetchAndAddArrayQueue<u64>>,
+}
+
+impl CtoSafe for Queue
+{
+	#[inline(always)]
+	fn cto_pool_opened(&mut self, cto_pool_arc: &CtoPoolArc)
+	{
+		self.free_list.cto_pool_opened(cto_pool_arc);
+		self.queue.cto_pool_opened(cto_pool_arc);
+	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.free_list.cto_pool_relocate(cto_pool_relocation);
+		self.queue.cto_pool_relocate(cto_pool_relocation);
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		self.free_list.cto_pool_traverse(cto_pool_traversal);
+		self.queue.cto_pool_traverse(cto_pool_traversal);
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("crash_simulation::Queue { free_list, queue }"), &[CtoStrongArc::<FreeList<Node<u64>>>::schema_hash(), CtoStrongArc::<PersistentFetchAndAddArrayQueue<u64>>::schema_hash()])
+	}
+}
+
+struct CrashImagesDirectory(PathBuf);
+
+impl Drop for CrashImagesDirectory
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		let _ = remove_dir_all(&self.0);
+	}
+}
+
+impl CrashImagesDirectory
+{
+	fn new(name: &str) -> Self
+	{
+		let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+		let path = temp_dir().join(format!("persistent-memory-crash-simulation-{}-{}-{}", name, since_epoch.as_secs(), since_epoch.subsec_nanos()));
+		create_dir(&path).unwrap();
+		CrashImagesDirectory(path)
+	}
+	
+	fn path(&self) -> &Path
+	{
+		&self.0
+	}
+}
+
+fn new_free_list<T>(cto_pool_arc: &CtoPoolArc) -> CtoStrongArc<FreeList<T>>
+{
+	FreeList::new(cto_pool_arc, EliminationArrayLength::number_of_threads_to_length(1), None::<fn(&CtoPoolArc) -> Option<InitializedFreeListElement<T>>>)
+}
+
+fn initialize_free_list(root: &mut CtoStrongArc<FreeList<u64>>, cto_pool_arc: &CtoPoolArc) -> Result<(), io::Error>
+{
+	let free_list = new_free_list(cto_pool_arc);
+	for value in 0 .. NumberOfValues
+	{
+		free_list.new_free_list_element(value, 0).push();
+	}
+	unsafe { write(root, free_list) };
+	Ok(())
+}
+
+fn initialize_queue(root: &mut Queue, cto_pool_arc: &CtoPoolArc) -> Result<(), io::Error>
+{
+	let free_list = new_free_list(cto_pool_arc);
+	for _ in 0 .. 4
+	{
+		// A node is initialized by the queue when it is popped from the free list.
+		free_list.new_free_list_element(unsafe { zeroed::<Node<u64>>() }, 0).push();
+	}
+	let queue = PersistentFetchAndAddArrayQueue::new(&free_list, cto_pool_arc).unwrap();
+	unsafe { write(root, Queue { free_list, queue }) };
+	Ok(())
+}
+
+fn initialize_blocks(root: &mut Blocks, cto_pool_arc: &CtoPoolArc) -> Result<(), io::Error>
+{
+	// The block allocator must be aligned to 4096; the memory is never freed.
+	const Alignment: usize = 4096;
+	let mut memory = cto_pool_arc.allocate_vec_with_capacity::<u8>(BlockAllocator::<CrashSimulationPersistence>::size_of(NumberOfBlocks, BlockSize::_64) + Alignment);
+	let block_allocator = BlockAllocator::<CrashSimulationPersistence>::new(memory.as_mut_ptr() as usize, NumberOfBlocks, BlockSize::_64);
+	forget(memory);
+	unsafe { write(root, Some(BlockAllocatorAlloc::new(block_allocator.as_ref(), cto_pool_arc))) };
+	Ok(())
+}
+
+fn layout(number_of_blocks: usize) -> Layout
+{
+	Layout::from_size_align(number_of_blocks * BlockSize::_64 as usize, 8).unwrap()
+}
+
+fn assert_no_failures(failures: Vec<(PathBuf, String)>)
+{
+	assert!(failures.is_empty(), "crash images that failed: {:?}", failures);
+}
+
+#[test]
+fn free_list_values_are_never_lost_into_another_value_or_duplicated_after_a_crash()
+{
+	for crash_image_mode in CrashImageModes.iter()
+	{
+		let directory = CrashImagesDirectory::new("free-list");
+		
+		let failures = simulate_crashes(directory.path(), "free-list", PoolSize, *crash_image_mode, CrashEveryNthFence, initialize_free_list, |cto_pool|
+		{
+			let free_list: &CtoStrongArc<FreeList<u64>> = &*cto_pool;
+			for _ in 0 .. 4
+			{
+				let mut popped = Vec::new();
+				while let Some(free_list_element) = free_list.pop()
+				{
+					popped.push(free_list_element);
+				}
+				for free_list_element in popped.drain(..).rev()
+				{
+					free_list.push(free_list_element);
+				}
+			}
+		},
+		|cto_pool: &CtoPool<CtoStrongArc<FreeList<u64>>>|
+		{
+			let mut values = HashSet::new();
+			while let Some(free_list_element) = cto_pool.pop()
+			{
+				let value = *free_list_element.value();
+				if value >= NumberOfValues
+				{
+					return Err(format!("value {} was never pushed", value))
+				}
+				if !values.insert(value)
+				{
+					return Err(format!("value {} was popped twice", value))
+				}
+			}
+			Ok(())
+		}).unwrap();
+		
+		assert_no_failures(failures);
+	}
+}
+
+#[test]
+fn queue_dequeues_in_order_without_duplicates_after_a_crash()
+{
+	for crash_image_mode in CrashImageModes.iter()
+	{
+		let directory = CrashImagesDirectory::new("queue");
+		
+		let failures = simulate_crashes(directory.path(), "queue", PoolSize, *crash_image_mode, CrashEveryNthFence, initialize_queue, |cto_pool|
+		{
+			for value in 0 .. NumberOfValues
+			{
+				let boxed = cto_pool.allocator().allocate_box(|value_pointer: *mut u64, _cto_pool_arc: &CtoPoolArc|
+				{
+					unsafe { write(value_pointer, value) };
+					Ok::<(), io::Error>(())
+				}).unwrap();
+				cto_pool.queue.enqueue(unsafe { NonNull::new_unchecked(CtoBox::into_raw(boxed)) }).unwrap();
+			}
+			for _ in 0 .. NumberOfValues / 2
+			{
+				cto_pool.queue.dequeue().unwrap();
+			}
+		},
+		|cto_pool: &CtoPool<Queue>|
+		{
+			let mut previous = None;
+			while let Some(item) = cto_pool.queue.dequeue()
+			{
+				let value = unsafe { *item.as_ptr() };
+				if value >= NumberOfValues
+				{
+					return Err(format!("value {} was never enqueued", value))
+				}
+				if let Some(previous) = previous
+				{
+					if value <= previous
+					{
+						return Err(format!("value {} was dequeued after value {}", value, previous))
+					}
+				}
+				previous = Some(value);
+			}
+			Ok(())
+		}).unwrap();
+		
+		assert_no_failures(failures);
+	}
+}
+
+#[test]
+fn block_allocator_never_hands_out_a_block_twice_after_a_crash()
+{
+	for crash_image_mode in CrashImageModes.iter()
+	{
+		let directory = CrashImagesDirectory::new("block-allocator");
+		
+		let failures = simulate_crashes(directory.path(), "block-allocator", PoolSize, *crash_image_mode, CrashEveryNthFence, initialize_blocks, |cto_pool|
+		{
+			let mut block_allocator_alloc: Blocks = (**cto_pool).clone();
+			let block_allocator_alloc = block_allocator_alloc.as_mut().unwrap();
+			
+			let first = unsafe { block_allocator_alloc.alloc(layout(4)) }.unwrap();
+			let second = unsafe { block_allocator_alloc.alloc(layout(4)) }.unwrap();
+			unsafe { write_bytes(second, 0xA5, layout(4).size()) };
+			unsafe { block_allocator_alloc.dealloc(first, layout(4)) };
+			unsafe { block_allocator_alloc.shrink_in_place(second, layout(4), layout(1)) }.unwrap();
+			let third = unsafe { block_allocator_alloc.alloc(layout(2)) }.unwrap();
+			unsafe { block_allocator_alloc.dealloc(third, layout(2)) };
+		},
+		|cto_pool: &CtoPool<Blocks>|
+		{
+			let mut block_allocator_alloc: Blocks = (**cto_pool).clone();
+			let block_allocator_alloc = block_allocator_alloc.as_mut().unwrap();
+			
+			let mut chains = HashSet::new();
+			while let Ok(chain) = unsafe { block_allocator_alloc.alloc(layout(1)) }
+			{
+				if !chains.insert(chain as usize)
+				{
+					return Err(format!("chain at {:?} was allocated twice", chain))
+				}
+				if chains.len() > NumberOfBlocks
+				{
+					return Err(format!("more than {} chains were allocated", NumberOfBlocks))
+				}
+			}
+			Ok(())
+		}).unwrap();
+		
+		assert_no_failures(failures);
+	}
+}
@@ -0,0 +1,46 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+#![cfg(feature = "persistency-check")]
+
+
+extern crate persistent_memory;
+
+
+use ::persistent_memory::cto_pool::persistency_check::PersistencyBug;
+use ::persistent_memory::cto_pool::persistency_check::PersistencyChecker;
+use ::persistent_memory::intrinsics::cache_line_size;
+use ::persistent_memory::persistent_memory_operations::persistent_fence;
+use ::persistent_memory::persistent_memory_operations::persistent_write_back;
+
+
+const NumberOfCacheLines: usize = 4;
+
+// Only one `PersistencyChecker` can run at a time, so this is the only test in this file.
+#[test]
+fn unflushed_store_and_redundant_flush_are_reported()
+{
+	let cache_line_size = cache_line_size();
+	let mut memory = vec![0u8; (NumberOfCacheLines + 1) * cache_line_size];
+	let inclusive_start_address = ((memory.as_mut_ptr() as usize + cache_line_size - 1) & !(cache_line_size - 1)) as *mut u8;
+	let cache_line = |index: usize| unsafe { inclusive_start_address.offset((index * cache_line_size) as isize) };
+	
+	let persistency_checker = PersistencyChecker::start(inclusive_start_address, NumberOfCacheLines * cache_line_size);
+	
+	// Stored to, written back and fenced: correct.
+	unsafe { *cache_line(0) = 1 };
+	persistent_write_back(cache_line(0));
+	persistent_fence();
+	
+	// Written back again without being stored to.
+	persistent_write_back(cache_line(0));
+	persistent_fence();
+	
+	// Stored to but never written back.
+	unsafe { *cache_line(1) = 1 };
+	
+	assert_eq!(persistency_checker.bugs(), vec![PersistencyBug::RedundantFlush(cache_line(0) as usize)], "an unflushed store should only be found when stopped");
+	
+	assert_eq!(persistency_checker.stop(), vec![PersistencyBug::UnflushedStore(cache_line(1) as usize), PersistencyBug::RedundantFlush(cache_line(0) as usize)]);
+}
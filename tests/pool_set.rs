@@ -0,0 +1,232 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+extern crate persistent_memory;
+
+
+use ::persistent_memory::pool_set::PoolSet;
+use ::persistent_memory::pool_set::PoolSetHeaders;
+use ::persistent_memory::pool_set::PoolSetParseError;
+use ::persistent_memory::pool_set::PoolSetPartSize;
+use ::persistent_memory::pool_set::PoolSetPartSize::*;
+use ::std::path::Path;
+
+
+fn parse(pool_set_file: &str) -> Result<PoolSet, PoolSetParseError>
+{
+	PoolSet::parse(pool_set_file.as_bytes())
+}
+
+#[test]
+fn part_size_without_suffix_is_in_bytes()
+{
+	assert_eq!(PoolSetPartSize::parse("4096"), Some(Bytes(4096)));
+	assert_eq!(PoolSetPartSize::parse("4096B"), Some(Bytes(4096)));
+}
+
+#[test]
+fn part_size_binary_suffixes()
+{
+	assert_eq!(PoolSetPartSize::parse("4K"), Some(Bytes(4 << 10)));
+	assert_eq!(PoolSetPartSize::parse("4KiB"), Some(Bytes(4 << 10)));
+	assert_eq!(PoolSetPartSize::parse("3M"), Some(Bytes(3 << 20)));
+	assert_eq!(PoolSetPartSize::parse("3MiB"), Some(Bytes(3 << 20)));
+	assert_eq!(PoolSetPartSize::parse("20G"), Some(Bytes(20 << 30)));
+	assert_eq!(PoolSetPartSize::parse("2TiB"), Some(Bytes(2 << 40)));
+	assert_eq!(PoolSetPartSize::parse("1P"), Some(Bytes(1 << 50)));
+}
+
+#[test]
+fn part_size_decimal_suffixes()
+{
+	assert_eq!(PoolSetPartSize::parse("4KB"), Some(Bytes(4_000)));
+	assert_eq!(PoolSetPartSize::parse("3MB"), Some(Bytes(3_000_000)));
+	assert_eq!(PoolSetPartSize::parse("20GB"), Some(Bytes(20_000_000_000)));
+	assert_eq!(PoolSetPartSize::parse("2TB"), Some(Bytes(2_000_000_000_000)));
+	assert_eq!(PoolSetPartSize::parse("1PB"), Some(Bytes(1_000_000_000_000_000)));
+}
+
+#[test]
+fn part_size_auto()
+{
+	assert_eq!(PoolSetPartSize::parse("AUTO"), Some(Automatic));
+}
+
+#[test]
+fn part_size_invalid()
+{
+	assert_eq!(PoolSetPartSize::parse(""), None);
+	assert_eq!(PoolSetPartSize::parse("G"), None);
+	assert_eq!(PoolSetPartSize::parse("4X"), None);
+	assert_eq!(PoolSetPartSize::parse("4k"), None);
+	assert_eq!(PoolSetPartSize::parse("-4K"), None);
+	assert_eq!(PoolSetPartSize::parse("auto"), None);
+}
+
+#[test]
+fn part_size_that_overflows_is_invalid()
+{
+	assert_eq!(PoolSetPartSize::parse("18446744073709551615"), Some(Bytes(::std::u64::MAX)));
+	assert_eq!(PoolSetPartSize::parse("18446744073709551616"), None);
+	assert_eq!(PoolSetPartSize::parse("16384P"), None);
+}
+
+#[test]
+fn parses_parts_options_and_replicas()
+{
+	let pool_set = parse("# A comment\n\nPMEMPOOLSET\nOPTION SINGLEHDR\n100G /mnt/pmem0/pool.part0 # trailing comment\n4KiB /mnt/pmem1/pool.part1\nAUTO /dev/dax0.0\n\nREPLICA\n300G /mnt/pmem2/pool.replica\n").unwrap();
+	
+	assert_eq!(pool_set.headers(), PoolSetHeaders::SingleHeader);
+	assert_eq!(pool_set.replicas().len(), 2);
+	
+	let master_replica_parts = pool_set.master_replica().parts();
+	assert_eq!(master_replica_parts.len(), 3);
+	assert_eq!(master_replica_parts[0].size(), Bytes(100 << 30));
+	assert_eq!(master_replica_parts[0].path(), Path::new("/mnt/pmem0/pool.part0"));
+	assert_eq!(master_replica_parts[1].size(), Bytes(4 << 10));
+	assert_eq!(master_replica_parts[1].path(), Path::new("/mnt/pmem1/pool.part1"));
+	assert_eq!(master_replica_parts[2].size(), Automatic);
+	assert_eq!(master_replica_parts[2].path(), Path::new("/dev/dax0.0"));
+	
+	let replica_parts = pool_set.replicas()[1].parts();
+	assert_eq!(replica_parts.len(), 1);
+	assert_eq!(replica_parts[0].size(), Bytes(300 << 30));
+	assert_eq!(replica_parts[0].path(), Path::new("/mnt/pmem2/pool.replica"));
+}
+
+#[test]
+fn headers_default_to_per_part()
+{
+	let pool_set = parse("PMEMPOOLSET\n1G /mnt/pmem0/pool\n").unwrap();
+	
+	assert_eq!(pool_set.headers(), PoolSetHeaders::PerPart);
+}
+
+#[test]
+fn no_headers_option()
+{
+	let pool_set = parse("PMEMPOOLSET\nOPTION NOHDRS\n1G /mnt/pmem0/pool\n").unwrap();
+	
+	assert_eq!(pool_set.headers(), PoolSetHeaders::NoHeaders);
+}
+
+#[test]
+fn missing_signature()
+{
+	match parse("1G /mnt/pmem0/pool\n")
+	{
+		Err(PoolSetParseError::MissingSignature) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+	
+	match parse("# only a comment\n\n")
+	{
+		Err(PoolSetParseError::MissingSignature) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+}
+
+#[test]
+fn invalid_option()
+{
+	match parse("PMEMPOOLSET\nOPTION NOSUCHOPTION\n1G /mnt/pmem0/pool\n")
+	{
+		Err(PoolSetParseError::InvalidOption(2, _)) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+	
+	match parse("PMEMPOOLSET\nOPTION SINGLEHDR NOHDRS\n1G /mnt/pmem0/pool\n")
+	{
+		Err(PoolSetParseError::InvalidOption(2, _)) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+}
+
+#[test]
+fn option_after_parts()
+{
+	match parse("PMEMPOOLSET\n1G /mnt/pmem0/pool\nOPTION SINGLEHDR\n")
+	{
+		Err(PoolSetParseError::OptionAfterParts(3)) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+}
+
+#[test]
+fn option_after_replica()
+{
+	match parse("PMEMPOOLSET\n1G /mnt/pmem0/pool\nREPLICA\nOPTION SINGLEHDR\n1G /mnt/pmem1/pool\n")
+	{
+		Err(PoolSetParseError::OptionAfterParts(4)) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+}
+
+#[test]
+fn relative_path()
+{
+	match parse("PMEMPOOLSET\n1G pool.part0\n")
+	{
+		Err(PoolSetParseError::RelativePath(2, ref path)) if path == Path::new("pool.part0") => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+}
+
+#[test]
+fn invalid_size()
+{
+	match parse("PMEMPOOLSET\n1X /mnt/pmem0/pool\n")
+	{
+		Err(PoolSetParseError::InvalidSize(2, ref size)) if size == "1X" => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+}
+
+#[test]
+fn invalid_part()
+{
+	match parse("PMEMPOOLSET\n1G\n")
+	{
+		Err(PoolSetParseError::InvalidPart(2, _)) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+	
+	match parse("PMEMPOOLSET\n1G /mnt/pmem0/pool /mnt/pmem1/pool\n")
+	{
+		Err(PoolSetParseError::InvalidPart(2, _)) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+}
+
+#[test]
+fn remote_replica_is_not_supported()
+{
+	match parse("PMEMPOOLSET\n1G /mnt/pmem0/pool\nREPLICA user@example.com remote.set\n")
+	{
+		Err(PoolSetParseError::RemoteReplicasAreNotSupported(3)) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+}
+
+#[test]
+fn empty_replicas()
+{
+	match parse("PMEMPOOLSET\nREPLICA\n1G /mnt/pmem0/pool\n")
+	{
+		Err(PoolSetParseError::EmptyReplica(2)) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+	
+	match parse("PMEMPOOLSET\n1G /mnt/pmem0/pool\nREPLICA\n")
+	{
+		Err(PoolSetParseError::EmptyReplica(3)) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+	
+	match parse("PMEMPOOLSET\n")
+	{
+		Err(PoolSetParseError::EmptyReplica(1)) => (),
+		result => panic!("unexpected result '{:?}'", result),
+	}
+}
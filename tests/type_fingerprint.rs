@@ -0,0 +1,61 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+extern crate persistent_memory;
+
+
+use ::persistent_memory::cto_pool::CtoPoolArc;
+use ::persistent_memory::cto_pool::CtoPoolRelocation;
+use ::persistent_memory::cto_pool::CtoPoolTraversal;
+use ::persistent_memory::cto_pool::CtoSafe;
+use ::persistent_memory::cto_pool::TypeFingerprint;
+use ::persistent_memory::cto_pool::arc::CtoStrongArc;
+use ::persistent_memory::cto_pool::free_list::FreeList;
+
+
+struct Versioned(u64);
+
+impl CtoSafe for Versioned
+{
+	#[inline(always)]
+	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("type_fingerprint::Versioned(u64)")
+	}
+	
+	#[inline(always)]
+	fn schema_version() -> u64
+	{
+		3
+	}
+}
+
+
+#[test]
+fn free_lists_of_different_types_have_different_fingerprints()
+{
+	assert_ne!(TypeFingerprint::of::<CtoStrongArc<FreeList<u32>>>().schema_hash, TypeFingerprint::of::<CtoStrongArc<FreeList<u64>>>().schema_hash);
+}
+
+#[test]
+fn mutable_reference_has_the_schema_version_of_what_it_refers_to()
+{
+	assert_eq!(<&mut Versioned as CtoSafe>::schema_version(), 3);
+	assert_eq!(TypeFingerprint::of::<&mut Versioned>().schema_version, 3);
+}
@@ -0,0 +1,867 @@
+// This file is part of persistent-memory. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT. No part of persistent-memory, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of persistent-memory. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/persistent-memory/master/COPYRIGHT.
+
+
+#![cfg(any(target_os = "android", target_os = "linux"))]
+#![feature(allocator_api)]
+
+
+extern crate persistent_memory;
+
+
+use ::persistent_memory::cto_pool::BestAvailablePersistence;
+use ::persistent_memory::cto_pool::CtoPool;
+use ::persistent_memory::cto_pool::CtoPoolAllocationError;
+use ::persistent_memory::cto_pool::CtoPoolArc;
+use ::persistent_memory::cto_pool::CtoPoolHeaderValidationError;
+use ::persistent_memory::cto_pool::CtoPoolMigrations;
+use ::persistent_memory::cto_pool::CtoPoolOpenError;
+use ::persistent_memory::cto_pool::CtoPoolRegistry;
+use ::persistent_memory::cto_pool::CtoPoolRelocation;
+use ::persistent_memory::cto_pool::CtoPoolTraversal;
+use ::persistent_memory::cto_pool::CtoPointerError;
+use ::persistent_memory::cto_pool::CtoSafe;
+use ::persistent_memory::cto_pool::LastShutdown;
+use ::persistent_memory::cto_pool::PmdkError;
+use ::persistent_memory::cto_pool::ReadOnlyCtoPool;
+use ::persistent_memory::cto_pool::RootDirectoryError;
+use ::persistent_memory::cto_pool::TypeFingerprint;
+use ::persistent_memory::cto_pool::VolatileCtoPoolFile;
+use ::persistent_memory::cto_pool::arc::CtoArc;
+use ::persistent_memory::cto_pool::arc::CtoArcCell;
+use ::persistent_memory::cto_pool::arc::CtoStrongArc;
+use ::persistent_memory::cto_pool::block_allocator::BlockAllocator;
+use ::persistent_memory::cto_pool::block_allocator::BlockAllocatorAlloc;
+use ::persistent_memory::cto_pool::block_allocator::BlockSize;
+use ::persistent_memory::cto_pool::boxed::CtoAtomicOption;
+use ::persistent_memory::cto_pool::boxed::CtoBox;
+use ::persistent_memory::cto_pool::collections::CtoVec;
+use ::persistent_memory::cto_pool::fetch_and_add_array_queue::Node;
+use ::persistent_memory::cto_pool::fetch_and_add_array_queue::PersistentFetchAndAddArrayQueue;
+use ::persistent_memory::cto_pool::free_list::EliminationArrayLength;
+use ::persistent_memory::cto_pool::free_list::FreeList;
+use ::persistent_memory::cto_pool::free_list::InitializedFreeListElement;
+use ::std::env::temp_dir;
+use ::std::fs::File;
+use ::std::fs::OpenOptions;
+use ::std::fs::create_dir;
+use ::std::fs::remove_dir_all;
+use ::std::heap::Alloc;
+use ::std::heap::Layout;
+use ::std::io;
+use ::std::io::Read;
+use ::std::io::Seek;
+use ::std::io::SeekFrom;
+use ::std::io::Write;
+use ::std::mem::forget;
+use ::std::mem::zeroed;
+use ::std::path::Path;
+use ::std::path::PathBuf;
+use ::std::ptr::NonNull;
+use ::std::ptr::write;
+use ::std::ptr::write_bytes;
+use ::std::sync::atomic::Ordering::SeqCst;
+use ::std::time::SystemTime;
+use ::std::time::UNIX_EPOCH;
+
+
+const PoolSize: usize = 4 * 1024 * 1024;
+
+type Blocks = Option<BlockAllocatorAlloc<BestAvailablePersistence>>;
+
+struct Relocatable
+{
+	cto_box: CtoBox<u64>,
+	cto_arc: CtoArc<u64>,
+	free_list: CtoStrongArc<FreeList<u64>>,
+	queue_free_list: CtoStrongArc<FreeList<Node<u64>>>,
+	queue: CtoStrongArc<PersistentFetchAndAddArrayQueue<u64>>,
+}
+
+impl CtoSafe for Relocatable
+{
+	#[inline(always)]
+	fn cto_pool_opened(&mut self, cto_pool_arc: &CtoPoolArc)
+	{
+		self.cto_box.cto_pool_opened(cto_pool_arc);
+		self.cto_arc.cto_pool_opened(cto_pool_arc);
+		self.free_list.cto_pool_opened(cto_pool_arc);
+		self.queue_free_list.cto_pool_opened(cto_pool_arc);
+		self.queue.cto_pool_opened(cto_pool_arc);
+	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.cto_box.cto_pool_relocate(cto_pool_relocation);
+		self.cto_arc.cto_pool_relocate(cto_pool_relocation);
+		self.free_list.cto_pool_relocate(cto_pool_relocation);
+		self.queue_free_list.cto_pool_relocate(cto_pool_relocation);
+		self.queue.cto_pool_relocate(cto_pool_relocation);
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		self.cto_box.cto_pool_traverse(cto_pool_traversal);
+		self.cto_arc.cto_pool_traverse(cto_pool_traversal);
+		self.free_list.cto_pool_traverse(cto_pool_traversal);
+		self.queue_free_list.cto_pool_traverse(cto_pool_traversal);
+		self.queue.cto_pool_traverse(cto_pool_traversal);
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("volatile_cto_pool_file::Relocatable { cto_box, cto_arc, free_list, queue_free_list, queue }"), &[CtoBox::<u64>::schema_hash(), CtoArc::<u64>::schema_hash(), CtoStrongArc::<FreeList<u64>>::schema_hash(), CtoStrongArc::<FreeList<Node<u64>>>::schema_hash(), CtoStrongArc::<PersistentFetchAndAddArrayQueue<u64>>::schema_hash()])
+	}
+}
+
+struct Doubled(u64);
+
+impl CtoSafe for Doubled
+{
+	#[inline(always)]
+	fn cto_pool_opened(&mut self, _cto_pool_arc: &CtoPoolArc)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, _cto_pool_relocation: &CtoPoolRelocation)
+	{
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, _cto_pool_traversal: &CtoPoolTraversal)
+	{
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::schema_hash_of("volatile_cto_pool_file::Doubled(u64)")
+	}
+	
+	#[inline(always)]
+	fn schema_version() -> u64
+	{
+		1
+	}
+}
+
+struct Cells
+{
+	cto_arc_cell: CtoArcCell<u64>,
+	cto_atomic_option: CtoAtomicOption<u64>,
+}
+
+impl CtoSafe for Cells
+{
+	#[inline(always)]
+	fn cto_pool_opened(&mut self, cto_pool_arc: &CtoPoolArc)
+	{
+		self.cto_arc_cell.cto_pool_opened(cto_pool_arc);
+		self.cto_atomic_option.cto_pool_opened(cto_pool_arc);
+	}
+	
+	#[inline(always)]
+	fn cto_pool_relocate(&mut self, cto_pool_relocation: &CtoPoolRelocation)
+	{
+		self.cto_arc_cell.cto_pool_relocate(cto_pool_relocation);
+		self.cto_atomic_option.cto_pool_relocate(cto_pool_relocation);
+	}
+	
+	#[inline(always)]
+	fn cto_pool_traverse(&self, cto_pool_traversal: &CtoPoolTraversal)
+	{
+		self.cto_arc_cell.cto_pool_traverse(cto_pool_traversal);
+		self.cto_atomic_option.cto_pool_traverse(cto_pool_traversal);
+	}
+	
+	#[inline(always)]
+	fn schema_hash() -> u64
+	{
+		TypeFingerprint::combine_schema_hashes(TypeFingerprint::schema_hash_of("volatile_cto_pool_file::Cells { cto_arc_cell, cto_atomic_option }"), &[CtoArcCell::<u64>::schema_hash(), CtoAtomicOption::<u64>::schema_hash()])
+	}
+}
+
+struct TemporaryDirectory(PathBuf);
+
+impl Drop for TemporaryDirectory
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		let _ = remove_dir_all(&self.0);
+	}
+}
+
+impl TemporaryDirectory
+{
+	fn new(name: &str) -> Self
+	{
+		let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+		let path = temp_dir().join(format!("persistent-memory-{}-{}-{}", name, since_epoch.as_secs(), since_epoch.subsec_nanos()));
+		create_dir(&path).unwrap();
+		TemporaryDirectory(path)
+	}
+	
+	fn path(&self) -> &Path
+	{
+		&self.0
+	}
+}
+
+fn initialize_nothing<T: CtoSafe>(root: &mut Option<T>, _cto_pool_arc: &CtoPoolArc) -> Result<(), io::Error>
+{
+	unsafe { write(root, None) };
+	Ok(())
+}
+
+fn initialize_seven(root: &mut u64, _cto_pool_arc: &CtoPoolArc) -> Result<(), io::Error>
+{
+	*root = 7;
+	Ok(())
+}
+
+fn initialize_vec(root: &mut CtoVec<CtoBox<u64>>, cto_pool_arc: &CtoPoolArc) -> Result<(), io::Error>
+{
+	unsafe { write(root, cto_pool_arc.allocate_vec_with_capacity(16)) };
+	Ok(())
+}
+
+fn allocate_box<RootValue: CtoSafe>(cto_pool: &CtoPool<RootValue>, value: u64) -> CtoBox<u64>
+{
+	cto_pool.allocator().allocate_box(|value_pointer: *mut u64, _cto_pool_arc: &CtoPoolArc|
+	{
+		unsafe { write(value_pointer, value) };
+		Ok::<(), io::Error>(())
+	}).unwrap()
+}
+
+fn new_free_list<T>(cto_pool_arc: &CtoPoolArc) -> CtoStrongArc<FreeList<T>>
+{
+	FreeList::new(cto_pool_arc, EliminationArrayLength::number_of_threads_to_length(1), None::<fn(&CtoPoolArc) -> Option<InitializedFreeListElement<T>>>)
+}
+
+fn initialize_relocatable(root: &mut Relocatable, cto_pool_arc: &CtoPoolArc) -> Result<(), io::Error>
+{
+	let cto_box = cto_pool_arc.allocate_box(|value_pointer: *mut u64, _cto_pool_arc: &CtoPoolArc|
+	{
+		unsafe { write(value_pointer, 11) };
+		Ok::<(), io::Error>(())
+	}).unwrap();
+	
+	let cto_arc = cto_pool_arc.allocate_arc(|value_pointer: *mut u64, _cto_pool_arc: &CtoPoolArc|
+	{
+		unsafe { write(value_pointer, 13) };
+		Ok::<(), io::Error>(())
+	}).unwrap();
+	
+	let free_list = new_free_list(cto_pool_arc);
+	for value in 0 .. 4
+	{
+		free_list.new_free_list_element(value, 0).push();
+	}
+	
+	let queue_free_list = new_free_list(cto_pool_arc);
+	for _ in 0 .. 4
+	{
+		// A node is initialized by the queue when it is popped from the free list.
+		queue_free_list.new_free_list_element(unsafe { zeroed::<Node<u64>>() }, 0).push();
+	}
+	let queue = PersistentFetchAndAddArrayQueue::new(&queue_free_list, cto_pool_arc).unwrap();
+	
+	unsafe { write(root, Relocatable { cto_box, cto_arc, free_list, queue_free_list, queue }) };
+	Ok(())
+}
+
+fn allocate_arc<RootValue: CtoSafe>(cto_pool: &CtoPool<RootValue>, value: u64) -> CtoArc<u64>
+{
+	cto_pool.allocator().allocate_arc(|value_pointer: *mut u64, _cto_pool_arc: &CtoPoolArc|
+	{
+		unsafe { write(value_pointer, value) };
+		Ok::<(), io::Error>(())
+	}).unwrap()
+}
+
+fn initialize_cells(root: &mut Cells, cto_pool_arc: &CtoPoolArc) -> Result<(), io::Error>
+{
+	let cto_arc = cto_pool_arc.allocate_arc(|value_pointer: *mut u64, _cto_pool_arc: &CtoPoolArc|
+	{
+		unsafe { write(value_pointer, 1) };
+		Ok::<(), io::Error>(())
+	}).unwrap();
+	
+	unsafe { write(root, Cells { cto_arc_cell: CtoArcCell::new(cto_arc), cto_atomic_option: CtoAtomicOption::none() }) };
+	Ok(())
+}
+
+fn root_address<RootValue: CtoSafe>(cto_pool: &CtoPool<RootValue>) -> usize
+{
+	&**cto_pool as *const RootValue as usize
+}
+
+fn layout(number_of_blocks: usize) -> Layout
+{
+	Layout::from_size_align(number_of_blocks * BlockSize::_64 as usize, 8).unwrap()
+}
+
+#[test]
+fn layout_name_with_nul_is_an_error()
+{
+	assert_eq!(VolatileCtoPoolFile::new("a\0b", PoolSize).err(), Some(PmdkError::InvalidLayoutName(1)));
+}
+
+#[test]
+fn reopen_closes_pool_even_if_objects_in_it_hold_references_to_it()
+{
+	let file = VolatileCtoPoolFile::new("reopen", PoolSize).unwrap();
+	
+	let mut cto_pool = file.open(initialize_vec).unwrap();
+	let boxed = allocate_box(&cto_pool, 1);
+	cto_pool.push(boxed);
+	
+	let cto_pool = unsafe { file.reopen(cto_pool, initialize_vec) }.unwrap();
+	
+	assert_eq!(cto_pool.last_shutdown(), LastShutdown::Clean);
+	assert_eq!(cto_pool.len(), 1);
+	assert_eq!(*cto_pool[0], 1);
+}
+
+#[test]
+fn free_extents_are_rebuilt_when_reopened()
+{
+	let file = VolatileCtoPoolFile::new("free-extents", PoolSize).unwrap();
+	
+	let mut cto_pool = file.open(initialize_vec).unwrap();
+	for value in 0 .. 8
+	{
+		let boxed = allocate_box(&cto_pool, value);
+		cto_pool.push(boxed);
+	}
+	cto_pool.remove(5);
+	cto_pool.remove(2);
+	let before = cto_pool.statistics();
+	
+	let mut cto_pool = unsafe { file.reopen(cto_pool, initialize_vec) }.unwrap();
+	let after = cto_pool.statistics();
+	
+	assert_eq!(after.total_size, before.total_size);
+	assert_eq!(after.allocated_size, before.allocated_size);
+	assert_eq!(after.free_size, before.free_size);
+	assert_eq!(after.number_of_allocations, before.number_of_allocations);
+	assert!(after.largest_free_extent >= before.largest_free_extent);
+	
+	for value in 8 .. 10
+	{
+		let boxed = allocate_box(&cto_pool, value);
+		cto_pool.push(boxed);
+	}
+	let values: Vec<u64> = cto_pool.iter().map(|boxed| **boxed).collect();
+	assert_eq!(values, vec![0, 1, 3, 4, 6, 7, 8, 9]);
+}
+
+#[test]
+fn corrupt_header_fails_checksum()
+{
+	let file = VolatileCtoPoolFile::new("checksum", PoolSize).unwrap();
+	
+	// The first byte of the pool's uuid, which is covered by the header's checksum.
+	const UuidOffset: u64 = 16;
+	{
+		let mut pool_file = OpenOptions::new().read(true).write(true).open(file.path()).unwrap();
+		let mut byte = [0u8; 1];
+		pool_file.seek(SeekFrom::Start(UuidOffset)).unwrap();
+		pool_file.read_exact(&mut byte).unwrap();
+		byte[0] ^= 0xFF;
+		pool_file.seek(SeekFrom::Start(UuidOffset)).unwrap();
+		pool_file.write_all(&byte).unwrap();
+	}
+	
+	match file.open(initialize_seven)
+	{
+		Err(CtoPoolOpenError::ValidationFailed(PmdkError::InvalidHeader(CtoPoolHeaderValidationError::BadChecksum(_, _)))) => (),
+		unexpected => panic!("expected a bad checksum, not {:?}", unexpected),
+	}
+}
+
+#[test]
+fn root_of_a_different_type_is_rejected()
+{
+	let file = VolatileCtoPoolFile::new("type-fingerprint", PoolSize).unwrap();
+	
+	drop(file.open(initialize_seven).unwrap());
+	
+	match file.open(|root: &mut u32, _cto_pool_arc: &CtoPoolArc| { *root = 3; Ok::<(), io::Error>(()) })
+	{
+		Err(CtoPoolOpenError::DifferentRootType { expected, found }) =>
+		{
+			assert_eq!(expected, TypeFingerprint::of::<u32>());
+			assert_eq!(found, TypeFingerprint::of::<u64>());
+		}
+		unexpected => panic!("expected a different root type, not {:?}", unexpected),
+	}
+	
+	let cto_pool = file.open(initialize_seven).unwrap();
+	assert_eq!(*cto_pool, 7);
+}
+
+#[test]
+fn block_allocator_bags_are_rebuilt_when_reopened_and_chains_grow_and_shrink_in_place()
+{
+	const NumberOfBlocks: usize = 8;
+	
+	let file = VolatileCtoPoolFile::new("block-allocator", PoolSize).unwrap();
+	
+	let mut cto_pool: CtoPool<Blocks> = file.open(initialize_nothing).unwrap();
+	{
+		let mut cto_pool_alloc = cto_pool.alloc().clone();
+		let memory = unsafe { cto_pool_alloc.alloc(Layout::from_size_align(BlockAllocator::<BestAvailablePersistence>::size_of(NumberOfBlocks, BlockSize::_64), 4096).unwrap()) }.unwrap();
+		let block_allocator = BlockAllocator::<BestAvailablePersistence>::new(memory as usize, NumberOfBlocks, BlockSize::_64);
+		let block_allocator_alloc = BlockAllocatorAlloc::new(unsafe { block_allocator.as_ref() }, cto_pool.allocator());
+		*cto_pool = Some(block_allocator_alloc);
+	}
+	
+	// Uses every block, then gives back all but the first two.
+	let offset =
+	{
+		let mut block_allocator_alloc: Blocks = (*cto_pool).clone();
+		let block_allocator_alloc = block_allocator_alloc.as_mut().unwrap();
+		let chain = unsafe { block_allocator_alloc.alloc(layout(NumberOfBlocks)) }.unwrap();
+		unsafe { write_bytes(chain, 0xA5, layout(NumberOfBlocks).size()) };
+		unsafe { block_allocator_alloc.shrink_in_place(chain, layout(NumberOfBlocks), layout(2)) }.unwrap();
+		(chain as usize).wrapping_sub(root_address(&cto_pool))
+	};
+	
+	let cto_pool = unsafe { file.reopen(cto_pool, initialize_nothing) }.unwrap();
+	
+	let chain = root_address(&cto_pool).wrapping_add(offset) as *mut u8;
+	for index in 0 .. layout(2).size()
+	{
+		assert_eq!(unsafe { *chain.offset(index as isize) }, 0xA5);
+	}
+	
+	let mut block_allocator_alloc: Blocks = (*cto_pool).clone();
+	let block_allocator_alloc = block_allocator_alloc.as_mut().unwrap();
+	
+	// The six blocks given back are the only free chain.
+	let remainder = unsafe { block_allocator_alloc.alloc(layout(NumberOfBlocks - 2)) }.unwrap();
+	assert_eq!(remainder as usize, chain as usize + layout(2).size());
+	assert!(unsafe { block_allocator_alloc.alloc(layout(1)) }.is_err());
+	
+	unsafe { block_allocator_alloc.dealloc(remainder, layout(NumberOfBlocks - 2)) };
+	unsafe { block_allocator_alloc.grow_in_place(chain, layout(2), layout(NumberOfBlocks)) }.unwrap();
+	assert!(unsafe { block_allocator_alloc.alloc(layout(1)) }.is_err());
+}
+
+#[test]
+fn freed_block_allocator_is_unregistered()
+{
+	const NumberOfBlocks: usize = 8;
+	
+	let file = VolatileCtoPoolFile::new("freed-block-allocator", PoolSize).unwrap();
+	
+	let cto_pool: CtoPool<u64> = file.open(initialize_seven).unwrap();
+	let mut cto_pool_alloc = cto_pool.alloc().clone();
+	let memory_layout = Layout::from_size_align(BlockAllocator::<BestAvailablePersistence>::size_of(NumberOfBlocks, BlockSize::_64), 4096).unwrap();
+	let memory = unsafe { cto_pool_alloc.alloc(memory_layout.clone()) }.unwrap();
+	let block_allocator = BlockAllocator::<BestAvailablePersistence>::new(memory as usize, NumberOfBlocks, BlockSize::_64);
+	let mut block_allocator_alloc = BlockAllocatorAlloc::new(unsafe { block_allocator.as_ref() }, cto_pool.allocator());
+	let chain = unsafe { block_allocator_alloc.alloc(layout(1)) }.unwrap();
+	
+	assert_eq!(CtoPoolRegistry::block_allocator_owning::<BestAvailablePersistence, u8>(chain), Some(block_allocator));
+	
+	drop(block_allocator_alloc);
+	unsafe { cto_pool_alloc.dealloc(memory, memory_layout) };
+	
+	assert_eq!(CtoPoolRegistry::block_allocator_owning::<BestAvailablePersistence, u8>(chain), None);
+}
+
+#[test]
+fn box_dropped_after_its_pool_is_leaked()
+{
+	let file = VolatileCtoPoolFile::new("box-outlives-pool", PoolSize).unwrap();
+	
+	let cto_pool: CtoPool<u64> = file.open(initialize_seven).unwrap();
+	let cto_box = allocate_box(&cto_pool, 11);
+	let value_pointer = &*cto_box as *const u64;
+	assert!(CtoPoolRegistry::pool_pointer_owning(value_pointer).is_some());
+	
+	// Nothing in the pool holds a `CtoPoolArc`, so this closes the pool.
+	drop(cto_pool);
+	assert!(CtoPoolRegistry::pool_pointer_owning(value_pointer).is_none());
+	
+	drop(cto_box);
+	
+	let cto_pool: CtoPool<u64> = file.open(initialize_seven).unwrap();
+	assert_eq!(*cto_pool, 7);
+}
+
+#[test]
+fn pointers_are_found_in_the_pool_they_point_into()
+{
+	let file = VolatileCtoPoolFile::new("first-of-two", PoolSize).unwrap();
+	let other_file = VolatileCtoPoolFile::new("second-of-two", PoolSize).unwrap();
+	
+	let cto_pool: CtoPool<u64> = file.open(initialize_seven).unwrap();
+	let other_cto_pool: CtoPool<u64> = other_file.open(initialize_seven).unwrap();
+	
+	let cto_box = allocate_box(&cto_pool, 1);
+	let other_cto_box = allocate_box(&other_cto_pool, 2);
+	let value_pointer = &*cto_box as *const u64;
+	let other_value_pointer = &*other_cto_box as *const u64;
+	
+	assert_eq!(CtoPoolRegistry::pool_pointer_owning(value_pointer), Some(cto_pool.allocator().pool_pointer()));
+	assert_eq!(CtoPoolRegistry::pool_pointer_owning(other_value_pointer), Some(other_cto_pool.allocator().pool_pointer()));
+	assert_eq!(CtoPoolRegistry::cto_pool_arc_owning(value_pointer).unwrap().pool_pointer(), cto_pool.allocator().pool_pointer());
+	assert_eq!(CtoPoolRegistry::cto_pool_arc_owning(other_value_pointer).unwrap().pool_pointer(), other_cto_pool.allocator().pool_pointer());
+	
+	assert!(CtoPoolRegistry::are_in_same_pool(value_pointer, &*cto_pool as *const u64));
+	assert!(!CtoPoolRegistry::are_in_same_pool(value_pointer, other_value_pointer));
+	
+	let on_the_stack = 3u64;
+	assert_eq!(CtoPoolRegistry::pool_pointer_owning(&on_the_stack as *const u64), None);
+	
+	// Each box is freed from its own pool.
+	drop(other_cto_box);
+	drop(cto_box);
+	assert_eq!(*cto_pool, 7);
+	assert_eq!(*other_cto_pool, 7);
+}
+
+#[test]
+fn pool_is_relocated_when_the_address_it_was_last_mapped_at_is_occupied()
+{
+	let file = VolatileCtoPoolFile::new("relocation", PoolSize).unwrap();
+	
+	let cto_pool: CtoPool<Relocatable> = file.open(initialize_relocatable).unwrap();
+	for value in 0 .. 3
+	{
+		let cto_box = allocate_box(&cto_pool, 100 + value);
+		cto_pool.queue.enqueue(unsafe { NonNull::new_unchecked(CtoBox::into_raw(cto_box)) }).unwrap();
+	}
+	let old_root_address = root_address(&cto_pool);
+	
+	let cto_pool = unsafe { file.reopen_relocated(cto_pool, initialize_relocatable) }.unwrap();
+	assert_ne!(root_address(&cto_pool), old_root_address);
+	
+	let root = &*cto_pool as *const Relocatable;
+	let is_in_pool = |pointer: *const u64| CtoPoolRegistry::are_in_same_pool(pointer, root);
+	
+	assert!(is_in_pool(&*cto_pool.cto_box));
+	assert_eq!(*cto_pool.cto_box, 11);
+	
+	assert!(is_in_pool(&*cto_pool.cto_arc));
+	assert_eq!(*cto_pool.cto_arc, 13);
+	
+	let mut values = Vec::new();
+	while let Some(free_list_element) = cto_pool.free_list.pop()
+	{
+		assert!(is_in_pool(free_list_element.value()));
+		values.push(*free_list_element.value());
+	}
+	values.sort();
+	assert_eq!(values, vec![0, 1, 2, 3]);
+	
+	for value in 0 .. 3
+	{
+		let item = cto_pool.queue.dequeue().unwrap();
+		assert!(is_in_pool(item.as_ptr()));
+		assert_eq!(unsafe { *item.as_ptr() }, 100 + value);
+		drop(unsafe { CtoBox::from_raw(item.as_ptr()) });
+	}
+	assert!(cto_pool.queue.dequeue().is_none());
+}
+
+#[test]
+fn read_only_pool_can_be_read_but_not_modified()
+{
+	let file = VolatileCtoPoolFile::new("read-only", PoolSize).unwrap();
+	
+	{
+		let cto_pool: CtoPool<u64> = file.open(initialize_seven).unwrap();
+		cto_pool.insert_root("eleven", 11u32).unwrap();
+	}
+	
+	let read_only_cto_pool: ReadOnlyCtoPool<u64> = file.open_read_only().unwrap();
+	assert!(read_only_cto_pool.has_root());
+	assert_eq!(*read_only_cto_pool, 7);
+	assert_eq!(read_only_cto_pool.root::<u32>("eleven").unwrap(), Some(&11));
+	assert_eq!(read_only_cto_pool.root::<u32>("twelve").unwrap(), None);
+	assert!(read_only_cto_pool.root::<u64>("eleven").is_err());
+	assert_eq!(read_only_cto_pool.list_roots(), vec![("eleven".to_owned(), TypeFingerprint::of::<u32>())]);
+	assert!(read_only_cto_pool.check().unwrap().is_empty());
+	
+	let cto_pool_arc = CtoPoolRegistry::cto_pool_arc_owning(&*read_only_cto_pool as *const u64).unwrap();
+	match cto_pool_arc.allocate_box(|value_pointer: *mut u64, _cto_pool_arc: &CtoPoolArc| { unsafe { write(value_pointer, 1) }; Ok::<(), io::Error>(()) })
+	{
+		Err(CtoPoolAllocationError::Allocation(PmdkError::ReadOnly)) => (),
+		unexpected => panic!("expected the pool to be read-only, not {:?}", unexpected),
+	}
+	drop(cto_pool_arc);
+	
+	match file.open_read_only::<u32>()
+	{
+		Err(CtoPoolOpenError::OpenFailed(_)) => (),
+		unexpected => panic!("expected the pool to be open already, not {:?}", unexpected),
+	}
+	drop(read_only_cto_pool);
+	
+	match file.open_read_only::<u32>()
+	{
+		Err(CtoPoolOpenError::DifferentRootType { expected, found }) =>
+		{
+			assert_eq!(expected, TypeFingerprint::of::<u32>());
+			assert_eq!(found, TypeFingerprint::of::<u64>());
+		}
+		unexpected => panic!("expected a different root type, not {:?}", unexpected),
+	}
+	
+	let cto_pool: CtoPool<u64> = file.open(initialize_seven).unwrap();
+	assert_eq!(*cto_pool, 7);
+}
+
+#[test]
+fn pool_grows_while_open()
+{
+	const GrowBy: usize = 8 * 1024 * 1024;
+	
+	let file = VolatileCtoPoolFile::new("grow", PoolSize).unwrap();
+	
+	let cto_pool: CtoPool<u64> = file.open(initialize_seven).unwrap();
+	let total_size = cto_pool.statistics().total_size;
+	
+	// Larger than the pool before it grows.
+	let large = Layout::from_size_align(6 * 1024 * 1024, 8).unwrap();
+	
+	let offset =
+	{
+		let mut cto_pool_alloc = cto_pool.alloc().clone();
+		assert!(unsafe { cto_pool_alloc.alloc(large.clone()) }.is_err());
+		
+		cto_pool.grow(file.path(), GrowBy).unwrap();
+		assert!(cto_pool.statistics().total_size > total_size);
+		
+		let memory = unsafe { cto_pool_alloc.alloc(large.clone()) }.unwrap();
+		unsafe { write_bytes(memory, 0x5A, large.size()) };
+		(memory as usize).wrapping_sub(root_address(&cto_pool))
+	};
+	let grown_total_size = cto_pool.statistics().total_size;
+	
+	let cto_pool = unsafe { file.reopen(cto_pool, initialize_seven) }.unwrap();
+	assert_eq!(cto_pool.statistics().total_size, grown_total_size);
+	assert_eq!(*cto_pool, 7);
+	
+	let memory = root_address(&cto_pool).wrapping_add(offset) as *mut u8;
+	assert_eq!(unsafe { *memory }, 0x5A);
+	assert_eq!(unsafe { *memory.offset(large.size() as isize - 1) }, 0x5A);
+	unsafe { cto_pool.alloc().clone().dealloc(memory, large) };
+}
+
+#[test]
+fn pool_grows_by_adding_a_part_to_its_pool_set_file()
+{
+	const PartSize: usize = 8 * 1024 * 1024;
+	
+	let directory = TemporaryDirectory::new("grow-by-adding-part");
+	let pool_set_file_path = directory.path().join("pool.set");
+	let first_part_path = directory.path().join("pool.part0");
+	let second_part_path = directory.path().join("pool.part1");
+	File::create(&pool_set_file_path).unwrap().write_all(format!("PMEMPOOLSET\n{} {}\n", PoolSize, first_part_path.display()).as_bytes()).unwrap();
+	
+	let open = || CtoPool::open(&pool_set_file_path, "grow-by-adding-part", 0, 0o600, initialize_seven);
+	
+	let cto_pool: CtoPool<u64> = open().unwrap();
+	let total_size = cto_pool.statistics().total_size;
+	
+	// Larger than the pool before it grows.
+	let large = Layout::from_size_align(6 * 1024 * 1024, 8).unwrap();
+	
+	{
+		let mut cto_pool_alloc = cto_pool.alloc().clone();
+		assert!(unsafe { cto_pool_alloc.alloc(large.clone()) }.is_err());
+		
+		cto_pool.grow_by_adding_part(&pool_set_file_path, &second_part_path, PartSize, 0o600).unwrap();
+		assert!(second_part_path.is_file());
+		let mut pool_set_file = String::new();
+		File::open(&pool_set_file_path).unwrap().read_to_string(&mut pool_set_file).unwrap();
+		assert!(pool_set_file.contains(&format!("{}", second_part_path.display())));
+		assert!(cto_pool.statistics().total_size > total_size);
+		
+		let memory = unsafe { cto_pool_alloc.alloc(large.clone()) }.unwrap();
+		unsafe { cto_pool_alloc.dealloc(memory, large) };
+	}
+	
+	let grown_total_size = cto_pool.statistics().total_size;
+	
+	// Nothing in the pool holds a `CtoPoolArc`, so this closes the pool.
+	drop(cto_pool);
+	
+	let cto_pool: CtoPool<u64> = open().unwrap();
+	assert_eq!(cto_pool.statistics().total_size, grown_total_size);
+	assert_eq!(*cto_pool, 7);
+}
+
+#[test]
+fn named_roots_are_kept_when_reopened()
+{
+	let file = VolatileCtoPoolFile::new("named-roots", PoolSize).unwrap();
+	
+	let cto_pool: CtoPool<u64> = file.open(initialize_seven).unwrap();
+	cto_pool.insert_root("first", 1u32).unwrap();
+	cto_pool.insert_root("second", 2u64).unwrap();
+	match cto_pool.insert_root("first", 3u32)
+	{
+		Err(RootDirectoryError::AlreadyExists(ref name)) => assert_eq!(name, "first"),
+		unexpected => panic!("expected the named root to exist already, not {:?}", unexpected),
+	}
+	
+	let mut cto_pool = unsafe { file.reopen(cto_pool, initialize_seven) }.unwrap();
+	let mut named_roots = cto_pool.list_roots();
+	named_roots.sort();
+	assert_eq!(named_roots, vec![("first".to_owned(), TypeFingerprint::of::<u32>()), ("second".to_owned(), TypeFingerprint::of::<u64>())]);
+	assert_eq!(cto_pool.root::<u32>("first").unwrap(), Some(&1));
+	assert_eq!(cto_pool.root::<u64>("second").unwrap(), Some(&2));
+	assert!(cto_pool.root::<u64>("first").is_err());
+	
+	*cto_pool.root_mut::<u64>("second").unwrap().unwrap() = 22;
+	assert!(cto_pool.remove_root::<u32>("first").unwrap());
+	assert!(!cto_pool.remove_root::<u32>("first").unwrap());
+	
+	let cto_pool = unsafe { file.reopen(cto_pool, initialize_seven) }.unwrap();
+	assert_eq!(cto_pool.list_roots(), vec![("second".to_owned(), TypeFingerprint::of::<u64>())]);
+	assert_eq!(cto_pool.root::<u32>("first").unwrap(), None);
+	assert_eq!(cto_pool.root::<u64>("second").unwrap(), Some(&22));
+	assert_eq!(*cto_pool, 7);
+}
+
+#[test]
+fn root_with_an_older_schema_version_is_migrated()
+{
+	let file = VolatileCtoPoolFile::new("migration", PoolSize).unwrap();
+	
+	{
+		let _cto_pool: CtoPool<u32> = file.open(|root: &mut u32, _cto_pool_arc: &CtoPoolArc| { *root = 21; Ok::<(), io::Error>(()) }).unwrap();
+	}
+	
+	let mut migrations = CtoPoolMigrations::<Doubled, io::Error>::default();
+	migrations.register(|old: &u32, _cto_pool_arc: &CtoPoolArc| Ok(Doubled((*old as u64) * 2)));
+	
+	let cto_pool: CtoPool<Doubled> = file.open_with_migrations(&migrations, |_root: &mut Doubled, _cto_pool_arc: &CtoPoolArc| panic!("the root should have been migrated, not created")).unwrap();
+	assert_eq!(cto_pool.0, 42);
+	
+	// Once migrated, no migration is needed.
+	let cto_pool = unsafe { file.reopen(cto_pool, |_root: &mut Doubled, _cto_pool_arc: &CtoPoolArc| Ok::<(), io::Error>(())) }.unwrap();
+	assert_eq!(cto_pool.0, 42);
+}
+
+#[test]
+fn root_with_an_older_schema_version_and_no_migration_is_rejected()
+{
+	let file = VolatileCtoPoolFile::new("missing-migration", PoolSize).unwrap();
+	
+	{
+		let _cto_pool: CtoPool<u32> = file.open(|root: &mut u32, _cto_pool_arc: &CtoPoolArc| { *root = 21; Ok::<(), io::Error>(()) }).unwrap();
+	}
+	
+	match file.open(|_root: &mut Doubled, _cto_pool_arc: &CtoPoolArc| Ok::<(), io::Error>(()))
+	{
+		Err(CtoPoolOpenError::MissingMigration(0, 1)) => (),
+		unexpected => panic!("expected a missing migration, not {:?}", unexpected),
+	}
+	
+	let cto_pool: CtoPool<u32> = file.open(|root: &mut u32, _cto_pool_arc: &CtoPoolArc| { *root = 0; Ok::<(), io::Error>(()) }).unwrap();
+	assert_eq!(*cto_pool, 21);
+}
+
+#[test]
+fn unreachable_allocation_is_collected_and_reachable_ones_are_kept()
+{
+	let file = VolatileCtoPoolFile::new("collect-leaks", PoolSize).unwrap();
+	
+	let mut cto_pool: CtoPool<CtoVec<CtoBox<u64>>> = file.open(initialize_vec).unwrap();
+	let cto_box = allocate_box(&cto_pool, 5);
+	cto_pool.push(cto_box);
+	assert!(cto_pool.collect_leaks().unwrap().is_empty());
+	
+	let allocated_size = cto_pool.statistics().allocated_size;
+	let leaked = unsafe { cto_pool.alloc().clone().alloc(Layout::from_size_align(1024, 8).unwrap()) }.unwrap();
+	assert!(cto_pool.statistics().allocated_size > allocated_size);
+	
+	let leaks = cto_pool.collect_leaks().unwrap();
+	assert_eq!(leaks.allocations.len(), 1);
+	assert_eq!(leaks.allocations[0].0, leaked as usize);
+	assert!(leaks.allocations[0].1 >= 1024);
+	assert!(leaks.blocks.is_empty());
+	
+	assert_eq!(cto_pool.statistics().allocated_size, allocated_size);
+	assert!(cto_pool.collect_leaks().unwrap().is_empty());
+	assert_eq!(*cto_pool[0], 5);
+}
+
+#[test]
+fn checked_set_and_checked_swap_reject_pointers_outside_the_pool()
+{
+	let file = VolatileCtoPoolFile::new("checked-pointers", PoolSize).unwrap();
+	let other_file = VolatileCtoPoolFile::new("checked-pointers-other", PoolSize).unwrap();
+	
+	let cto_pool: CtoPool<Cells> = file.open(initialize_cells).unwrap();
+	let other_cto_pool: CtoPool<u64> = other_file.open(initialize_seven).unwrap();
+	let cto_arc_cell_location = &cto_pool.cto_arc_cell as *const CtoArcCell<u64> as usize;
+	let cto_atomic_option_location = &cto_pool.cto_atomic_option as *const CtoAtomicOption<u64> as usize;
+	
+	// Only the address of the value is used; it is large enough to hold the counters of a `CtoArc` or the header of a `CtoBox`.
+	let mut on_the_stack = [0u64; 8];
+	let on_the_stack_pointer = on_the_stack.as_mut_ptr();
+	
+	let volatile_cto_arc = unsafe { CtoArc::from_raw(on_the_stack_pointer) };
+	match cto_pool.cto_arc_cell.checked_set(volatile_cto_arc)
+	{
+		Err((error, volatile_cto_arc)) =>
+		{
+			assert_eq!(error, CtoPointerError::VolatileTarget(cto_arc_cell_location, on_the_stack_pointer as usize));
+			forget(volatile_cto_arc);
+		}
+		Ok(_) => panic!("a CtoArc on the stack was stored in the pool"),
+	}
+	
+	let other_cto_arc = allocate_arc(&other_cto_pool, 2);
+	let other_cto_arc_pointer = CtoArc::as_ptr(&other_cto_arc) as usize;
+	match cto_pool.cto_arc_cell.checked_set(other_cto_arc)
+	{
+		Err((error, _other_cto_arc)) => assert_eq!(error, CtoPointerError::DifferentPool(cto_arc_cell_location, other_cto_arc_pointer)),
+		Ok(_) => panic!("a CtoArc in another pool was stored in the pool"),
+	}
+	
+	let volatile_cto_box = unsafe { CtoBox::from_raw(on_the_stack_pointer) };
+	match cto_pool.cto_atomic_option.checked_swap(volatile_cto_box, SeqCst)
+	{
+		Err((error, volatile_cto_box)) =>
+		{
+			assert_eq!(error, CtoPointerError::VolatileTarget(cto_atomic_option_location, on_the_stack_pointer as usize));
+			forget(volatile_cto_box);
+		}
+		Ok(_) => panic!("a CtoBox on the stack was stored in the pool"),
+	}
+	
+	let other_cto_box = allocate_box(&other_cto_pool, 3);
+	let other_cto_box_pointer = CtoBox::as_ptr(&other_cto_box) as usize;
+	match cto_pool.cto_atomic_option.checked_swap(other_cto_box, SeqCst)
+	{
+		Err((error, _other_cto_box)) => assert_eq!(error, CtoPointerError::DifferentPool(cto_atomic_option_location, other_cto_box_pointer)),
+		Ok(_) => panic!("a CtoBox in another pool was stored in the pool"),
+	}
+	
+	// Nothing was stored by the rejected calls; pointers into the same pool are accepted.
+	assert_eq!(*cto_pool.cto_arc_cell.get(), 1);
+	assert!(cto_pool.cto_atomic_option.take(SeqCst).is_none());
+	
+	let previous = cto_pool.cto_arc_cell.checked_set(allocate_arc(&cto_pool, 4)).map_err(|(error, _)| error).unwrap();
+	assert_eq!(*previous, 1);
+	assert_eq!(*cto_pool.cto_arc_cell.get(), 4);
+	
+	let previous = cto_pool.cto_atomic_option.checked_swap(allocate_box(&cto_pool, 5), SeqCst).map_err(|(error, _)| error).unwrap();
+	assert!(previous.is_none());
+	assert_eq!(*cto_pool.cto_atomic_option.take(SeqCst).unwrap(), 5);
+}